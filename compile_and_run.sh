@@ -5,10 +5,8 @@ BASEFILE_CLEAN="${BASEFILE%.*}"
 # remove old files if existant
 rm "$BASEFILE_CLEAN.o"
 rm "$BASEFILE_CLEAN.out"
-# compile C code to object file
-cargo r -- -g $1 || exit 1
-# link Object file to a static binary
-cc "$BASEFILE_CLEAN.o" -o "$BASEFILE_CLEAN.out" -no-pie -static || exit 1
+# compile C code and link it to a static binary
+cargo r -- -g $1 -o "$BASEFILE_CLEAN.out" -static || exit 1
 # run compiled file
 echo "running compiled file:"
 echo "----------------------"
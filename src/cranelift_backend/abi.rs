@@ -0,0 +1,137 @@
+use cranelift::{
+    codegen::ir::ArgumentPurpose,
+    prelude::{isa::CallConv, *},
+};
+
+use crate::mir::{EightbyteClass, MIRType};
+
+/*
+System V x86-64 calling convention for the MIR types, scalars are left to cranelift.
+Aggregates (see EightbyteClass):
+    INTEGER and SSE eightbytes are one I64 or F64 each, in the next free register of that class,
+        if there are not enough registers left for all of them the aggregate goes to memory
+    MEMORY arguments are copied onto the stack by cranelift (StructArgument),
+        the callee gets the address of the copy
+    MEMORY return values are written to a buffer of the caller, its address is a hidden
+        first argument (StructReturn) and cranelift returns it in rax again
+*/
+
+const INTEGER_ARGUMENT_REGISTERS: usize = 6;
+const SSE_ARGUMENT_REGISTERS: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum AbiPassing {
+    /// one cranelift value of that type
+    Scalar(Type),
+    /// the eightbytes of an aggregate, one cranelift value each
+    Eightbytes(Vec<Type>),
+    /// an aggregate in memory, the size rounded up to eightbytes
+    Memory(u32),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct AbiSignature {
+    pub(crate) signature: Signature,
+    pub(crate) params: Vec<AbiPassing>,
+    /// None for void
+    pub(crate) returns: Option<AbiPassing>,
+}
+
+/// the size of the stack memory an aggregate needs, so eightbytes can be loaded and stored whole
+pub(crate) fn eightbyte_rounded_size(mir_type: &MIRType) -> u32 {
+    mir_type.get_size_in_bytes().div_ceil(8) * 8
+}
+
+fn eightbyte_types(eightbytes: &[EightbyteClass; 2]) -> Option<Vec<Type>> {
+    let mut types = vec![];
+    for class in eightbytes {
+        match class {
+            EightbyteClass::NoClass => {}
+            EightbyteClass::Integer => types.push(types::I64),
+            EightbyteClass::Sse => types.push(types::F64),
+            EightbyteClass::Memory => return None,
+        }
+    }
+    Some(types)
+}
+
+fn classify(mir_type: &MIRType) -> AbiPassing {
+    match mir_type {
+        MIRType::Aggregate { eightbytes, .. } => match eightbyte_types(eightbytes) {
+            Some(types) => AbiPassing::Eightbytes(types),
+            None => AbiPassing::Memory(eightbyte_rounded_size(mir_type)),
+        },
+        scalar => AbiPassing::Scalar(scalar.into_cranelift_type()),
+    }
+}
+
+impl AbiSignature {
+    pub(crate) fn new(args: &[MIRType], return_type: Option<&MIRType>) -> Self {
+        let mut signature = Signature::new(CallConv::SystemV);
+        let mut free_integer_registers = INTEGER_ARGUMENT_REGISTERS;
+        let mut free_sse_registers = SSE_ARGUMENT_REGISTERS;
+
+        let returns = return_type.map(classify);
+        match &returns {
+            None => {}
+            Some(AbiPassing::Scalar(scalar)) => signature.returns.push(AbiParam::new(*scalar)),
+            Some(AbiPassing::Eightbytes(eightbytes)) => signature
+                .returns
+                .extend(eightbytes.iter().map(|eightbyte| AbiParam::new(*eightbyte))),
+            Some(AbiPassing::Memory(_)) => {
+                signature
+                    .params
+                    .push(AbiParam::special(types::I64, ArgumentPurpose::StructReturn));
+                free_integer_registers -= 1;
+            }
+        }
+
+        let mut params = vec![];
+        for arg in args {
+            let mut passing = classify(arg);
+            match &passing {
+                AbiPassing::Scalar(scalar) => {
+                    if scalar.is_float() {
+                        free_sse_registers = free_sse_registers.saturating_sub(1);
+                    } else {
+                        free_integer_registers = free_integer_registers.saturating_sub(1);
+                    }
+                }
+                AbiPassing::Eightbytes(eightbytes) => {
+                    let needed_sse = eightbytes.iter().filter(|ty| ty.is_float()).count();
+                    let needed_integer = eightbytes.len() - needed_sse;
+                    if needed_integer <= free_integer_registers && needed_sse <= free_sse_registers
+                    {
+                        free_integer_registers -= needed_integer;
+                        free_sse_registers -= needed_sse;
+                    } else {
+                        passing = AbiPassing::Memory(eightbyte_rounded_size(arg));
+                    }
+                }
+                AbiPassing::Memory(_) => {}
+            }
+
+            match &passing {
+                AbiPassing::Scalar(scalar) => signature.params.push(AbiParam::new(*scalar)),
+                AbiPassing::Eightbytes(eightbytes) => signature
+                    .params
+                    .extend(eightbytes.iter().map(|eightbyte| AbiParam::new(*eightbyte))),
+                AbiPassing::Memory(size) => signature.params.push(AbiParam::special(
+                    types::I64,
+                    ArgumentPurpose::StructArgument(*size),
+                )),
+            }
+            params.push(passing);
+        }
+
+        AbiSignature {
+            signature,
+            params,
+            returns,
+        }
+    }
+
+    pub(crate) fn has_struct_return(&self) -> bool {
+        matches!(self.returns, Some(AbiPassing::Memory(_)))
+    }
+}
@@ -0,0 +1,94 @@
+use cranelift_module::{DataId, FuncOrDataId, Linkage, Module};
+
+use crate::mir::GlobalEntity;
+
+use super::{abi::AbiSignature, CraneliftBackend};
+
+/*
+objects with static storage duration:
+    zero initialized objects go to .bss, initialized ones to .data, const ones to .rodata
+    addresses in the initial bytes are relocations against the symbol, functions are declared
+        with their signature so a later definition matches
+    objects only declared here are imported
+*/
+
+impl CraneliftBackend {
+    pub(crate) fn define_globals(&mut self, globals: &[GlobalEntity]) {
+        // declare everything first, so initializers can refer to any global
+        let mut data_ids: Vec<DataId> = vec![];
+        for global in globals {
+            let (linkage, writable) = match &global.definition {
+                Some(definition) if global.extern_linkage => (Linkage::Export, definition.writable),
+                Some(definition) => (Linkage::Local, definition.writable),
+                None => (Linkage::Import, true),
+            };
+            let data_id = self
+                .module
+                .declare_data(&global.name, linkage, writable, false)
+                .map_err(|e| e.to_string())
+                .unwrap();
+            data_ids.push(data_id);
+        }
+
+        for (global, data_id) in globals.iter().zip(data_ids) {
+            let Some(definition) = &global.definition else {
+                continue;
+            };
+            self.data_ctx.clear();
+            self.data_ctx.set_align(definition.alignment);
+            match &definition.bytes {
+                Some(bytes) => self.data_ctx.define(bytes.clone().into_boxed_slice()),
+                None => self.data_ctx.define_zeroinit(definition.size as usize),
+            }
+            for relocation in &definition.relocations {
+                match &relocation.function {
+                    Some(signature) => {
+                        let func_id = match self.module.get_name(&relocation.symbol) {
+                            Some(FuncOrDataId::Func(func_id)) => func_id,
+                            _ => {
+                                let abi = AbiSignature::new(
+                                    &signature.args,
+                                    signature.return_type.as_ref(),
+                                );
+                                self.module
+                                    .declare_function(
+                                        &relocation.symbol,
+                                        Linkage::Import,
+                                        &abi.signature,
+                                    )
+                                    .map_err(|e| e.to_string())
+                                    .unwrap()
+                            }
+                        };
+                        let func_ref = self
+                            .module
+                            .declare_func_in_data(func_id, &mut self.data_ctx);
+                        self.data_ctx
+                            .write_function_addr(relocation.offset as u32, func_ref);
+                    }
+                    None => {
+                        let target = match self.module.get_name(&relocation.symbol) {
+                            Some(FuncOrDataId::Data(data_id)) => data_id,
+                            _ => self
+                                .module
+                                .declare_data(&relocation.symbol, Linkage::Import, true, false)
+                                .map_err(|e| e.to_string())
+                                .unwrap(),
+                        };
+                        let global_value =
+                            self.module.declare_data_in_data(target, &mut self.data_ctx);
+                        self.data_ctx.write_data_addr(
+                            relocation.offset as u32,
+                            global_value,
+                            relocation.addend,
+                        );
+                    }
+                }
+            }
+            self.module
+                .define_data(data_id, &self.data_ctx)
+                .map_err(|e| e.to_string())
+                .unwrap();
+        }
+    }
+}
@@ -1,4 +1,7 @@
+mod abi;
+mod globals;
 mod helpers;
+mod switch;
 mod translate_function;
 
 use std::error::Error;
@@ -6,10 +9,13 @@ use std::error::Error;
 use log::error;
 
 use cranelift::{
-    codegen::ir::{ConstantData, ConstantPool},
+    codegen::{
+        ir::{ConstantData, ConstantPool},
+        CodegenError,
+    },
     prelude::*,
 };
-use cranelift_module::{DataContext, Linkage, Module};
+use cranelift_module::{DataContext, Linkage, Module, ModuleError};
 use cranelift_object::{ObjectBuilder, ObjectModule};
 
 use crate::{mir::MIRProgramm, parser::parse_nodes::Constant};
@@ -31,6 +37,9 @@ pub struct CraneliftBackend {
     /// The module, with the Object backend, which manages the Object'd
     /// functions.
     module: ObjectModule,
+
+    /// Assembly listing of every defined function, only collected when requested.
+    disassembly: Option<String>,
 }
 
 impl Default for CraneliftBackend {
@@ -51,16 +60,19 @@ impl Default for CraneliftBackend {
             ctx: module.make_context(),
             data_ctx: DataContext::new(),
             module,
+            disassembly: None,
         }
     }
 }
 
 impl CraneliftBackend {
+    pub(crate) fn with_disassembly(mut self) -> Self {
+        self.disassembly = Some(String::new());
+        self
+    }
     /// Compile a string in the toy language into machine code.
-    pub(crate) fn compile(&mut self, input: MIRProgramm) {
-        for global in input.globals {
-            if global.extern_linkage {}
-        }
+    pub(crate) fn compile(&mut self, input: MIRProgramm) -> Result<(), String> {
+        self.define_globals(&input.globals);
         // println!("before func: {}", self.ctx.func);
         for function in &input.functions {
             self.translate_function(function.clone());
@@ -78,13 +90,36 @@ impl CraneliftBackend {
             // cannot finish relocations until all functions to be called are
             // defined. For this toy demo for now, we'll just finalize the
             // function below.
-            let errors = self.module.define_function(id, &mut self.ctx);
-            if let Err(error) = errors {
+            self.ctx.set_disasm(self.disassembly.is_some());
+            if let Err(error) = self.module.define_function(id, &mut self.ctx) {
                 error!("{:?}", error.source());
+                let reason = match error {
+                    ModuleError::Compilation(CodegenError::Verifier(errors)) => {
+                        format!("verifier errors:\n{}", errors.to_string().trim_end())
+                    }
+                    error => error.to_string(),
+                };
+                return Err(format!("cannot compile '{}': {}", function.name, reason));
+            }
+            if let Some(listing) = &mut self.disassembly {
+                let disasm = self
+                    .ctx
+                    .compiled_code()
+                    .and_then(|compiled| compiled.disasm.as_deref())
+                    .unwrap_or_default();
+                listing.push_str(&format!(
+                    "\t.globl {}\n{}:\n{}\n",
+                    function.name, function.name, disasm
+                ));
             }
             // Now that compilation is finished, we can clear out the context state.
             self.module.clear_context(&mut self.ctx);
         }
+        Ok(())
+    }
+    /// the listing collected since `with_disassembly`
+    pub(crate) fn disassembly(&self) -> Option<&str> {
+        self.disassembly.as_deref()
     }
     pub(crate) fn finish(self) -> Vec<u8> {
         // Finalize the functions which we just defined, which resolves any
@@ -0,0 +1,103 @@
+use cranelift::{codegen::ir::JumpTableData, prelude::*};
+
+use super::translate_function::CraneliftFunctionTranslator;
+
+/*
+A switch block compares its value against the case values, as bits of the value type, so they are ordered unsigned:
+    a few cases are compared one after the other
+        cond = value == case_0; brnz cond label_0; jump next ... jump default
+    dense cases, filling at least half of their range, are looked up in a jump table
+        index = value - first; br_table index, default, [label_first, ..., label_last]
+    sparse cases are halved at the middle one until they are few or dense
+        cond = value >= middle; brnz cond upper_half; jump lower_half
+br_table only takes an I32 index, a wider one is checked against the table size first.
+*/
+const COMPARED_CASES: usize = 3;
+
+/// at least half of the values from the first to the last case have a label
+fn is_dense(cases: &[(u64, Block)]) -> bool {
+    let range = cases[cases.len() - 1].0 - cases[0].0;
+    range < 2 * cases.len() as u64
+}
+
+impl CraneliftFunctionTranslator<'_> {
+    /// `cases` are sorted by their value
+    pub(crate) fn translate_switch(
+        &mut self,
+        value: Value,
+        cases: &[(u64, Block)],
+        default: Block,
+    ) {
+        if cases.len() <= COMPARED_CASES {
+            self.translate_case_compares(value, cases, default);
+        } else if is_dense(cases) {
+            self.translate_jump_table(value, cases, default);
+        } else {
+            let (lower_cases, upper_cases) = cases.split_at(cases.len() / 2);
+            let is_upper = self.func_builder.ins().icmp_imm(
+                IntCC::UnsignedGreaterThanOrEqual,
+                value,
+                upper_cases[0].0 as i64,
+            );
+            let upper_block = self.func_builder.create_block();
+            let lower_block = self.func_builder.create_block();
+            self.func_builder.ins().brnz(is_upper, upper_block, &[]);
+            self.func_builder.ins().jump(lower_block, &[]);
+
+            self.func_builder.switch_to_block(lower_block);
+            self.translate_switch(value, lower_cases, default);
+            self.func_builder.switch_to_block(upper_block);
+            self.translate_switch(value, upper_cases, default);
+        }
+    }
+
+    fn translate_case_compares(&mut self, value: Value, cases: &[(u64, Block)], default: Block) {
+        for (case_value, case_block) in cases {
+            let is_case = self
+                .func_builder
+                .ins()
+                .icmp_imm(IntCC::Equal, value, *case_value as i64);
+            let next_block = self.func_builder.create_block();
+            self.func_builder.ins().brnz(is_case, *case_block, &[]);
+            self.func_builder.ins().jump(next_block, &[]);
+            self.func_builder.switch_to_block(next_block);
+        }
+        self.func_builder.ins().jump(default, &[]);
+    }
+
+    fn translate_jump_table(&mut self, value: Value, cases: &[(u64, Block)], default: Block) {
+        let first = cases[0].0;
+        let last = cases[cases.len() - 1].0;
+
+        // the values without a label go to default
+        let mut jump_table = JumpTableData::new();
+        let mut next_entry = first;
+        for (case_value, case_block) in cases {
+            while next_entry != *case_value {
+                jump_table.push_entry(default);
+                next_entry = next_entry.wrapping_add(1);
+            }
+            jump_table.push_entry(*case_block);
+            next_entry = next_entry.wrapping_add(1);
+        }
+        let jump_table = self.func_builder.create_jump_table(jump_table);
+
+        let mut index = self
+            .func_builder
+            .ins()
+            .iadd_imm(value, (first as i64).wrapping_neg());
+        if self.func_builder.func.dfg.value_type(index) != types::I32 {
+            let is_outside = self.func_builder.ins().icmp_imm(
+                IntCC::UnsignedGreaterThan,
+                index,
+                (last - first) as i64,
+            );
+            let inside_block = self.func_builder.create_block();
+            self.func_builder.ins().brnz(is_outside, default, &[]);
+            self.func_builder.ins().jump(inside_block, &[]);
+            self.func_builder.switch_to_block(inside_block);
+            index = self.func_builder.ins().ireduce(types::I32, index);
+        }
+        self.func_builder.ins().br_table(index, default, jump_table);
+    }
+}
@@ -6,16 +6,19 @@ use std::{
 
 use cranelift::{
     codegen::ir::{Constant, ConstantData, ConstantPool, StackSlot},
-    prelude::{isa::CallConv, *},
+    prelude::*,
 };
 
-use cranelift_module::{DataContext, Linkage, Module};
+use cranelift_module::{DataContext, FuncOrDataId, Linkage, Module};
 use cranelift_object::ObjectModule;
 use log::{debug, info};
 
 use crate::mir::{MIRBlock, MIRFunction, MIRInstruction, MIRType, MIRValue};
 
-use super::CraneliftBackend;
+use super::{
+    abi::{eightbyte_rounded_size, AbiPassing, AbiSignature},
+    CraneliftBackend,
+};
 
 impl MIRType {
     pub(crate) fn into_cranelift_type(&self) -> Type {
@@ -28,6 +31,9 @@ impl MIRType {
             MIRType::I32 => types::I32,
             MIRType::U64 => types::I64,
             MIRType::I64 => types::I64,
+            MIRType::Aggregate { .. } => {
+                panic!("aggregates have no cranelift type, they live in memory")
+            }
         }
     }
 }
@@ -37,22 +43,10 @@ impl CraneliftBackend {
         info!("translating function: {}", input.name);
 
         self.ctx.func.clear();
-        self.ctx.func.signature.call_conv = CallConv::SystemV;
-
-        // add signature
-        for p in &input.signature.args {
-            self.ctx
-                .func
-                .signature
-                .params
-                .push(AbiParam::new(p.into_cranelift_type()));
-        }
 
-        // Our toy language currently only supports one return value, though
-        // Cranelift is designed to support more.
-        self.ctx.func.signature.returns.push(AbiParam::new(
-            input.signature.return_type.into_cranelift_type(),
-        ));
+        // add signature, aggregates are split up or passed in memory
+        let abi = AbiSignature::new(&input.signature.args, input.signature.return_type.as_ref());
+        self.ctx.func.signature = abi.signature.clone();
 
         // Create the builder to build a function.
         let mut builder = FunctionBuilder::new(&mut self.ctx.func, &mut self.builder_context);
@@ -75,18 +69,52 @@ impl CraneliftBackend {
         // var map for later
         let mut var_stack_slot_map: HashMap<String, StackSlot> = HashMap::new();
         let mut var_idx_counter = 0;
+        let block_params = builder.block_params(entry_block).to_vec();
+        let mut block_params = block_params.into_iter();
+        let struct_return = if abi.has_struct_return() {
+            block_params.next()
+        } else {
+            None
+        };
         // register function parameters as variables
-        for (param_idx, (param, param_type)) in input
+        for ((param, param_type), passing) in input
             .parameter_names
             .iter()
             .zip(input.signature.args.iter())
-            .enumerate()
+            .zip(abi.params.iter())
         {
-            let val = builder.block_params(entry_block)[param_idx];
-            let data =
-                StackSlotData::new(StackSlotKind::ExplicitSlot, param_type.get_size_in_bytes());
-            let var_slot = builder.create_sized_stack_slot(data);
-            builder.ins().stack_store(val, var_slot, 0);
+            let var_slot = builder.create_sized_stack_slot(StackSlotData::new(
+                StackSlotKind::ExplicitSlot,
+                eightbyte_rounded_size(param_type),
+            ));
+            match passing {
+                AbiPassing::Scalar(_) => {
+                    builder
+                        .ins()
+                        .stack_store(block_params.next().unwrap(), var_slot, 0);
+                }
+                AbiPassing::Eightbytes(eightbytes) => {
+                    for offset in (0..eightbytes.len()).map(|idx| idx as i32 * 8) {
+                        builder
+                            .ins()
+                            .stack_store(block_params.next().unwrap(), var_slot, offset);
+                    }
+                }
+                AbiPassing::Memory(_) => {
+                    let pointer = self.module.target_config().pointer_type();
+                    let slot_addr = builder.ins().stack_addr(pointer, var_slot, 0);
+                    builder.emit_small_memory_copy(
+                        self.module.target_config(),
+                        slot_addr,
+                        block_params.next().unwrap(),
+                        param_type.get_size_in_bytes() as u64,
+                        8,
+                        8,
+                        true,
+                        MemFlags::new(),
+                    );
+                }
+            }
             if let std::collections::hash_map::Entry::Vacant(e) =
                 var_stack_slot_map.entry(param.to_string())
             {
@@ -117,6 +145,8 @@ impl CraneliftBackend {
             var_stack_slot_map: &mut var_stack_slot_map,
             block_map: HashMap::new(),
             value_trans_map: BTreeMap::new(),
+            abi,
+            struct_return,
         };
 
         // initialize all blocks
@@ -150,6 +180,10 @@ pub(crate) struct CraneliftFunctionTranslator<'a> {
     pub(crate) var_stack_slot_map: &'a mut HashMap<String, StackSlot>,
     pub(crate) block_map: HashMap<usize, Block>,
     pub(crate) value_trans_map: BTreeMap<MIRValue, Value>,
+    /// how the parameters and the return value of the translated function are passed
+    pub(crate) abi: AbiSignature,
+    /// the caller's buffer for a returned aggregate in memory
+    pub(crate) struct_return: Option<Value>,
 }
 impl CraneliftFunctionTranslator<'_> {
     pub(crate) fn translate_block(
@@ -187,6 +221,21 @@ impl CraneliftFunctionTranslator<'_> {
         if let Some(branches) = &current_block.branches {
             debug!("adding branching to func/block: {}", self.func_builder.func);
             let cond_value = self.mir_value_to_cranelift_value(branches.0);
+            if current_block.is_switch {
+                let mut cases: Vec<(u64, Block)> = vec![];
+                let mut default = None;
+                for branch in &branches.1 {
+                    let to_block = *self.block_map.get(&branch.to_block).unwrap();
+                    if branch.is_default {
+                        default = Some(to_block);
+                    } else {
+                        cases.push((branch.value_needed, to_block));
+                    }
+                }
+                cases.sort_by_key(|(case_value, _)| *case_value);
+                self.translate_switch(cond_value, &cases, default.unwrap());
+                return;
+            }
             for branch in &branches.1 {
                 debug!("adding branching jump: {:?}", branch);
                 if branch.is_default {
@@ -304,9 +353,39 @@ impl CraneliftFunctionTranslator<'_> {
                         self.func_builder.ins().imul(left_value, right_value)
                     }
                     crate::mir::IntMathKind::Div => {
-                        self.func_builder.ins().sdiv(left_value, right_value)
-                    } // TODO: this stuff needs to be signed dependent
-                    crate::mir::IntMathKind::Mod => todo!(),
+                        if self.mir_function.value_type_map[&result_mir_value].is_signed() {
+                            self.func_builder.ins().sdiv(left_value, right_value)
+                        } else {
+                            self.func_builder.ins().udiv(left_value, right_value)
+                        }
+                    }
+                    crate::mir::IntMathKind::Mod => {
+                        if self.mir_function.value_type_map[&result_mir_value].is_signed() {
+                            self.func_builder.ins().srem(left_value, right_value)
+                        } else {
+                            self.func_builder.ins().urem(left_value, right_value)
+                        }
+                    }
+                    crate::mir::IntMathKind::And => {
+                        self.func_builder.ins().band(left_value, right_value)
+                    }
+                    crate::mir::IntMathKind::Or => {
+                        self.func_builder.ins().bor(left_value, right_value)
+                    }
+                    crate::mir::IntMathKind::Xor => {
+                        self.func_builder.ins().bxor(left_value, right_value)
+                    }
+                    crate::mir::IntMathKind::Shl => {
+                        self.func_builder.ins().ishl(left_value, right_value)
+                    }
+                    crate::mir::IntMathKind::Shr => {
+                        if self.mir_function.value_type_map[&result_mir_value].is_signed() {
+                            self.func_builder.ins().sshr(left_value, right_value)
+                        } else {
+                            self.func_builder.ins().ushr(left_value, right_value)
+                        }
+                    }
+                    crate::mir::IntMathKind::Not => self.func_builder.ins().bnot(left_value),
                 }; //;
                 self.insert_value_trans_pair(result_mir_value, math_res_value);
             }
@@ -319,55 +398,133 @@ impl CraneliftFunctionTranslator<'_> {
                 // direct call to function with known direct name
 
                 // make a new signature for the function call
-                let mut sig = self.module.make_signature();
-
-                // finish the signature with type
-                if !signature_of_function.overloadable {
-                    for p in signature_of_function.args {
-                        sig.params.push(AbiParam::new(p.into_cranelift_type()));
-                    }
+                let arg_types = if !signature_of_function.overloadable {
+                    signature_of_function.args.clone()
                 } else {
-                    for parg in &arg_values {
-                        // for the value type of each argument get the type
-                        sig.params.push(AbiParam::new(
-                            self.mir_function
+                    // for the value type of each argument get the type
+                    arg_values
+                        .iter()
+                        .map(|parg| {
+                            *self
+                                .mir_function
                                 .value_type_map
                                 .get(parg)
                                 .unwrap_or_else(|| panic!("no MIRType for MIRValue: {:?}", parg))
-                                .into_cranelift_type(),
-                        ));
-                    }
-                }
-
-                // return type of called function
-                sig.returns.push(AbiParam::new(
-                    signature_of_function.return_type.into_cranelift_type(),
-                ));
+                        })
+                        .collect()
+                };
+                let return_type = signature_of_function.return_type;
+                let abi = AbiSignature::new(&arg_types, return_type.as_ref());
 
+                // a variadic function is declared once with its fixed parameters, every call
+                // goes through its address with the signature of that call
+                let declared_sig =
+                    AbiSignature::new(&signature_of_function.args, return_type.as_ref()).signature;
                 let callee = self
                     .module
-                    .declare_function(&func_name, Linkage::Export, &sig)
+                    .declare_function(&func_name, Linkage::Export, &declared_sig)
                     .map_err(|e| e.to_string())
                     .unwrap();
                 let local_callee = self
                     .module
                     .declare_func_in_func(callee, self.func_builder.func);
 
-                // generate the Cranelift Values for the call
+                // generate the Cranelift Values for the call, a returned aggregate in memory goes
+                // to a new stack temporary
                 let mut call_args = vec![];
-                for mir_arg_value in arg_values {
+                let struct_return = if abi.has_struct_return() {
+                    let return_buffer = self.make_stack_temporary(&return_type.unwrap());
+                    call_args.push(return_buffer);
+                    Some(return_buffer)
+                } else {
+                    None
+                };
+                for (mir_arg_value, passing) in arg_values.into_iter().zip(abi.params.iter()) {
                     let cranelift_value = self.mir_value_to_cranelift_value(mir_arg_value);
-                    call_args.push(cranelift_value);
+                    match passing {
+                        AbiPassing::Scalar(_) | AbiPassing::Memory(_) => {
+                            call_args.push(cranelift_value)
+                        }
+                        AbiPassing::Eightbytes(eightbytes) => {
+                            for (idx, eightbyte) in eightbytes.iter().enumerate() {
+                                call_args.push(self.func_builder.ins().load(
+                                    *eightbyte,
+                                    MemFlags::new(),
+                                    cranelift_value,
+                                    idx as i32 * 8,
+                                ));
+                            }
+                        }
+                    }
                 }
 
                 // call the function
-                let call = self.func_builder.ins().call(local_callee, &call_args);
-                let cranelift_return_value = self.func_builder.inst_results(call)[0];
+                let call = if signature_of_function.overloadable {
+                    let callee_address =
+                        self.func_builder.ins().func_addr(types::I64, local_callee);
+                    let call_sig = self.func_builder.import_signature(abi.signature.clone());
+                    self.func_builder
+                        .ins()
+                        .call_indirect(call_sig, callee_address, &call_args)
+                } else {
+                    self.func_builder.ins().call(local_callee, &call_args)
+                };
+                let results = self.func_builder.inst_results(call).to_vec();
+                let cranelift_return_value = match (&abi.returns, struct_return) {
+                    // the void value is never read, it only needs a definition
+                    (None, _) => self.func_builder.ins().iconst(types::I32, 0),
+                    (Some(AbiPassing::Scalar(_)), _) => results[0],
+                    (Some(AbiPassing::Eightbytes(_)), _) => {
+                        let return_buffer = self.make_stack_temporary(&return_type.unwrap());
+                        for (idx, result) in results.into_iter().enumerate() {
+                            self.func_builder.ins().store(
+                                MemFlags::new(),
+                                result,
+                                return_buffer,
+                                idx as i32 * 8,
+                            );
+                        }
+                        return_buffer
+                    }
+                    (Some(AbiPassing::Memory(_)), return_buffer) => return_buffer.unwrap(),
+                };
                 self.insert_value_trans_pair(mir_return_value, cranelift_return_value);
             }
-            MIRInstruction::Return(mir_value) => {
+            MIRInstruction::Return(None) => {
+                self.func_builder.ins().return_(&[]);
+            }
+            MIRInstruction::Return(Some(mir_value)) => {
                 let cranelift_value = self.mir_value_to_cranelift_value(mir_value);
-                self.func_builder.ins().return_(&[cranelift_value]);
+                match self.abi.returns.clone().unwrap() {
+                    AbiPassing::Scalar(_) => {
+                        self.func_builder.ins().return_(&[cranelift_value]);
+                    }
+                    AbiPassing::Eightbytes(eightbytes) => {
+                        let return_values: Vec<Value> = eightbytes
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, eightbyte)| {
+                                self.func_builder.ins().load(
+                                    *eightbyte,
+                                    MemFlags::new(),
+                                    cranelift_value,
+                                    idx as i32 * 8,
+                                )
+                            })
+                            .collect();
+                        self.func_builder.ins().return_(&return_values);
+                    }
+                    AbiPassing::Memory(_) => {
+                        let return_type = self.mir_function.signature.return_type.unwrap();
+                        self.copy_memory(
+                            self.struct_return.unwrap(),
+                            cranelift_value,
+                            &return_type,
+                        );
+                        // cranelift hands the buffer address back itself
+                        self.func_builder.ins().return_(&[]);
+                    }
+                }
             }
             MIRInstruction::Compare(compare_result, left_mir_value, right_mir_value, cmp_kind) => {
                 let left_value = self.mir_value_to_cranelift_value(left_mir_value);
@@ -380,6 +537,10 @@ impl CraneliftFunctionTranslator<'_> {
                         crate::mir::IntCmpKind::LT => IntCC::SignedLessThan,
                         crate::mir::IntCmpKind::GET => IntCC::SignedGreaterThanOrEqual,
                         crate::mir::IntCmpKind::LET => IntCC::SignedLessThanOrEqual,
+                        crate::mir::IntCmpKind::UnsignedGT => IntCC::UnsignedGreaterThan,
+                        crate::mir::IntCmpKind::UnsignedLT => IntCC::UnsignedLessThan,
+                        crate::mir::IntCmpKind::UnsignedGET => IntCC::UnsignedGreaterThanOrEqual,
+                        crate::mir::IntCmpKind::UnsignedLET => IntCC::UnsignedLessThanOrEqual,
                     },
                     left_value,
                     right_value,
@@ -408,6 +569,26 @@ impl CraneliftFunctionTranslator<'_> {
                 );
                 self.insert_value_trans_pair(ouput_result, ref_value);
             }
+            MIRInstruction::GetAddrOfGlobal(output_value, symbol) => {
+                let pointer = self.module.target_config().pointer_type();
+                // globals are declared before any function is translated
+                let address = match self.module.get_name(&symbol) {
+                    Some(FuncOrDataId::Data(data_id)) => {
+                        let local_id = self
+                            .module
+                            .declare_data_in_func(data_id, self.func_builder.func);
+                        self.func_builder.ins().symbol_value(pointer, local_id)
+                    }
+                    Some(FuncOrDataId::Func(func_id)) => {
+                        let local_id = self
+                            .module
+                            .declare_func_in_func(func_id, self.func_builder.func);
+                        self.func_builder.ins().func_addr(pointer, local_id)
+                    }
+                    None => panic!("global '{}' was never declared", symbol),
+                };
+                self.insert_value_trans_pair(output_value, address);
+            }
             MIRInstruction::Deref(output_res, value_to_deref, wanted_deref_type) => {
                 let cranelift_value_to_deref = self.mir_value_to_cranelift_value(value_to_deref);
                 let value = self.func_builder.ins().load(
@@ -418,82 +599,61 @@ impl CraneliftFunctionTranslator<'_> {
                 );
                 self.insert_value_trans_pair(output_res, value);
             }
+            MIRInstruction::StoreAtAddr(addr, value_to_store, _provided_type) => {
+                let cranelift_addr = self.mir_value_to_cranelift_value(addr);
+                let cranelift_value = self.mir_value_to_cranelift_value(value_to_store);
+                self.func_builder
+                    .ins()
+                    .store(MemFlags::new(), cranelift_value, cranelift_addr, 0);
+            }
             MIRInstruction::IntConvert(output_value, input_value, target_type) => {
                 let cranelift_input_value = self.mir_value_to_cranelift_value(input_value);
                 let current_type = self.mir_function.value_type_map.get(&input_value).unwrap();
 
-                let cranelift_ouput_value = match (&current_type, &target_type) {
-                    (MIRType::U8, MIRType::U8) => cranelift_input_value,
-                    (MIRType::U8, MIRType::I8) => todo!(),
-                    (MIRType::U8, MIRType::U16) => todo!(),
-                    (MIRType::U8, MIRType::I16) => todo!(),
-                    (MIRType::U8, MIRType::U32) => todo!(),
-                    (MIRType::U8, MIRType::I32) => todo!(),
-                    (MIRType::U8, MIRType::U64) => todo!(),
-                    (MIRType::U8, MIRType::I64) => todo!(),
-                    (MIRType::I8, MIRType::U8) => todo!(),
-                    (MIRType::I8, MIRType::I8) => cranelift_input_value,
-                    (MIRType::I8, MIRType::U16) => todo!(),
-                    (MIRType::I8, MIRType::I16) => todo!(),
-                    (MIRType::I8, MIRType::U32) => todo!(),
-                    (MIRType::I8, MIRType::I32) => todo!(),
-                    (MIRType::I8, MIRType::U64) => todo!(),
-                    (MIRType::I8, MIRType::I64) => todo!(),
-                    (MIRType::U16, MIRType::U8) => todo!(),
-                    (MIRType::U16, MIRType::I8) => todo!(),
-                    (MIRType::U16, MIRType::U16) => cranelift_input_value,
-                    (MIRType::U16, MIRType::I16) => todo!(),
-                    (MIRType::U16, MIRType::U32) => todo!(),
-                    (MIRType::U16, MIRType::I32) => todo!(),
-                    (MIRType::U16, MIRType::U64) => todo!(),
-                    (MIRType::U16, MIRType::I64) => todo!(),
-                    (MIRType::I16, MIRType::U8) => todo!(),
-                    (MIRType::I16, MIRType::I8) => todo!(),
-                    (MIRType::I16, MIRType::U16) => todo!(),
-                    (MIRType::I16, MIRType::I16) => cranelift_input_value,
-                    (MIRType::I16, MIRType::U32) => todo!(),
-                    (MIRType::I16, MIRType::I32) => todo!(),
-                    (MIRType::I16, MIRType::U64) => todo!(),
-                    (MIRType::I16, MIRType::I64) => todo!(),
-                    (MIRType::U32, MIRType::U8) => todo!(),
-                    (MIRType::U32, MIRType::I8) => todo!(),
-                    (MIRType::U32, MIRType::U16) => todo!(),
-                    (MIRType::U32, MIRType::I16) => todo!(),
-                    (MIRType::U32, MIRType::U32) => cranelift_input_value,
-                    (MIRType::U32, MIRType::I32) => todo!(),
-                    (MIRType::U32, MIRType::U64) => todo!(),
-                    (MIRType::U32, MIRType::I64) => todo!(),
-                    (MIRType::I32, MIRType::U8) => todo!(),
-                    (MIRType::I32, MIRType::I8) => todo!(),
-                    (MIRType::I32, MIRType::U16) => todo!(),
-                    (MIRType::I32, MIRType::I16) => todo!(),
-                    (MIRType::I32, MIRType::U32) => todo!(),
-                    (MIRType::I32, MIRType::I32) => cranelift_input_value,
-                    (MIRType::I32, MIRType::U64) => todo!(),
-                    (MIRType::I32, MIRType::I64) => self
-                        .func_builder
-                        .ins()
-                        .sextend(target_type.into_cranelift_type(), cranelift_input_value),
-                    (MIRType::U64, MIRType::U8) => todo!(),
-                    (MIRType::U64, MIRType::I8) => todo!(),
-                    (MIRType::U64, MIRType::U16) => todo!(),
-                    (MIRType::U64, MIRType::I16) => todo!(),
-                    (MIRType::U64, MIRType::U32) => todo!(),
-                    (MIRType::U64, MIRType::I32) => todo!(),
-                    (MIRType::U64, MIRType::U64) => cranelift_input_value,
-                    (MIRType::U64, MIRType::I64) => todo!(),
-                    (MIRType::I64, MIRType::U8) => todo!(),
-                    (MIRType::I64, MIRType::I8) => todo!(),
-                    (MIRType::I64, MIRType::U16) => todo!(),
-                    (MIRType::I64, MIRType::I16) => todo!(),
-                    (MIRType::I64, MIRType::U32) => todo!(),
-                    (MIRType::I64, MIRType::I32) => todo!(),
-                    (MIRType::I64, MIRType::U64) => todo!(),
-                    (MIRType::I64, MIRType::I64) => cranelift_input_value,
-                };
+                let current_size = current_type.get_size_in_bytes();
+                let target_size = target_type.get_size_in_bytes();
+                let cranelift_ouput_value =
+                    if current_type.is_aggregate() || target_type.is_aggregate() {
+                        panic!("aggregates can not be converted")
+                    } else if current_size == target_size {
+                        // same bits, only the signedness changes
+                        cranelift_input_value
+                    } else if current_size > target_size {
+                        self.func_builder
+                            .ins()
+                            .ireduce(target_type.into_cranelift_type(), cranelift_input_value)
+                    } else if current_type.is_signed() {
+                        self.func_builder
+                            .ins()
+                            .sextend(target_type.into_cranelift_type(), cranelift_input_value)
+                    } else {
+                        self.func_builder
+                            .ins()
+                            .uextend(target_type.into_cranelift_type(), cranelift_input_value)
+                    };
 
                 self.insert_value_trans_pair(output_value, cranelift_ouput_value);
             }
+            MIRInstruction::StackAlloc(output_value, allocated_type) => {
+                let address = self.make_stack_temporary(&allocated_type);
+                self.insert_value_trans_pair(output_value, address);
+            }
+            MIRInstruction::CopyMemory(destination, source, copied_type) => {
+                let destination = self.mir_value_to_cranelift_value(destination);
+                let source = self.mir_value_to_cranelift_value(source);
+                self.copy_memory(destination, source, &copied_type);
+            }
+            MIRInstruction::ZeroMemory(destination, zeroed_type) => {
+                let destination = self.mir_value_to_cranelift_value(destination);
+                self.func_builder.emit_small_memset(
+                    self.module.target_config(),
+                    destination,
+                    0,
+                    zeroed_type.get_size_in_bytes() as u64,
+                    1,
+                    MemFlags::new(),
+                );
+            }
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -501,6 +661,34 @@ impl CraneliftFunctionTranslator<'_> {
 }
 
 impl CraneliftFunctionTranslator<'_> {
+    /// address of a new stack slot for `mir_type`, rounded up to whole eightbytes
+    pub(crate) fn make_stack_temporary(&mut self, mir_type: &MIRType) -> Value {
+        let slot = self
+            .func_builder
+            .create_sized_stack_slot(StackSlotData::new(
+                StackSlotKind::ExplicitSlot,
+                eightbyte_rounded_size(mir_type),
+            ));
+        self.func_builder
+            .ins()
+            .stack_addr(self.module.target_config().pointer_type(), slot, 0)
+    }
+    pub(crate) fn copy_memory(&mut self, destination: Value, source: Value, mir_type: &MIRType) {
+        let alignment = match mir_type {
+            MIRType::Aggregate { alignment, .. } => (*alignment).min(8) as u8,
+            scalar => scalar.get_size_in_bytes() as u8,
+        };
+        self.func_builder.emit_small_memory_copy(
+            self.module.target_config(),
+            destination,
+            source,
+            mir_type.get_size_in_bytes() as u64,
+            alignment,
+            alignment,
+            true,
+            MemFlags::new(),
+        );
+    }
     pub(crate) fn mir_value_to_cranelift_value(&self, mir_value: MIRValue) -> Value {
         *self.value_trans_map.get(&mir_value).unwrap()
     }
@@ -1,123 +1,1015 @@
-use log::debug;
+use crate::{
+    mir::MIRConstant,
+    parser::{
+        parse_nodes::{
+            declarations::{DeclarationSpecifiers, Initializer},
+            expressions::{
+                AdditiveOperator, CExpression, ConstantExpression, EqualityOperator,
+                GenericAssociation, MultiplicativeOperator, RelationalOperator, ShiftOperator,
+                UnaryOperator,
+            },
+            Constant, NumberLike,
+        },
+        span::{Span, Spanned},
+    },
+};
+
+use super::{
+    ext_type::{ExtType, PrettyType},
+    CompileTimeValue, EnvironmentController,
+};
+
+/*
+(6.6) constant expressions are evaluated with the types C gives them:
+    every integer is kept wrapped to the width and signedness of its type,
+    operands go through the integer promotions and usual arithmetic conversions first
+    address constants are a symbol plus a byte offset, only file scope objects and functions have one
+    the operand that is not evaluated, like the unselected side of ?:, does not need to be constant
+*/
+
+/// a compile time value with its C type
+#[derive(Debug, Clone)]
+struct TypedValue {
+    value: CompileTimeValue,
+    value_type: ExtType,
+}
+
+fn int_type(signed: bool, size: u64) -> ExtType {
+    ExtType::Int {
+        is_const: false,
+        is_volatile: false,
+        signed,
+        size,
+    }
+}
+
+fn size_t() -> ExtType {
+    int_type(false, 8)
+}
+
+/// `value` truncated to the width of the integer or pointer type, sign extended if signed
+fn wrap_to_type(value: i128, value_type: &ExtType) -> i128 {
+    let (signed, size) = match value_type {
+        ExtType::Int { signed, size, .. } => (*signed, *size),
+        ExtType::Pointer { .. } => (false, 8),
+        _ => return value,
+    };
+    let bits = size * 8;
+    let truncated = value & ((1i128 << bits) - 1);
+    if signed && (truncated >> (bits - 1)) & 1 == 1 {
+        truncated - (1i128 << bits)
+    } else {
+        truncated
+    }
+}
 
-use crate::parser::parse_nodes::expressions::{CExpression, ConstantExpression};
+fn int_value(value: i128, value_type: ExtType) -> TypedValue {
+    TypedValue {
+        value: CompileTimeValue::Int(wrap_to_type(value, &value_type)),
+        value_type,
+    }
+}
+
+/// like int_value for the result of arithmetic, (6.6) a signed result that does not fit is
+/// undefined behavior (6.5), so no constant expression
+fn arithmetic_result(value: i128, value_type: ExtType) -> Result<TypedValue, String> {
+    if matches!(value_type, ExtType::Int { signed: true, .. })
+        && wrap_to_type(value, &value_type) != value
+    {
+        return Err("integer overflow in constant expression".to_string());
+    }
+    Ok(int_value(value, value_type))
+}
+
+fn bool_value(value: bool) -> TypedValue {
+    int_value(value as i128, int_type(true, 4))
+}
 
-use super::{CompileTimeValue, EnvironmentController};
+/// (6.3.1.1) everything smaller than int becomes int
+pub(crate) fn promoted(value_type: &ExtType) -> ExtType {
+    match value_type {
+        ExtType::Int { size, .. } if *size < 4 => int_type(true, 4),
+        other => other.clone().unqualified(),
+    }
+}
+
+/// (6.3.1.8) the common type of two arithmetic operands
+pub(crate) fn usual_arithmetic_conversion(left: &ExtType, right: &ExtType) -> ExtType {
+    match (promoted(left), promoted(right)) {
+        (
+            ExtType::Float {
+                size: left_size, ..
+            },
+            ExtType::Float {
+                size: right_size, ..
+            },
+        ) => ExtType::Float {
+            is_const: false,
+            is_volatile: false,
+            size: left_size.max(right_size),
+        },
+        (float @ ExtType::Float { .. }, _) | (_, float @ ExtType::Float { .. }) => float,
+        (
+            ExtType::Int {
+                signed: left_signed,
+                size: left_size,
+                ..
+            },
+            ExtType::Int {
+                signed: right_signed,
+                size: right_size,
+                ..
+            },
+        ) => {
+            if left_signed == right_signed {
+                int_type(left_signed, left_size.max(right_size))
+            } else {
+                let (unsigned_size, signed_size) = if left_signed {
+                    (right_size, left_size)
+                } else {
+                    (left_size, right_size)
+                };
+                if unsigned_size >= signed_size {
+                    int_type(false, unsigned_size)
+                } else {
+                    int_type(true, signed_size)
+                }
+            }
+        }
+        (left, _) => left,
+    }
+}
+
+fn is_arithmetic(value_type: &ExtType) -> bool {
+    matches!(value_type, ExtType::Int { .. } | ExtType::Float { .. })
+}
+
+/// (6.3.2.1) arrays and functions become pointers to them
+fn decayed(value: TypedValue) -> TypedValue {
+    TypedValue {
+        value: value.value,
+        value_type: value.value_type.decayed(),
+    }
+}
+
+/// (6.3) `value` as if cast to `target_type`
+fn converted(value: TypedValue, target_type: &ExtType) -> Result<TypedValue, String> {
+    let target_type = target_type.clone().unqualified();
+    let converted_value = match (&target_type, value.value) {
+        (ExtType::Int { .. } | ExtType::Pointer { .. }, CompileTimeValue::Int(int)) => {
+            CompileTimeValue::Int(wrap_to_type(int, &target_type))
+        }
+        (ExtType::Int { .. } | ExtType::Pointer { .. }, CompileTimeValue::Float(float)) => {
+            if !float.is_finite() {
+                return Err("conversion of a non finite floating value to an integer".to_string());
+            }
+            CompileTimeValue::Int(wrap_to_type(float.trunc() as i128, &target_type))
+        }
+        (ExtType::Float { size, .. }, CompileTimeValue::Int(int)) => {
+            CompileTimeValue::Float(rounded_float(int as f64, *size))
+        }
+        (ExtType::Float { size, .. }, CompileTimeValue::Float(float)) => {
+            CompileTimeValue::Float(rounded_float(float, *size))
+        }
+        (ExtType::Int { size: 8, .. } | ExtType::Pointer { .. }, address) => address,
+        (ExtType::Void, other) => other,
+        (_, CompileTimeValue::Address { .. } | CompileTimeValue::String(_)) => {
+            return Err("an address does not fit into that type".to_string())
+        }
+        _ => return Err(format!("cannot convert to {:?}", target_type)),
+    };
+    Ok(TypedValue {
+        value: converted_value,
+        value_type: target_type,
+    })
+}
+
+fn rounded_float(value: f64, size: u64) -> f64 {
+    if size == 4 {
+        value as f32 as f64
+    } else {
+        value
+    }
+}
+
+fn truthiness(value: &TypedValue) -> Result<bool, String> {
+    match &value.value {
+        CompileTimeValue::Int(int) => Ok(*int != 0),
+        CompileTimeValue::Float(float) => Ok(*float != 0.0),
+        // the address of an object is never null
+        CompileTimeValue::Address { .. } | CompileTimeValue::String(_) => Ok(true),
+    }
+}
+
+fn expect_int(value: &TypedValue) -> Result<i128, String> {
+    match value.value {
+        CompileTimeValue::Int(int) => Ok(int),
+        _ => Err("operand needs to be an integer".to_string()),
+    }
+}
+
+/// both operands converted to their common type
+fn arithmetic_operands(
+    left: TypedValue,
+    right: TypedValue,
+) -> Result<(TypedValue, TypedValue, ExtType), String> {
+    if !is_arithmetic(&left.value_type) || !is_arithmetic(&right.value_type) {
+        return Err("operands need to be arithmetic".to_string());
+    }
+    let common_type = usual_arithmetic_conversion(&left.value_type, &right.value_type);
+    Ok((
+        converted(left, &common_type)?,
+        converted(right, &common_type)?,
+        common_type,
+    ))
+}
+
+/// (6.4.4.1) the type of an integer constant is the first of its list its value fits in
+fn number_constant(number: &NumberLike) -> Result<TypedValue, String> {
+    let lowercase = number.from.to_ascii_lowercase();
+    let is_hex = lowercase.starts_with("0x");
+    if !is_hex && (lowercase.contains('.') || lowercase.contains('e')) {
+        let is_float = lowercase.ends_with('f');
+        let digits = lowercase.trim_end_matches(['f', 'l']);
+        let value = digits
+            .parse::<f64>()
+            .map_err(|_| format!("invalid floating constant '{}'", number.from))?;
+        let size = if is_float { 4 } else { 8 };
+        return Ok(TypedValue {
+            value: CompileTimeValue::Float(rounded_float(value, size)),
+            value_type: ExtType::Float {
+                is_const: false,
+                is_volatile: false,
+                size,
+            },
+        });
+    }
+
+    let value = number
+        .to_integer()
+        .ok_or_else(|| format!("invalid integer constant '{}'", number.from))?;
+    let suffix = &lowercase[lowercase.trim_end_matches(['u', 'l']).len()..];
+    let is_unsigned = suffix.contains('u');
+    let is_long = suffix.contains('l');
+    let is_decimal = !(is_hex || lowercase.starts_with("0b") || lowercase.starts_with('0'))
+        || lowercase.trim_end_matches(['u', 'l']) == "0";
+
+    let mut candidates = vec![];
+    if !is_long {
+        if !is_unsigned {
+            candidates.push(int_type(true, 4));
+        }
+        if !is_decimal || is_unsigned {
+            candidates.push(int_type(false, 4));
+        }
+    }
+    if !is_unsigned {
+        candidates.push(int_type(true, 8));
+    }
+    if !is_decimal || is_unsigned {
+        candidates.push(int_type(false, 8));
+    }
+    candidates
+        .into_iter()
+        .find(|candidate| wrap_to_type(value, candidate) == value)
+        .map(|value_type| int_value(value, value_type))
+        .ok_or_else(|| format!("integer constant '{}' is too large", number.from))
+}
 
 impl EnvironmentController {
-    pub(crate) fn run_constant_expression(&mut self, expr: ConstantExpression) -> CompileTimeValue {
-        self.try_run_expression_at_compile_time((*expr.internal).clone())
+    pub(crate) fn run_constant_expression(
+        &mut self,
+        expr: &ConstantExpression,
+    ) -> Result<CompileTimeValue, String> {
+        self.try_run_expression_at_compile_time(&expr.internal)
     }
+
+    /// the value of a constant expression, or why it is none
     pub(crate) fn try_run_expression_at_compile_time(
         &mut self,
-        expr: CExpression,
-    ) -> CompileTimeValue {
-        match expr {
-            CExpression::Expression(_) => todo!(),
-            CExpression::Assignment {
-                to_assign: _,
-                operator: _,
-                value: _,
-            } => todo!(),
-            CExpression::Ternary {
-                condition: _,
-                if_true: _,
-                tern_else: _,
-            } => todo!(),
-            CExpression::LogicalOr(_) => todo!(),
-            CExpression::LogicalAnd(_) => todo!(),
-            CExpression::InclusiveOr(_) => todo!(),
-            CExpression::ExlusiveOr(_) => todo!(),
-            CExpression::And(_) => todo!(),
-            CExpression::Equality {
-                left_piece: _,
-                equality_op: _,
-                right_piece: _,
-            } => todo!(),
-            CExpression::Relational {
-                left_piece: _,
-                equality_op: _,
-                right_piece: _,
-            } => todo!(),
-            CExpression::Shift {
-                value: _,
-                shift_type: _,
-                shift_amount: _,
-            } => todo!(),
-            CExpression::Additive {
-                left_value: _,
-                op: _,
-                right_value: _,
-            } => todo!(),
+        expr: &Spanned<CExpression>,
+    ) -> Result<CompileTimeValue, String> {
+        Ok(self.evaluate_constant(expr)?.value)
+    }
+
+    /// the value of a constant expression converted as if by assignment to `target_type`
+    pub(crate) fn evaluate_constant_as(
+        &mut self,
+        expr: &Spanned<CExpression>,
+        target_type: &ExtType,
+    ) -> Result<CompileTimeValue, String> {
+        let value = decayed(self.evaluate_constant(expr)?);
+        Ok(converted(value, target_type)?.value)
+    }
+
+    /// the value of an integer constant expression, anything else is reported at its span
+    pub(crate) fn expect_integer_constant(&mut self, expr: &Spanned<CExpression>) -> i128 {
+        match self.try_run_expression_at_compile_time(expr) {
+            Ok(CompileTimeValue::Int(value)) => value,
+            Ok(_) => expr
+                .span
+                .fail_at_span("expected an integer constant expression"),
+            Err(reason) => expr.span.fail_at_span(&format!(
+                "expected an integer constant expression: {}",
+                reason
+            )),
+        }
+    }
+
+    /// (6.7.1) a constexpr object is initialized by a constant expression, of an integer one the value is kept
+    pub(crate) fn constexpr_value(
+        &mut self,
+        specifiers: &DeclarationSpecifiers,
+        object_type: &PrettyType,
+        initializer: Option<&Spanned<Initializer>>,
+        span: &Span,
+    ) -> Option<i128> {
+        if !specifiers.storage.constexpr_c {
+            return None;
+        }
+        let Some(initializer) = initializer else {
+            span.fail_at_span("constexpr object requires an initializer")
+        };
+        let (ExtType::Int { .. }, Initializer::Single(value_expr)) =
+            (&object_type.inner_type, &*initializer.inner)
+        else {
+            return None;
+        };
+        let value = self.expect_integer_constant(value_expr);
+        // (6.7.1) the value has to be representable, there is no implicit conversion
+        if wrap_to_type(value, &object_type.inner_type) != value {
+            value_expr.span.fail_at_span(&format!(
+                "constexpr initializer value {} is not representable in the object type",
+                value
+            ))
+        }
+        Some(value)
+    }
+
+    fn evaluate_constant(&mut self, expr: &Spanned<CExpression>) -> Result<TypedValue, String> {
+        match &*expr.inner {
+            CExpression::Constant(constant) => match constant {
+                Constant::Number(number) => number_constant(number),
+                Constant::Bool(value) => Ok(int_value(*value as i128, int_type(false, 1))),
+                Constant::Nullptr => Ok(TypedValue {
+                    value: CompileTimeValue::Int(0),
+                    value_type: ExtType::Pointer {
+                        is_const: false,
+                        is_volatile: false,
+                        to: Box::new(ExtType::Void),
+                    },
+                }),
+            },
+            CExpression::StringLiteral(literal) => Ok(TypedValue {
+                value: CompileTimeValue::String(literal.value.clone()),
+                value_type: ExtType::Array {
+                    is_const: false,
+                    is_volatile: false,
+                    arr_size: Some(
+                        MIRConstant::from_string(literal.value.clone()).value.len() as u64
+                    ),
+                    to: Box::new(int_type(false, 1)),
+                },
+            }),
+            CExpression::Identifier(ident) => {
+                if let Some(value) = self.get_enumeration_constant(&ident.identifier) {
+                    return Ok(int_value(value, int_type(true, 4)));
+                }
+                if let Some(variable) = self.symbol_table.get_top_variable(&ident.identifier) {
+                    let variable = variable.borrow();
+                    if let Some(value) = variable.constexpr_value {
+                        let value_type = variable.associated_type.inner_type.clone();
+                        return Ok(int_value(value, value_type));
+                    }
+                    // only arrays and functions are used through their address
+                    if !matches!(
+                        variable.associated_type.inner_type,
+                        ExtType::Array { .. } | ExtType::Function { .. }
+                    ) {
+                        return Err(format!(
+                            "the value of '{}' is not constant",
+                            ident.identifier
+                        ));
+                    }
+                }
+                let (symbol, offset, object_type) = self.evaluate_address(expr)?;
+                if matches!(
+                    object_type,
+                    ExtType::Array { .. } | ExtType::Function { .. }
+                ) {
+                    Ok(decayed(TypedValue {
+                        value: CompileTimeValue::Address { symbol, offset },
+                        value_type: object_type,
+                    }))
+                } else {
+                    Err(format!(
+                        "the value of '{}' is not constant",
+                        ident.identifier
+                    ))
+                }
+            }
+            CExpression::Paranthesised(inner) => self.evaluate_constant(inner),
+            CExpression::Unary { unary_op, value } => {
+                if *unary_op == UnaryOperator::REF {
+                    let (symbol, offset, object_type) = self.evaluate_address(value)?;
+                    return Ok(TypedValue {
+                        value: CompileTimeValue::Address { symbol, offset },
+                        value_type: ExtType::Pointer {
+                            is_const: false,
+                            is_volatile: false,
+                            to: Box::new(object_type),
+                        },
+                    });
+                }
+                let operand = decayed(self.evaluate_constant(value)?);
+                match unary_op {
+                    UnaryOperator::BOOLEANINVERT => Ok(bool_value(!truthiness(&operand)?)),
+                    _ if !is_arithmetic(&operand.value_type) => {
+                        Err("operand needs to be arithmetic".to_string())
+                    }
+                    UnaryOperator::VALUE => {
+                        let promoted_type = promoted(&operand.value_type);
+                        converted(operand, &promoted_type)
+                    }
+                    UnaryOperator::NEGATIVE => match operand.value {
+                        CompileTimeValue::Float(float) => Ok(TypedValue {
+                            value: CompileTimeValue::Float(-float),
+                            value_type: operand.value_type,
+                        }),
+                        _ => arithmetic_result(
+                            expect_int(&operand)?.wrapping_neg(),
+                            promoted(&operand.value_type),
+                        ),
+                    },
+                    UnaryOperator::BITWISEINVERT => Ok(int_value(
+                        !expect_int(&operand)?,
+                        promoted(&operand.value_type),
+                    )),
+                    UnaryOperator::REF | UnaryOperator::DEREF => {
+                        Err("reading an object is not a constant expression".to_string())
+                    }
+                }
+            }
+            CExpression::Cast { type_name, value } => {
+                let target_type = self.extract_pretty_type_from_type_name(&type_name.inner);
+                let target_type = self.complete_type(target_type).inner_type;
+                let operand = decayed(self.evaluate_constant(value)?);
+                converted(operand, &target_type)
+            }
+            CExpression::SizeOf { value } => {
+                let operand_type = self.type_of_constant_operand(value)?;
+                Ok(int_value(operand_type.size_of() as i128, size_t()))
+            }
+            CExpression::SizeOfType { type_name } => {
+                let operand_type = self.extract_pretty_type_from_type_name(&type_name.inner);
+                let operand_type = self.complete_type(operand_type);
+                Ok(int_value(operand_type.size_of() as i128, size_t()))
+            }
+            CExpression::AlignOfType { type_name } => {
+                let operand_type = self.extract_pretty_type_from_type_name(&type_name.inner);
+                let operand_type = self.complete_type(operand_type);
+                Ok(int_value(operand_type.align_of() as i128, size_t()))
+            }
             CExpression::Multiplicative {
-                left_value: _,
-                op: _,
-                right_value: _,
-            } => todo!(),
-            CExpression::Cast {
-                type_name: _,
-                value: _,
-            } => todo!(),
-            CExpression::PrefixIncrement {
-                increment_type: _,
-                value: _,
-            } => todo!(),
+                left_value,
+                op,
+                right_value,
+            } => {
+                let left = decayed(self.evaluate_constant(left_value)?);
+                let right = decayed(self.evaluate_constant(right_value)?);
+                let (left, right, common_type) = arithmetic_operands(left, right)?;
+                match (left.value, right.value) {
+                    (CompileTimeValue::Float(left), CompileTimeValue::Float(right)) => {
+                        let result = match op {
+                            MultiplicativeOperator::Mult => left * right,
+                            MultiplicativeOperator::Div => left / right,
+                            MultiplicativeOperator::Mod => {
+                                return Err("invalid operands to %".to_string())
+                            }
+                        };
+                        converted(
+                            TypedValue {
+                                value: CompileTimeValue::Float(result),
+                                value_type: common_type.clone(),
+                            },
+                            &common_type,
+                        )
+                    }
+                    (CompileTimeValue::Int(left), CompileTimeValue::Int(right)) => {
+                        let result = match op {
+                            MultiplicativeOperator::Mult => left.wrapping_mul(right),
+                            _ if right == 0 => {
+                                return Err("division by zero".to_string());
+                            }
+                            MultiplicativeOperator::Div => left / right,
+                            // (6.5.5) a % b is undefined if a / b is
+                            MultiplicativeOperator::Mod => {
+                                arithmetic_result(left / right, common_type.clone())?;
+                                left % right
+                            }
+                        };
+                        arithmetic_result(result, common_type)
+                    }
+                    _ => Err("operands need to be arithmetic".to_string()),
+                }
+            }
+            CExpression::Additive {
+                left_value,
+                op,
+                right_value,
+            } => {
+                let left = decayed(self.evaluate_constant(left_value)?);
+                let right = decayed(self.evaluate_constant(right_value)?);
+                if matches!(left.value_type, ExtType::Pointer { .. })
+                    || matches!(right.value_type, ExtType::Pointer { .. })
+                {
+                    return self.evaluate_pointer_additive(left, op, right);
+                }
+                let (left, right, common_type) = arithmetic_operands(left, right)?;
+                match (left.value, right.value) {
+                    (CompileTimeValue::Float(left), CompileTimeValue::Float(right)) => {
+                        let result = match op {
+                            AdditiveOperator::Plus => left + right,
+                            AdditiveOperator::Minus => left - right,
+                        };
+                        converted(
+                            TypedValue {
+                                value: CompileTimeValue::Float(result),
+                                value_type: common_type.clone(),
+                            },
+                            &common_type,
+                        )
+                    }
+                    (CompileTimeValue::Int(left), CompileTimeValue::Int(right)) => {
+                        let result = match op {
+                            AdditiveOperator::Plus => left.wrapping_add(right),
+                            AdditiveOperator::Minus => left.wrapping_sub(right),
+                        };
+                        arithmetic_result(result, common_type)
+                    }
+                    _ => Err("operands need to be arithmetic".to_string()),
+                }
+            }
+            CExpression::Shift {
+                value,
+                shift_type,
+                shift_amount,
+            } => {
+                let left = self.evaluate_constant(value)?;
+                let amount = expect_int(&self.evaluate_constant(shift_amount)?)?;
+                let result_type = promoted(&left.value_type);
+                let left = expect_int(&converted(left, &result_type)?)?;
+                if amount < 0 || amount >= (result_type.size_of() * 8) as i128 {
+                    return Err(format!("shift count {} is out of range", amount));
+                }
+                let result = match shift_type {
+                    ShiftOperator::Left => left << amount,
+                    ShiftOperator::Right => left >> amount,
+                };
+                arithmetic_result(result, result_type)
+            }
+            CExpression::Relational {
+                left_piece,
+                equality_op,
+                right_piece,
+            } => {
+                let ordering = self.evaluate_comparison(left_piece, right_piece)?;
+                Ok(bool_value(match equality_op {
+                    RelationalOperator::Lesser => ordering.is_lt(),
+                    RelationalOperator::Greater => ordering.is_gt(),
+                    RelationalOperator::LesserEqual => ordering.is_le(),
+                    RelationalOperator::GreaterEqual => ordering.is_ge(),
+                }))
+            }
+            CExpression::Equality {
+                left_piece,
+                equality_op,
+                right_piece,
+            } => {
+                let ordering = self.evaluate_comparison(left_piece, right_piece)?;
+                Ok(bool_value(match equality_op {
+                    EqualityOperator::Equal => ordering.is_eq(),
+                    EqualityOperator::NotEqual => ordering.is_ne(),
+                }))
+            }
+            CExpression::And(operands)
+            | CExpression::ExlusiveOr(operands)
+            | CExpression::InclusiveOr(operands) => {
+                let mut result = self.evaluate_constant(&operands[0])?;
+                for operand in &operands[1..] {
+                    let right = self.evaluate_constant(operand)?;
+                    let (left, right, common_type) = arithmetic_operands(result, right)?;
+                    let (left, right) = (expect_int(&left)?, expect_int(&right)?);
+                    result = int_value(
+                        match &*expr.inner {
+                            CExpression::And(_) => left & right,
+                            CExpression::ExlusiveOr(_) => left ^ right,
+                            _ => left | right,
+                        },
+                        common_type,
+                    );
+                }
+                Ok(result)
+            }
+            CExpression::LogicalAnd(operands) => {
+                for operand in operands {
+                    if !truthiness(&self.evaluate_constant(operand)?)? {
+                        return Ok(bool_value(false));
+                    }
+                }
+                Ok(bool_value(true))
+            }
+            CExpression::LogicalOr(operands) => {
+                for operand in operands {
+                    if truthiness(&self.evaluate_constant(operand)?)? {
+                        return Ok(bool_value(true));
+                    }
+                }
+                Ok(bool_value(false))
+            }
+            CExpression::Ternary {
+                condition,
+                if_true,
+                tern_else,
+            } => {
+                let (taken, not_taken) = if truthiness(&self.evaluate_constant(condition)?)? {
+                    (if_true, tern_else)
+                } else {
+                    (tern_else, if_true)
+                };
+                let result = decayed(self.evaluate_constant(taken)?);
+                // the other operand only contributes its type
+                match self.evaluate_constant(not_taken) {
+                    Ok(other)
+                        if is_arithmetic(&result.value_type)
+                            && is_arithmetic(&other.value_type) =>
+                    {
+                        let common_type =
+                            usual_arithmetic_conversion(&result.value_type, &other.value_type);
+                        converted(result, &common_type)
+                    }
+                    _ => Ok(result),
+                }
+            }
+            CExpression::GenericSelection(selection) => {
+                let controlling_type =
+                    self.type_of_constant_operand(&selection.assignment_expression)?;
+                let controlling_type = decayed(TypedValue {
+                    value: CompileTimeValue::Int(0),
+                    value_type: controlling_type,
+                })
+                .value_type
+                .unqualified();
+                let mut default = None;
+                for association in selection.generic_assoc_list.inner.iter() {
+                    match association {
+                        GenericAssociation::TypeName {
+                            type_name,
+                            assignment_expression,
+                        } => {
+                            let association_type =
+                                self.extract_pretty_type_from_type_name(type_name);
+                            if self.complete_type(association_type).inner_type == controlling_type {
+                                return self.evaluate_constant(assignment_expression);
+                            }
+                        }
+                        GenericAssociation::Default(assignment_expression) => {
+                            default = Some(assignment_expression)
+                        }
+                    }
+                }
+                match default {
+                    Some(assignment_expression) => self.evaluate_constant(assignment_expression),
+                    None => Err("no _Generic association matches".to_string()),
+                }
+            }
+            CExpression::ArraySubscription { .. }
+            | CExpression::DirectMemberAccess { .. }
+            | CExpression::IndirectMemberAccess { .. }
+            | CExpression::TypeInitializer { .. } => {
+                // only arrays are fine, their address is used
+                let (symbol, offset, object_type) = self.evaluate_address(expr)?;
+                if matches!(object_type, ExtType::Array { .. }) {
+                    Ok(decayed(TypedValue {
+                        value: CompileTimeValue::Address { symbol, offset },
+                        value_type: object_type,
+                    }))
+                } else {
+                    Err("reading an object is not a constant expression".to_string())
+                }
+            }
+            CExpression::Expression(_) => {
+                Err("comma operator in a constant expression".to_string())
+            }
+            CExpression::Assignment { .. }
+            | CExpression::PrefixIncrement { .. }
+            | CExpression::PostfixIncrement { .. } => {
+                Err("assignment in a constant expression".to_string())
+            }
+            CExpression::FunctionCall { .. } => {
+                Err("function call in a constant expression".to_string())
+            }
+            CExpression::StatementExpression(_) => {
+                Err("statement expression in a constant expression".to_string())
+            }
+            // GNU: only a static initializer in the function of the label
+            CExpression::LabelAddress(label) => match self.label_addresses.get(&label.identifier) {
+                Some(label_block_id) => Ok(TypedValue {
+                    value: CompileTimeValue::Int(*label_block_id as i128),
+                    value_type: ExtType::Pointer {
+                        is_const: false,
+                        is_volatile: false,
+                        to: Box::new(ExtType::Void),
+                    },
+                }),
+                None => Err("label address outside of a function".to_string()),
+            },
+        }
+    }
+
+    /// (6.6) `+` and `-` with a pointer operand, addresses stay symbol plus offset
+    fn evaluate_pointer_additive(
+        &mut self,
+        left: TypedValue,
+        op: &AdditiveOperator,
+        right: TypedValue,
+    ) -> Result<TypedValue, String> {
+        let left_is_pointer = matches!(left.value_type, ExtType::Pointer { .. });
+        let right_is_pointer = matches!(right.value_type, ExtType::Pointer { .. });
+        let (pointer, other) = match (left_is_pointer, right_is_pointer, op) {
+            (true, false, _) => (left, right),
+            (false, true, AdditiveOperator::Plus) => (right, left),
+            (true, true, AdditiveOperator::Minus) => {
+                let element_size = self.pointee_size(&left.value_type);
+                let distance = match (left.value, right.value) {
+                    (
+                        CompileTimeValue::Address {
+                            symbol: left_symbol,
+                            offset: left_offset,
+                        },
+                        CompileTimeValue::Address {
+                            symbol: right_symbol,
+                            offset: right_offset,
+                        },
+                    ) if left_symbol == right_symbol => (left_offset - right_offset) as i128,
+                    (CompileTimeValue::Int(left), CompileTimeValue::Int(right)) => left - right,
+                    _ => {
+                        return Err(
+                            "difference of addresses of different objects is not constant"
+                                .to_string(),
+                        )
+                    }
+                };
+                return Ok(int_value(distance / element_size, int_type(true, 8)));
+            }
+            _ => return Err("invalid operands to pointer arithmetic".to_string()),
+        };
+
+        let mut byte_offset = expect_int(&other)? * self.pointee_size(&pointer.value_type);
+        if *op == AdditiveOperator::Minus {
+            byte_offset = -byte_offset;
+        }
+        let value = match pointer.value {
+            CompileTimeValue::Address { symbol, offset } => CompileTimeValue::Address {
+                symbol,
+                offset: offset + byte_offset as i64,
+            },
+            CompileTimeValue::Int(address) => {
+                CompileTimeValue::Int(wrap_to_type(address + byte_offset, &pointer.value_type))
+            }
+            _ => return Err("invalid operands to pointer arithmetic".to_string()),
+        };
+        Ok(TypedValue {
+            value,
+            value_type: pointer.value_type,
+        })
+    }
+
+    fn pointee_size(&mut self, pointer_type: &ExtType) -> i128 {
+        match pointer_type {
+            ExtType::Pointer { to, .. } => self.complete_type(to.into_pretty()).size_of() as i128,
+            _ => 1,
+        }
+    }
+
+    /// the order of two comparable constants, pointers only compare within one object
+    fn evaluate_comparison(
+        &mut self,
+        left: &Spanned<CExpression>,
+        right: &Spanned<CExpression>,
+    ) -> Result<std::cmp::Ordering, String> {
+        let left = decayed(self.evaluate_constant(left)?);
+        let right = decayed(self.evaluate_constant(right)?);
+        if is_arithmetic(&left.value_type) && is_arithmetic(&right.value_type) {
+            let (left, right, _) = arithmetic_operands(left, right)?;
+            return match (left.value, right.value) {
+                (CompileTimeValue::Int(left), CompileTimeValue::Int(right)) => Ok(left.cmp(&right)),
+                (CompileTimeValue::Float(left), CompileTimeValue::Float(right)) => left
+                    .partial_cmp(&right)
+                    .ok_or_else(|| "comparison with NaN".to_string()),
+                _ => Err("operands need to be arithmetic".to_string()),
+            };
+        }
+        match (left.value, right.value) {
+            (CompileTimeValue::Int(left), CompileTimeValue::Int(right)) => Ok(left.cmp(&right)),
+            (
+                CompileTimeValue::Address {
+                    symbol: left_symbol,
+                    offset: left_offset,
+                },
+                CompileTimeValue::Address {
+                    symbol: right_symbol,
+                    offset: right_offset,
+                },
+            ) if left_symbol == right_symbol => Ok(left_offset.cmp(&right_offset)),
+            // an address compared to the null pointer
+            (CompileTimeValue::Address { .. }, CompileTimeValue::Int(0)) => {
+                Ok(std::cmp::Ordering::Greater)
+            }
+            (CompileTimeValue::Int(0), CompileTimeValue::Address { .. }) => {
+                Ok(std::cmp::Ordering::Less)
+            }
+            _ => Err("comparison of addresses of different objects is not constant".to_string()),
+        }
+    }
+
+    /// symbol, byte offset and type of an object with a constant address
+    fn evaluate_address(
+        &mut self,
+        expr: &Spanned<CExpression>,
+    ) -> Result<(String, i64, ExtType), String> {
+        match &*expr.inner {
+            CExpression::Identifier(ident) => {
+                let Some(variable) = self.symbol_table.get_top_variable(&ident.identifier) else {
+                    return Err(format!(
+                        "use of undeclared identifier '{}'",
+                        ident.identifier
+                    ));
+                };
+                let variable = variable.borrow().clone();
+                let object_type = self.complete_type(variable.associated_type).inner_type;
+                // objects with static storage and functions have a fixed address
+                match (variable.static_symbol, &object_type) {
+                    (Some(symbol), _) => Ok((symbol, 0, object_type)),
+                    (None, ExtType::Function { .. }) => {
+                        Ok((ident.identifier.clone(), 0, object_type))
+                    }
+                    (None, _) => Err(format!(
+                        "the address of '{}' is not constant",
+                        ident.identifier
+                    )),
+                }
+            }
+            CExpression::Paranthesised(inner) => self.evaluate_address(inner),
+            CExpression::DirectMemberAccess { to_access, member } => {
+                let (symbol, offset, aggregate_type) = self.evaluate_address(to_access)?;
+                let (member_offset, member_type) =
+                    aggregate_type
+                        .find_member(&member.identifier)
+                        .ok_or_else(|| format!("no member named '{}'", member.identifier))?;
+                let member_type = self.complete_type(member_type).inner_type;
+                Ok((symbol, offset + member_offset as i64, member_type))
+            }
+            CExpression::IndirectMemberAccess { to_access, member } => {
+                let (symbol, offset, aggregate_type) = self.evaluate_pointed_to(to_access)?;
+                let (member_offset, member_type) =
+                    aggregate_type
+                        .find_member(&member.identifier)
+                        .ok_or_else(|| format!("no member named '{}'", member.identifier))?;
+                let member_type = self.complete_type(member_type).inner_type;
+                Ok((symbol, offset + member_offset as i64, member_type))
+            }
+            CExpression::ArraySubscription { array, index } => {
+                let base = decayed(self.evaluate_constant(array)?);
+                let index = decayed(self.evaluate_constant(index)?);
+                let (base, index) = if matches!(base.value_type, ExtType::Pointer { .. }) {
+                    (base, index)
+                } else {
+                    (index, base)
+                };
+                let element =
+                    self.evaluate_pointer_additive(base, &AdditiveOperator::Plus, index)?;
+                self.pointed_to(element)
+            }
             CExpression::Unary {
-                unary_op: _,
-                value: _,
-            } => todo!(),
-            CExpression::SizeOf { value: _ } => todo!(),
-            CExpression::SizeOfType { type_name: _ } => todo!(),
-            CExpression::AlignOfType { type_name: _ } => todo!(),
-            CExpression::ArraySubscription { array: _, index: _ } => todo!(),
-            CExpression::FunctionCall {
-                function: _,
-                arguments: _,
-            } => todo!(),
-            CExpression::DirectMemberAccess {
-                to_access: _,
-                member: _,
-            } => todo!(),
-            CExpression::IndirectMemberAccess {
-                to_access: _,
-                member: _,
-            } => todo!(),
-            CExpression::PostfixIncrement {
-                increment_type: _,
-                value: _,
-            } => todo!(),
+                unary_op: UnaryOperator::DEREF,
+                value,
+            } => self.evaluate_pointed_to(value),
+            // (6.5.2.5) outside of a function a compound literal has static storage
             CExpression::TypeInitializer {
-                type_name: _,
-                initializer_list: _,
-            } => todo!(),
-            CExpression::Identifier(_) => todo!(),
-            CExpression::Constant(constant) => match constant {
-                crate::parser::parse_nodes::Constant::Number(number_string) => {
-                    let og = number_string.from;
-                    if og.contains(".") {
-                        CompileTimeValue::Float(og.parse::<f64>().expect(
-                            "failed to convert string to float in constant expression runner",
-                        ))
-                    } else {
-                        let og_trimmed = og
-                            .trim_end_matches('l')
-                            .trim_end_matches('L')
-                            .trim_end_matches('l')
-                            .trim_end_matches('L')
-                            .trim_end_matches('u')
-                            .trim_end_matches('U');
-                        debug!("og: {},og_trimmed: {}", og, og_trimmed);
-                        CompileTimeValue::Int(og_trimmed.parse::<i128>().expect(
-                            "failed to convert string to int in constant expression runner",
+                type_name,
+                initializer_list,
+            } => {
+                let literal_type = self.compound_literal_type(&type_name.inner, initializer_list);
+                let symbol = format!(".L.compoundliteral.{}", self.mir_programm.globals.len());
+                self.define_static_object(
+                    &symbol,
+                    false,
+                    true,
+                    &literal_type,
+                    Some(initializer_list),
+                    &expr.span,
+                );
+                Ok((symbol, 0, literal_type.inner_type))
+            }
+            _ => Err("expression has no constant address".to_string()),
+        }
+    }
+
+    fn evaluate_pointed_to(
+        &mut self,
+        pointer: &Spanned<CExpression>,
+    ) -> Result<(String, i64, ExtType), String> {
+        let pointer = decayed(self.evaluate_constant(pointer)?);
+        self.pointed_to(pointer)
+    }
+
+    fn pointed_to(&mut self, pointer: TypedValue) -> Result<(String, i64, ExtType), String> {
+        match (pointer.value, pointer.value_type) {
+            (CompileTimeValue::Address { symbol, offset }, ExtType::Pointer { to, .. }) => {
+                let pointee = self.complete_type(to.into_pretty()).inner_type;
+                Ok((symbol, offset, pointee))
+            }
+            _ => Err("dereferencing something that is no address constant".to_string()),
+        }
+    }
+
+    /// (6.5.3.4) the type of a sizeof operand, which is not evaluated and need not be constant
+    pub(crate) fn type_of_constant_operand(
+        &mut self,
+        expr: &Spanned<CExpression>,
+    ) -> Result<ExtType, String> {
+        let operand_type = match &*expr.inner {
+            CExpression::Identifier(ident) => {
+                match self.symbol_table.get_top_variable(&ident.identifier) {
+                    Some(variable) => variable.borrow().associated_type.inner_type.clone(),
+                    None => {
+                        return Err(format!(
+                            "use of undeclared identifier '{}'",
+                            ident.identifier
                         ))
                     }
                 }
+            }
+            CExpression::Paranthesised(inner) => return self.type_of_constant_operand(inner),
+            CExpression::DirectMemberAccess { to_access, member }
+            | CExpression::IndirectMemberAccess { to_access, member } => {
+                let mut aggregate_type = self.type_of_constant_operand(to_access)?;
+                if matches!(&*expr.inner, CExpression::IndirectMemberAccess { .. }) {
+                    aggregate_type = Self::pointee_type(aggregate_type)?;
+                }
+                let aggregate_type = self.complete_type(aggregate_type.into_pretty()).inner_type;
+                aggregate_type
+                    .find_member(&member.identifier)
+                    .ok_or_else(|| format!("no member named '{}'", member.identifier))?
+                    .1
+                    .inner_type
+            }
+            CExpression::ArraySubscription { array, index } => {
+                let array_type = self.type_of_constant_operand(array)?;
+                match Self::pointee_type(array_type) {
+                    Ok(element_type) => element_type,
+                    Err(_) => Self::pointee_type(self.type_of_constant_operand(index)?)?,
+                }
+            }
+            CExpression::Unary {
+                unary_op: UnaryOperator::DEREF,
+                value,
+            } => Self::pointee_type(self.type_of_constant_operand(value)?)?,
+            CExpression::Unary {
+                unary_op: UnaryOperator::REF,
+                value,
+            } => ExtType::Pointer {
+                is_const: false,
+                is_volatile: false,
+                to: Box::new(self.type_of_constant_operand(value)?),
             },
-            CExpression::StringLiteral(string_literal) => {
-                CompileTimeValue::String(string_literal.value)
+            CExpression::TypeInitializer {
+                type_name,
+                initializer_list,
+            } => {
+                self.compound_literal_type(&type_name.inner, initializer_list)
+                    .inner_type
+            }
+            CExpression::FunctionCall { function, .. } => {
+                match self.type_of_constant_operand(function)? {
+                    ExtType::Function { returns, .. } => *returns,
+                    ExtType::Pointer { to, .. } => match *to {
+                        ExtType::Function { returns, .. } => *returns,
+                        _ => return Err("called object is not a function".to_string()),
+                    },
+                    _ => return Err("called object is not a function".to_string()),
+                }
             }
-            CExpression::Paranthesised(_) => todo!(),
-            CExpression::GenericSelection(_) => todo!(),
+            _ => self.evaluate_constant(expr)?.value_type,
+        };
+        Ok(self.complete_type(operand_type.into_pretty()).inner_type)
+    }
+
+    fn pointee_type(pointer_type: ExtType) -> Result<ExtType, String> {
+        match pointer_type {
+            ExtType::Pointer { to, .. } | ExtType::Array { to, .. } => Ok(*to),
+            _ => Err("subscripted value is not an array or pointer".to_string()),
         }
     }
 }
@@ -3,17 +3,36 @@ Qualifiers?
 
 */
 
+use std::cell::RefCell;
+
 use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     mir::{MIRBlock, MIRInstruction, MIRType, MIRValue},
-    parser::parse_nodes::declarations::{
-        CFunctionSpecifier, CStorageClass, DeclarationSpecifiers, DerivedDeclarator,
+    parser::{
+        parse_nodes::{
+            declarations::{
+                CAlignmentSpecifier, CFunctionSpecifier, CStorageClass, DeclarationSpecifiers,
+                DerivedDeclarator,
+            },
+            expressions::CExpression,
+            gnu_extensions::GnuAttribute,
+        },
+        span::Spanned,
+        types::{
+            CEnumType, CSructDeclaration, CStructDeclarator, CTypeName, CTypeOfArgument,
+            CTypeQualifiers,
+        },
     },
 };
 
-use super::{walker::walk_func::FunctionContext, CompileTimeValue, EnvironmentController};
+use super::{
+    constant_expr::usual_arithmetic_conversion,
+    symbol_table::{TagInstance, VariableInstance},
+    walker::walk_func::FunctionContext,
+    CompileTimeValue, EnvironmentController,
+};
 
 /// A transformed Type from the Parser
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -58,14 +77,20 @@ pub(crate) enum ExtType {
         is_const: bool,
         is_volatile: bool,
         tag: Option<String>,
+        #[serde(default)]
+        alignment: Option<u64>,
         members: Vec<StructOrUnionMember>,
     },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) struct StructOrUnionMember {
-    ident: String,
-    member_type: Box<ExtType>,
+    /// empty for anonymous struct and union members, their members are found through them
+    pub(crate) ident: String,
+    pub(crate) member_type: Box<ExtType>,
+    /// from `_Alignas` or `__attribute__((aligned))`, never lowers the natural alignment
+    #[serde(default)]
+    pub(crate) alignment: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -118,90 +143,77 @@ impl EnvironmentController {
                 }
                 if !is_float {
                     ExtType::Int {
-                        is_const: decl_spec.qualifiers.const_q,
+                        is_const: decl_spec.qualifiers.const_q || decl_spec.storage.constexpr_c,
                         is_volatile: decl_spec.qualifiers.volatile_q,
                         signed,
                         size,
                     }
                 } else {
                     ExtType::Float {
-                        is_const: decl_spec.qualifiers.const_q,
+                        is_const: decl_spec.qualifiers.const_q || decl_spec.storage.constexpr_c,
                         is_volatile: decl_spec.qualifiers.volatile_q,
                         size,
                     }
                 }
             }
-            // TODO: Tags must be compatible in scope => _IO_FILE gets completed :)
-            StructOrUnion(struct_or_union) => match struct_or_union.struct_type {
-                crate::parser::types::CStructOrUnionTypeType::Struct => ExtType::Struct {
-                    is_const: decl_spec.qualifiers.const_q,
-                    is_volatile: decl_spec.qualifiers.volatile_q,
-                    tag: struct_or_union
-                        .ident
-                        .as_ref()
-                        .map(|tag| tag.identifier.clone()),
-                    members: {
-                        let mut collector = vec![];
-
-                        for member in &struct_or_union.declarations {
-                            match member{
-                                crate::parser::types::CSructDeclaration::StaticAssertDeclaration(static_assert_decl) => self.handle_static_assert(static_assert_decl),
-                                crate::parser::types::CSructDeclaration::StructDeclaration { specifier_qualifier, delcarator_list } => {
-                                    for decl in delcarator_list {
-                                        let true_decl = match &*decl.inner {
-                                            crate::parser::types::CStructDeclarator::Declarator(decl) => decl,
-                                            crate::parser::types::CStructDeclarator::BitField { declarator: _, expr: _ } => panic!("bit fields unsupported"),
-                                        };
-                                        let extracted_type = self
-                                            .extract_pretty_type_from_declaration_specifiers_and_derived_declarator(
-                                                DeclarationSpecifiers { storage: CStorageClass{ typedef_c: false, extern_c: false, static_c: false, thread_local_c: false, auto_c: false, register_c: false }, qualifiers: specifier_qualifier.qualifiers.clone(), specifiers: specifier_qualifier.specifier.clone(), function: CFunctionSpecifier{ inline: false, no_return: false }, alignment: None },
-                                                true_decl.derive.clone(),
-                                            );
-                                        collector.push(StructOrUnionMember { ident: true_decl.base.identifier.clone(), member_type: Box::new(extracted_type.inner_type) });
-                                    }
-                                },
-                            }
-                        }
+            StructOrUnion(struct_or_union) => {
+                let is_const = decl_spec.qualifiers.const_q || decl_spec.storage.constexpr_c;
+                let is_volatile = decl_spec.qualifiers.volatile_q;
+                let tag = struct_or_union
+                    .ident
+                    .as_ref()
+                    .map(|tag| tag.identifier.clone());
 
-                        collector
-                    },
-                    alignment: None, //TODO!
-                },
-                crate::parser::types::CStructOrUnionTypeType::Union => ExtType::Union {
-                    is_const: decl_spec.qualifiers.const_q,
-                    is_volatile: decl_spec.qualifiers.volatile_q,
-                    tag: struct_or_union
-                        .ident
+                // `struct tag` without a body refers to the tag in scope, if it was defined already
+                if struct_or_union.declarations.is_empty() {
+                    if let Some(tag_instance) = tag
                         .as_ref()
-                        .map(|tag| tag.identifier.clone()),
-                    members: {
-                        let mut collector = vec![];
-
-                        for member in &struct_or_union.declarations {
-                            match member{
-                                crate::parser::types::CSructDeclaration::StaticAssertDeclaration(static_assert_decl) => self.handle_static_assert(static_assert_decl),
-                                crate::parser::types::CSructDeclaration::StructDeclaration { specifier_qualifier, delcarator_list } => {
-                                    for decl in delcarator_list {
-                                        let true_decl = match &*decl.inner {
-                                            crate::parser::types::CStructDeclarator::Declarator(decl) => decl,
-                                            crate::parser::types::CStructDeclarator::BitField { declarator: _, expr: _ } => panic!("bit fields unsupported"),
-                                        };
-                                        let extracted_type = self
-                                            .extract_pretty_type_from_declaration_specifiers_and_derived_declarator(
-                                                DeclarationSpecifiers { storage: CStorageClass{ typedef_c: false, extern_c: false, static_c: false, thread_local_c: false, auto_c: false, register_c: false }, qualifiers: specifier_qualifier.qualifiers.clone(), specifiers: specifier_qualifier.specifier.clone(), function: CFunctionSpecifier{ inline: false, no_return: false }, alignment: None },
-                                                true_decl.derive.clone(),
-                                            );
-                                        collector.push(StructOrUnionMember { ident: true_decl.base.identifier.clone(), member_type: Box::new(extracted_type.inner_type) });
-                                    }
-                                },
-                            }
-                        }
+                        .and_then(|tag| self.symbol_table.get_top_tag(tag))
+                    {
+                        let tag_type = tag_instance.borrow().tag_type.inner_type.clone();
+                        return tag_type.qualified(is_const, is_volatile);
+                    }
+                }
 
-                        collector
+                reject_packed(&struct_or_union.attributes);
+                let members = self.extract_struct_or_union_members(&struct_or_union.declarations);
+                let alignment = self.extract_alignment_from_attributes(&struct_or_union.attributes);
+                let extracted = match struct_or_union.struct_type {
+                    crate::parser::types::CStructOrUnionTypeType::Struct => ExtType::Struct {
+                        is_const,
+                        is_volatile,
+                        tag: tag.clone(),
+                        alignment,
+                        members,
                     },
-                },
-            },
-            Enum(_) => todo!("enum Currently not implemented"),
+                    crate::parser::types::CStructOrUnionTypeType::Union => ExtType::Union {
+                        is_const,
+                        is_volatile,
+                        tag: tag.clone(),
+                        alignment,
+                        members,
+                    },
+                };
+
+                // a definition registers the tag, so later `struct tag` and self references complete
+                if let Some(tag) = tag {
+                    if !struct_or_union.declarations.is_empty() {
+                        self.symbol_table.get_current_scope().tags.insert(
+                            tag,
+                            RefCell::new(TagInstance {
+                                tag_type: extracted.clone().unqualified().into_pretty(),
+                            }),
+                        );
+                    }
+                }
+                extracted
+            }
+            Enum(enum_type) => {
+                let is_const = decl_spec.qualifiers.const_q || decl_spec.storage.constexpr_c;
+                let is_volatile = decl_spec.qualifiers.volatile_q;
+                self.extract_enum_type(&enum_type)
+                    .qualified(is_const, is_volatile)
+            }
             Typedefed(typedef_name) => {
                 if let Some(typedef_instance) = self
                     .symbol_table
@@ -213,6 +225,317 @@ impl EnvironmentController {
                 }
             }
             Atomic(_) => todo!("Atomic not supported"),
+            TypeOf(type_of) => {
+                let argument_type = match &type_of.argument {
+                    CTypeOfArgument::TypeName(type_name) => {
+                        let extracted_type = self
+                            .extract_pretty_type_from_declaration_specifiers_and_derived_declarator(
+                                DeclarationSpecifiers {
+                                    storage: decl_spec.storage.clone(),
+                                    qualifiers: type_name.base.qualifiers.clone(),
+                                    specifiers: type_name.base.specifier.clone(),
+                                    function: decl_spec.function.clone(),
+                                    alignment: None,
+                                    attributes: vec![],
+                                },
+                                *type_name.declarator.inner.clone(),
+                            );
+                        extracted_type.inner_type
+                    }
+                    // the expression is not evaluated, only its type is worked out
+                    CTypeOfArgument::Expression(expr) => {
+                        match self.type_of_constant_operand(expr) {
+                            Ok(expression_type) => expression_type,
+                            Err(reason) => {
+                                expr.span.fail_at_span(&format!("{} in __typeof__", reason))
+                            }
+                        }
+                    }
+                };
+                if type_of.unqualified {
+                    argument_type.unqualified()
+                } else {
+                    argument_type
+                }
+            }
+        }
+    }
+}
+
+/*
+(6.7.2.2) enum-specifier:
+    enum attribute-specifier-sequence opt identifier opt enum-type-specifier opt { enumerator-list }
+    enum identifier enum-type-specifier opt
+every enumerator is an int constant in the ordinary identifier namespace,
+without a value it is one more than the previous one, the first one is 0
+the enumerated type itself is int as well
+*/
+impl EnvironmentController {
+    pub(crate) fn extract_enum_type(&mut self, enum_type: &Spanned<CEnumType>) -> ExtType {
+        let int_type = ExtType::Int {
+            is_const: false,
+            is_volatile: false,
+            signed: true,
+            size: 4,
+        };
+        let tag = enum_type.ident.as_ref().map(|tag| tag.identifier.clone());
+
+        // `enum tag` without a body refers to the tag in scope
+        if enum_type.enumerators.is_empty() {
+            if let Some(tag_instance) = tag
+                .as_ref()
+                .and_then(|tag| self.symbol_table.get_top_tag(tag))
+            {
+                return tag_instance.borrow().tag_type.inner_type.clone();
+            }
+            return int_type;
+        }
+
+        let mut next_value = 0;
+        for enumerator in &enum_type.enumerators {
+            let value = match &enumerator.const_assignment {
+                Some(assignment) => self.expect_integer_constant(&assignment.internal),
+                None => next_value,
+            };
+            if i32::try_from(value).is_err() {
+                enumerator
+                    .span
+                    .fail_at_span("enumerator value is outside the range of int")
+            }
+            self.symbol_table.get_current_scope().variables.insert(
+                enumerator.enumeration_constant.identifier.clone(),
+                RefCell::new(VariableInstance {
+                    is_extern: false,
+                    usage_counter: 0,
+                    associated_type: int_type.clone().qualified(true, false).into_pretty(),
+                    constant_value: Some(value),
+                    constexpr_value: None,
+                    static_symbol: None,
+                    local: None,
+                }),
+            );
+            next_value = value + 1;
+        }
+
+        if let Some(tag) = tag {
+            self.symbol_table.get_current_scope().tags.insert(
+                tag,
+                RefCell::new(TagInstance {
+                    tag_type: int_type.clone().into_pretty(),
+                }),
+            );
+        }
+        int_type
+    }
+
+    /// the value of `ident` if it names an enumeration constant
+    pub(crate) fn get_enumeration_constant(&mut self, ident: &str) -> Option<i128> {
+        self.symbol_table
+            .get_top_variable(ident)
+            .and_then(|variable| variable.borrow().constant_value)
+    }
+}
+
+/*
+(6.7.2.1) struct-declaration:
+    specifier-qualifier-list struct-declarator-list opt ;
+    static_assert-declaration
+a struct-declaration without declarators is only allowed for an anonymous struct or union
+*/
+impl EnvironmentController {
+    pub(crate) fn extract_struct_or_union_members(
+        &mut self,
+        declarations: &[CSructDeclaration],
+    ) -> Vec<StructOrUnionMember> {
+        let mut collector = vec![];
+
+        for member in declarations {
+            match member {
+                CSructDeclaration::StaticAssertDeclaration(static_assert_decl) => {
+                    self.handle_static_assert(static_assert_decl)
+                }
+                CSructDeclaration::StructDeclaration {
+                    alignment,
+                    specifier_qualifier,
+                    delcarator_list,
+                } => {
+                    let decl_spec = DeclarationSpecifiers {
+                        storage: CStorageClass {
+                            typedef_c: false,
+                            extern_c: false,
+                            static_c: false,
+                            thread_local_c: false,
+                            auto_c: false,
+                            register_c: false,
+                            constexpr_c: false,
+                        },
+                        qualifiers: specifier_qualifier.qualifiers.clone(),
+                        specifiers: specifier_qualifier.specifier.clone(),
+                        function: CFunctionSpecifier {
+                            inline: false,
+                            no_return: false,
+                        },
+                        alignment: None,
+                        attributes: vec![],
+                    };
+                    let alignas = alignment
+                        .as_ref()
+                        .map(|alignment| self.extract_alignment(alignment));
+
+                    if delcarator_list.is_empty() {
+                        let member_type =
+                            self.extract_base_ext_type_from_declaration_specifiers(decl_spec);
+                        if matches!(member_type, ExtType::Struct { .. } | ExtType::Union { .. }) {
+                            collector.push(StructOrUnionMember {
+                                ident: String::new(),
+                                member_type: Box::new(member_type),
+                                alignment: alignas,
+                            });
+                        } else {
+                            specifier_qualifier
+                                .span
+                                .error_at_span("declaration does not declare anything");
+                        }
+                        continue;
+                    }
+
+                    for decl in delcarator_list {
+                        let true_decl = match &*decl.inner {
+                            CStructDeclarator::Declarator(decl) => decl,
+                            CStructDeclarator::BitField { .. } => {
+                                decl.span.fail_at_span("bit-fields are not supported")
+                            }
+                        };
+                        reject_packed(&true_decl.attributes);
+                        let extracted_type = self
+                            .extract_pretty_type_from_declaration_specifiers_and_derived_declarator(
+                                decl_spec.clone(),
+                                true_decl.derive.clone(),
+                            );
+                        let alignment = alignas
+                            .max(self.extract_alignment_from_attributes(&true_decl.attributes));
+                        collector.push(StructOrUnionMember {
+                            ident: true_decl.base.identifier.clone(),
+                            member_type: Box::new(extracted_type.inner_type),
+                            alignment,
+                        });
+                    }
+                }
+            }
+        }
+
+        collector
+    }
+
+    /// the alignment in bytes `_Alignas` asks for, (6.7.5) `_Alignas(0)` has no effect
+    pub(crate) fn extract_alignment(&mut self, alignment: &Spanned<CAlignmentSpecifier>) -> u64 {
+        let requested = match &*alignment.inner {
+            CAlignmentSpecifier::ToType(type_name) => self
+                .extract_pretty_type_from_type_name(&type_name.inner)
+                .align_of(),
+            CAlignmentSpecifier::ToExpression(expr) => {
+                match self.try_run_expression_at_compile_time(&expr.internal) {
+                    Ok(CompileTimeValue::Int(val)) if val >= 0 => val as u64,
+                    _ => {
+                        alignment
+                            .span
+                            .fail_at_span("_Alignas needs a non negative integer constant");
+                    }
+                }
+            }
+        };
+        if requested != 0 && !requested.is_power_of_two() {
+            alignment
+                .span
+                .fail_at_span("requested alignment is not a power of 2");
+        }
+        requested
+    }
+
+    /// `__attribute__((aligned(n)))`, a bare `aligned` is the biggest alignment, 16 on x86_64
+    pub(crate) fn extract_alignment_from_attributes(
+        &mut self,
+        attributes: &[Spanned<GnuAttribute>],
+    ) -> Option<u64> {
+        let mut alignment = None;
+        for attribute in attributes {
+            if let GnuAttribute::Aligned(expr) = &*attribute.inner {
+                let requested = match expr {
+                    Some(expr) => match self.try_run_expression_at_compile_time(&expr.internal) {
+                        Ok(CompileTimeValue::Int(val))
+                            if val > 0 && (val as u64).is_power_of_two() =>
+                        {
+                            val as u64
+                        }
+                        _ => {
+                            attribute
+                                .span
+                                .fail_at_span("requested alignment is not a positive power of 2");
+                        }
+                    },
+                    None => 16,
+                };
+                alignment = alignment.max(Some(requested));
+            }
+        }
+        alignment
+    }
+
+    pub(crate) fn extract_pretty_type_from_type_name(
+        &mut self,
+        type_name: &CTypeName,
+    ) -> PrettyType {
+        self.extract_pretty_type_from_declaration_specifiers_and_derived_declarator(
+            DeclarationSpecifiers {
+                storage: CStorageClass {
+                    typedef_c: false,
+                    extern_c: false,
+                    static_c: false,
+                    thread_local_c: false,
+                    auto_c: false,
+                    register_c: false,
+                    constexpr_c: false,
+                },
+                qualifiers: type_name.base.qualifiers.clone(),
+                specifiers: type_name.base.specifier.clone(),
+                function: CFunctionSpecifier {
+                    inline: false,
+                    no_return: false,
+                },
+                alignment: None,
+                attributes: vec![],
+            },
+            *type_name.declarator.inner.clone(),
+        )
+    }
+
+    /// a struct or union that was incomplete where it was named gets its members from the tag in scope
+    pub(crate) fn complete_type(&mut self, incomplete: PrettyType) -> PrettyType {
+        match &incomplete.inner_type {
+            ExtType::Struct {
+                is_const,
+                is_volatile,
+                tag: Some(tag),
+                members,
+                ..
+            }
+            | ExtType::Union {
+                is_const,
+                is_volatile,
+                tag: Some(tag),
+                members,
+                ..
+            } if members.is_empty() => match self.symbol_table.get_top_tag(tag) {
+                Some(tag_instance) => tag_instance
+                    .borrow()
+                    .tag_type
+                    .inner_type
+                    .clone()
+                    .qualified(*is_const, *is_volatile)
+                    .into_pretty(),
+                None => incomplete,
+            },
+            _ => incomplete,
         }
     }
 }
@@ -223,6 +546,150 @@ impl ExtType {
             inner_type: self.clone(),
         }
     }
+    /// (6.3.2.1) arrays and functions become pointers to them
+    pub(crate) fn decayed(self) -> ExtType {
+        match self {
+            ExtType::Array { to, .. } => ExtType::Pointer {
+                is_const: false,
+                is_volatile: false,
+                to,
+            },
+            function @ ExtType::Function { .. } => ExtType::Pointer {
+                is_const: false,
+                is_volatile: false,
+                to: Box::new(function),
+            },
+            other => other,
+        }
+    }
+    /// (6.5.2.2) default argument promotions, as applied to K&R parameters
+    pub(crate) fn default_argument_promoted(self) -> ExtType {
+        match self {
+            ExtType::Int { size, .. } if size < 4 => ExtType::Int {
+                is_const: false,
+                is_volatile: false,
+                signed: true,
+                size: 4,
+            },
+            ExtType::Float { size: 4, .. } => ExtType::Float {
+                is_const: false,
+                is_volatile: false,
+                size: 8,
+            },
+            ExtType::Array { to, .. } => ExtType::Pointer {
+                is_const: false,
+                is_volatile: false,
+                to,
+            },
+            ExtType::Function { .. } => ExtType::Pointer {
+                is_const: false,
+                is_volatile: false,
+                to: Box::new(self),
+            },
+            other => other.unqualified(),
+        }
+    }
+    /// (6.7.6.3) array and function parameters are adjusted to pointers
+    pub(crate) fn adjusted_parameter(self) -> ExtType {
+        match self {
+            ExtType::Array {
+                is_const,
+                is_volatile,
+                to,
+                ..
+            } => ExtType::Pointer {
+                is_const,
+                is_volatile,
+                to,
+            },
+            ExtType::Function { .. } => ExtType::Pointer {
+                is_const: false,
+                is_volatile: false,
+                to: Box::new(self),
+            },
+            other => other,
+        }
+    }
+    /// the same type with the top level const and volatile of a declaration added
+    pub(crate) fn qualified(mut self, const_q: bool, volatile_q: bool) -> ExtType {
+        match &mut self {
+            ExtType::Int {
+                is_const,
+                is_volatile,
+                ..
+            }
+            | ExtType::Float {
+                is_const,
+                is_volatile,
+                ..
+            }
+            | ExtType::Array {
+                is_const,
+                is_volatile,
+                ..
+            }
+            | ExtType::Pointer {
+                is_const,
+                is_volatile,
+                ..
+            }
+            | ExtType::Struct {
+                is_const,
+                is_volatile,
+                ..
+            }
+            | ExtType::Union {
+                is_const,
+                is_volatile,
+                ..
+            } => {
+                *is_const |= const_q;
+                *is_volatile |= volatile_q;
+            }
+            ExtType::Void | ExtType::Function { .. } => {}
+        }
+        self
+    }
+    /// the same type without top level const and volatile
+    pub(crate) fn unqualified(mut self) -> ExtType {
+        match &mut self {
+            ExtType::Int {
+                is_const,
+                is_volatile,
+                ..
+            }
+            | ExtType::Float {
+                is_const,
+                is_volatile,
+                ..
+            }
+            | ExtType::Array {
+                is_const,
+                is_volatile,
+                ..
+            }
+            | ExtType::Pointer {
+                is_const,
+                is_volatile,
+                ..
+            }
+            | ExtType::Struct {
+                is_const,
+                is_volatile,
+                ..
+            }
+            | ExtType::Union {
+                is_const,
+                is_volatile,
+                ..
+            } => {
+                *is_const = false;
+                *is_volatile = false;
+            }
+            ExtType::Void | ExtType::Function { .. } => {}
+        }
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -236,6 +703,29 @@ impl PrettyType {
             inner_type: ExtType::Void,
         }
     }
+    /// the signed result of subtracting pointers, long on x86-64
+    pub(crate) fn ptrdiff_t() -> PrettyType {
+        ExtType::Int {
+            is_const: false,
+            is_volatile: false,
+            signed: true,
+            size: 8,
+        }
+        .into_pretty()
+    }
+    /// the unsigned result of sizeof, unsigned long on x86-64
+    pub(crate) fn size_t() -> PrettyType {
+        ExtType::Int {
+            is_const: false,
+            is_volatile: false,
+            signed: false,
+            size: 8,
+        }
+        .into_pretty()
+    }
+    pub(crate) fn is_pointer(&self) -> bool {
+        matches!(self.inner_type, ExtType::Pointer { .. })
+    }
 }
 
 impl EnvironmentController {
@@ -263,38 +753,28 @@ impl EnvironmentController {
                 is_volatile: qualifiers.volatile_q,
                 to: Box::new(self.handle_derived_declarator_for_pretty_type(wrap_around, *to)),
             },
-            DerivedDeclarator::Array {
-                qualifiers,
-                is_static,
-                size_expr,
-                vla,
-                to,
-            } => {
-                if is_static {
-                    debug!("static in array ignored");
-                }
-                if vla {
-                    warn!("VLA unsupported, ignored in prettying type");
+            // the parser chains consecutive suffixes last one outermost, but for a[2][3]
+            // the first dimension is the outermost array, so the run is applied reversed
+            array @ DerivedDeclarator::Array { .. } => {
+                let mut dimensions = vec![];
+                let mut rest = array;
+                while let DerivedDeclarator::Array {
+                    qualifiers,
+                    is_static,
+                    size_expr,
+                    vla,
+                    to,
+                } = rest
+                {
+                    dimensions.push((qualifiers, is_static, size_expr, vla));
+                    rest = *to;
                 }
 
-                let arr_size: Option<u64> = if let Some(size_expr) = size_expr {
-                    let const_value = self.try_run_expression_at_compile_time((*size_expr).clone());
-                    if let CompileTimeValue::Int(val) = const_value {
-                        Some(val as u64)
-                    } else {
-                        size_expr.span.error_at_span("arr_size is no integer!");
-                        panic!();
-                    }
-                } else {
-                    None
-                };
-
-                ExtType::Array {
-                    is_const: qualifiers.const_q,
-                    is_volatile: qualifiers.volatile_q,
-                    arr_size,
-                    to: Box::new(self.handle_derived_declarator_for_pretty_type(wrap_around, *to)),
+                let mut array_type = self.handle_derived_declarator_for_pretty_type(wrap_around, rest);
+                for (qualifiers, is_static, size_expr, vla) in dimensions {
+                    array_type = self.wrap_array_dimension(array_type, qualifiers, is_static, size_expr, vla);
                 }
+                array_type
             }
             DerivedDeclarator::FunctionType {
                 parameter_type_list,
@@ -313,7 +793,7 @@ impl EnvironmentController {
                                 let name = declarator.base.identifier.clone();
                                 FunctionParameter{
                                     ident: name,
-                                    parameter_type: Box::new(para_type.inner_type),
+                                    parameter_type: Box::new(para_type.inner_type.adjusted_parameter()),
                                 }
                             },
                             AbstractDeclarator { specifiers, abstract_declarator } => {
@@ -327,131 +807,147 @@ impl EnvironmentController {
                                 // let name = declarator.base.identifier.clone();
                                 FunctionParameter{
                                     ident: String::from("__anon__"),
-                                    parameter_type: Box::new(para_type),
+                                    parameter_type: Box::new(para_type.adjusted_parameter()),
                                 }
                             },
                         }
                     }).collect(),
             },
+            // K&R: parameters are int until the declaration list of the definition says otherwise
             DerivedDeclarator::FunctionIdentified {
-                identifier_list: _,
-                to: _,
-            } => unimplemented!("DerivedDeclarator::FunctionIdentified unsupported currently :/"),
+                identifier_list,
+                to,
+            } => ExtType::Function {
+                overextendable: false,
+                returns: Box::new(self.handle_derived_declarator_for_pretty_type(wrap_around, *to)),
+                parameters: identifier_list
+                    .iter()
+                    .map(|ident| FunctionParameter {
+                        ident: ident.identifier.clone(),
+                        parameter_type: Box::new(ExtType::Int {
+                            is_const: false,
+                            is_volatile: false,
+                            signed: true,
+                            size: 4,
+                        }),
+                    })
+                    .collect(),
+            },
         }
     }
 }
 
 impl EnvironmentController {
+    fn wrap_array_dimension(
+        &mut self,
+        element_type: ExtType,
+        qualifiers: Spanned<CTypeQualifiers>,
+        is_static: bool,
+        size_expr: Option<Spanned<CExpression>>,
+        vla: bool,
+    ) -> ExtType {
+        if is_static {
+            debug!("static in array ignored");
+        }
+        if vla {
+            warn!("VLA unsupported, ignored in prettying type");
+        }
+
+        // (6.7.6.2) a size that is no integer constant expression makes a variable length array
+        let arr_size: Option<u64> = size_expr.map(|size_expr| {
+            let size = match self.try_run_expression_at_compile_time(&size_expr) {
+                Ok(CompileTimeValue::Int(size)) => size,
+                Ok(_) => size_expr
+                    .span
+                    .fail_at_span("size of array has non-integer type"),
+                Err(reason) => size_expr.span.fail_at_span(&format!(
+                    "variable length arrays are not supported: {}",
+                    reason
+                )),
+            };
+            if size < 0 {
+                size_expr.span.fail_at_span("array size is negative");
+            }
+            size as u64
+        });
+
+        ExtType::Array {
+            is_const: qualifiers.const_q,
+            is_volatile: qualifiers.volatile_q,
+            arr_size,
+            to: Box::new(element_type),
+        }
+    }
+}
+
+impl EnvironmentController {
+    /// `value` converted to the integer or pointer type `target_type`,
+    /// anything else is left as it is
+    pub(crate) fn convert_value(
+        &mut self,
+        ctx: &mut FunctionContext,
+        value: MIRValue,
+        target_type: &PrettyType,
+    ) -> MIRValue {
+        let value_type = ctx.mir_function.value_type_map_pretty.get(&value).unwrap();
+        let is_scalar = |pretty_type: &PrettyType| {
+            matches!(
+                pretty_type.inner_type,
+                ExtType::Int { .. } | ExtType::Pointer { .. }
+            )
+        };
+        if value_type == target_type || !is_scalar(value_type) || !is_scalar(target_type) {
+            return value;
+        }
+        let output_value = ctx
+            .mir_function
+            .make_intermediate_value_typed(target_type.clone());
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::IntConvert(
+                output_value,
+                value,
+                MIRType::extract_from_pretty_type(target_type),
+            ),
+        );
+        output_value
+    }
+
+    /// (6.3.1.8) converts both integer operands to their common type
     pub(crate) fn arithmatic_conversion(
         &mut self,
         ctx: &mut FunctionContext,
         left_value: MIRValue,
         right_value: MIRValue,
     ) -> (MIRValue, MIRValue) {
-        let left_type_orig = ctx
-            .mir_function
-            .value_type_map_pretty
-            .get(&left_value)
-            .unwrap();
-        let right_type_orig = ctx
-            .mir_function
-            .value_type_map_pretty
-            .get(&right_value)
-            .unwrap();
-        if left_type_orig == right_type_orig {
-            return (left_value, right_value);
-        }
-        match &left_type_orig.inner_type {
-            ExtType::Int {
-                is_const,
-                is_volatile,
-                signed,
-                size,
-            } => {
-                let left_is_const = *is_const;
-                let left_is_volatile = *is_volatile;
-                let left_is_signed = *signed;
-                let left_size = *size;
-                match &right_type_orig.inner_type {
-                    ExtType::Int {
-                        is_const,
-                        is_volatile,
-                        signed,
-                        size,
-                    } => {
-                        let right_is_const = *is_const;
-                        let right_is_volatile = *is_volatile;
-                        let right_is_signed = *signed;
-                        let right_size = *size;
-                        // both Int
-                        if !left_is_signed && !right_is_signed {
-                            let new_type = ExtType::Int {
-                                is_const: false,
-                                is_volatile: false,
-                                signed: false,
-                                size: left_size.max(right_size),
-                            }
-                            .into_pretty();
-                            let output_value_left = ctx
-                                .mir_function
-                                .make_intermediate_value_typed(new_type.clone());
-                            MIRBlock::ins_instr(
-                                &ctx.mir_function.current_block,
-                                MIRInstruction::IntConvert(
-                                    output_value_left,
-                                    left_value,
-                                    MIRType::extract_from_pretty_type(&new_type),
-                                ),
-                            );
-                            let output_value_right = ctx
-                                .mir_function
-                                .make_intermediate_value_typed(new_type.clone());
-                            MIRBlock::ins_instr(
-                                &ctx.mir_function.current_block,
-                                MIRInstruction::IntConvert(
-                                    output_value_right,
-                                    right_value,
-                                    MIRType::extract_from_pretty_type(&new_type),
-                                ),
-                            );
-                            return (output_value_left, output_value_right);
-                        } else {
-                            let new_type = ExtType::Int {
-                                is_const: false,
-                                is_volatile: false,
-                                signed: true,
-                                size: left_size.max(right_size),
-                            }
-                            .into_pretty();
-                            let output_value_left = ctx
-                                .mir_function
-                                .make_intermediate_value_typed(new_type.clone());
-                            MIRBlock::ins_instr(
-                                &ctx.mir_function.current_block,
-                                MIRInstruction::IntConvert(
-                                    output_value_left,
-                                    left_value,
-                                    MIRType::extract_from_pretty_type(&new_type),
-                                ),
-                            );
-                            let output_value_right = ctx
-                                .mir_function
-                                .make_intermediate_value_typed(new_type.clone());
-                            MIRBlock::ins_instr(
-                                &ctx.mir_function.current_block,
-                                MIRInstruction::IntConvert(
-                                    output_value_right,
-                                    right_value,
-                                    MIRType::extract_from_pretty_type(&new_type),
-                                ),
-                            );
-                            return (output_value_left, output_value_right);
-                        }
-                    }
-                    _ => unimplemented!(),
-                }
-            }
-            _ => unimplemented!(),
+        let value_type = |value: &MIRValue| {
+            ctx.mir_function
+                .value_type_map_pretty
+                .get(value)
+                .unwrap()
+                .inner_type
+                .clone()
+        };
+        let (left_type, right_type) = (value_type(&left_value), value_type(&right_value));
+        if !matches!(left_type, ExtType::Int { .. }) || !matches!(right_type, ExtType::Int { .. }) {
+            unimplemented!()
         }
+        let common_type = usual_arithmetic_conversion(&left_type, &right_type).into_pretty();
+        (
+            self.convert_value(ctx, left_value, &common_type),
+            self.convert_value(ctx, right_value, &common_type),
+        )
+    }
+}
+
+/// the layout never leaves out padding, so a packed struct or member would be laid out wrong
+fn reject_packed(attributes: &[Spanned<GnuAttribute>]) {
+    if let Some(packed) = attributes
+        .iter()
+        .find(|attribute| matches!(*attribute.inner, GnuAttribute::Packed))
+    {
+        packed
+            .span
+            .fail_at_span("__attribute__((packed)) is not supported");
     }
 }
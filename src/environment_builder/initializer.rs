@@ -0,0 +1,325 @@
+use crate::{
+    mir::MIRConstant,
+    parser::{
+        parse_nodes::{
+            declarations::{Designator, Initializer},
+            expressions::CExpression,
+        },
+        span::{Span, Spanned},
+        types::CTypeName,
+    },
+};
+
+use super::{
+    ext_type::{ExtType, PrettyType},
+    EnvironmentController,
+};
+
+/*
+(6.7.10) initialization of aggregates:
+    a brace list fills the elements or members of its object in order,
+    the current object is the innermost brace list or the aggregate the last designator went into
+    a designator like [2] or .member moves the position, the following initializers continue after it
+    an initializer without braces for an aggregate element starts filling that element,
+    the rest of it comes from the following initializers (brace elision)
+    a char array is initialized by a string literal, a struct or union by an expression of its type
+    a union takes one initializer, for the first member unless a designator names another one
+    everything that is not initialized explicitly is zero
+*/
+
+/// the part of an object one expression of an initializer writes
+#[derive(Debug, Clone)]
+pub(crate) struct InitializedElement {
+    pub(crate) offset: u64,
+    pub(crate) element_type: ExtType,
+    pub(crate) value: Spanned<CExpression>,
+}
+
+/// an aggregate a brace list is filling and the index of its next element
+struct CurrentObject {
+    object_type: ExtType,
+    offset: u64,
+    next: usize,
+}
+
+fn is_aggregate(object_type: &ExtType) -> bool {
+    matches!(
+        object_type,
+        ExtType::Array { .. } | ExtType::Struct { .. } | ExtType::Union { .. }
+    )
+}
+
+pub(crate) fn is_character_array(object_type: &ExtType) -> bool {
+    matches!(object_type, ExtType::Array { to, .. } if matches!(**to, ExtType::Int { size: 1, .. }))
+}
+
+/// the literal of a string literal initializer, parentheses allowed
+pub(crate) fn string_literal(expr: &Spanned<CExpression>) -> Option<&str> {
+    match &*expr.inner {
+        CExpression::StringLiteral(literal) => Some(&literal.value),
+        CExpression::Paranthesised(inner) => string_literal(inner),
+        _ => None,
+    }
+}
+
+impl EnvironmentController {
+    /// (6.5.2.5) the type of a compound literal, an array of unknown size is completed by its
+    /// initializer list like the one of a declaration
+    pub(crate) fn compound_literal_type(
+        &mut self,
+        type_name: &CTypeName,
+        initializer_list: &Spanned<Initializer>,
+    ) -> PrettyType {
+        let literal_type = self.extract_pretty_type_from_type_name(type_name);
+        let literal_type = self.complete_type(literal_type);
+        self.type_completed_by_initializer(literal_type, initializer_list)
+    }
+
+    /// the elements `initializer` writes into an object of `object_type`, in order, and the
+    /// number of elements it has, which is the size of an array declared without one
+    pub(crate) fn flatten_initializer(
+        &mut self,
+        object_type: &ExtType,
+        initializer: &Spanned<Initializer>,
+    ) -> (Vec<InitializedElement>, u64) {
+        let mut elements = vec![];
+        let count = match &*initializer.inner {
+            Initializer::Single(value) => {
+                elements.push(InitializedElement {
+                    offset: 0,
+                    element_type: object_type.clone(),
+                    value: value.clone(),
+                });
+                match string_literal(value) {
+                    Some(literal) if is_character_array(object_type) => {
+                        MIRConstant::from_string(literal.to_string()).value.len() as u64
+                    }
+                    _ => 1,
+                }
+            }
+            Initializer::Compound(list) => {
+                self.flatten_brace_list(object_type, 0, list, &mut elements)
+            }
+        };
+        (elements, count)
+    }
+
+    fn flatten_brace_list(
+        &mut self,
+        object_type: &ExtType,
+        offset: u64,
+        list: &[(Vec<Designator>, Spanned<Initializer>)],
+        elements: &mut Vec<InitializedElement>,
+    ) -> u64 {
+        // `int x = {1};` and `char s[] = {"text"};`
+        if !is_aggregate(object_type) || is_character_array(object_type) {
+            if let [(designators, initializer)] = list {
+                let is_whole = match &*initializer.inner {
+                    Initializer::Single(value) => {
+                        !is_aggregate(object_type) || string_literal(value).is_some()
+                    }
+                    Initializer::Compound(_) => !is_aggregate(object_type),
+                };
+                if designators.is_empty() && is_whole {
+                    let (inner_elements, count) =
+                        self.flatten_initializer(object_type, initializer);
+                    elements.extend(
+                        inner_elements
+                            .into_iter()
+                            .map(|element| InitializedElement {
+                                offset: offset + element.offset,
+                                ..element
+                            }),
+                    );
+                    return count;
+                }
+            }
+            if !is_aggregate(object_type) {
+                let span = list.first().map(|(_, initializer)| &initializer.span);
+                span.cloned()
+                    .unwrap_or_default()
+                    .fail_at_span("a scalar is initialized by exactly one expression")
+            }
+        }
+
+        let mut stack = vec![CurrentObject {
+            object_type: object_type.clone(),
+            offset,
+            next: 0,
+        }];
+        let mut count = 0;
+        for (designators, initializer) in list {
+            if !designators.is_empty() {
+                stack.truncate(1);
+                self.apply_designators(&mut stack, designators, &initializer.span);
+            }
+            loop {
+                let top = stack.last().unwrap();
+                let Some((element_type, element_offset)) =
+                    self.element_at(&top.object_type, top.next)
+                else {
+                    if stack.len() == 1 {
+                        initializer
+                            .span
+                            .fail_at_span("excess elements in initializer")
+                    }
+                    stack.pop();
+                    Self::advance(stack.last_mut().unwrap());
+                    continue;
+                };
+                let element_offset = top.offset + element_offset;
+                match &*initializer.inner {
+                    Initializer::Compound(inner) => {
+                        self.flatten_brace_list(&element_type, element_offset, inner, elements);
+                    }
+                    Initializer::Single(value) => {
+                        if is_aggregate(&element_type)
+                            && !self.initializes_whole(&element_type, value)
+                        {
+                            // brace elision, the following initializers fill the element
+                            stack.push(CurrentObject {
+                                object_type: element_type,
+                                offset: element_offset,
+                                next: 0,
+                            });
+                            continue;
+                        }
+                        elements.push(InitializedElement {
+                            offset: element_offset,
+                            element_type,
+                            value: value.clone(),
+                        });
+                    }
+                }
+                Self::advance(stack.last_mut().unwrap());
+                break;
+            }
+            // an element the outermost list is still filling counts too
+            let outermost = &stack[0];
+            count = count.max(outermost.next as u64 + (stack.len() > 1) as u64);
+        }
+        count
+    }
+
+    /// moves to the next element, a union is complete after one
+    fn advance(current: &mut CurrentObject) {
+        current.next = match &current.object_type {
+            ExtType::Union { members, .. } => members.len(),
+            _ => current.next + 1,
+        };
+    }
+
+    /// type and offset of the element at `index`, None past the end
+    fn element_at(&mut self, object_type: &ExtType, index: usize) -> Option<(ExtType, u64)> {
+        let (element_type, offset) = match object_type {
+            ExtType::Array { arr_size, to, .. } => {
+                if arr_size.is_some_and(|size| index as u64 >= size) {
+                    return None;
+                }
+                let element_type = self.complete_type(to.into_pretty()).inner_type;
+                let offset = index as u64 * element_type.size_of();
+                (element_type, offset)
+            }
+            ExtType::Struct { members, .. } | ExtType::Union { members, .. } => {
+                let member = members.get(index)?;
+                (
+                    (*member.member_type).clone(),
+                    object_type.member_offsets()[index],
+                )
+            }
+            _ => return None,
+        };
+        Some((
+            self.complete_type(element_type.into_pretty()).inner_type,
+            offset,
+        ))
+    }
+
+    /// true if `value` initializes all of an aggregate element instead of its first scalar
+    fn initializes_whole(&mut self, element_type: &ExtType, value: &Spanned<CExpression>) -> bool {
+        if is_character_array(element_type) && string_literal(value).is_some() {
+            return true;
+        }
+        if !matches!(element_type, ExtType::Struct { .. } | ExtType::Union { .. }) {
+            return false;
+        }
+        match self.type_of_constant_operand(value) {
+            Ok(value_type) => value_type.unqualified() == element_type.clone().unqualified(),
+            Err(_) => false,
+        }
+    }
+
+    /// positions the current objects at the element `designators` name
+    fn apply_designators(
+        &mut self,
+        stack: &mut Vec<CurrentObject>,
+        designators: &[Designator],
+        span: &Span,
+    ) {
+        for (position, designator) in designators.iter().enumerate() {
+            if position > 0 {
+                // the previous designator named an aggregate element, go into it
+                let top = stack.last().unwrap();
+                let (element_type, element_offset) =
+                    self.element_at(&top.object_type, top.next).unwrap();
+                if !is_aggregate(&element_type) {
+                    span.fail_at_span("designator for something that is not an aggregate")
+                }
+                let offset = top.offset + element_offset;
+                stack.push(CurrentObject {
+                    object_type: element_type,
+                    offset,
+                    next: 0,
+                });
+            }
+            match designator {
+                Designator::Array(index_expr) => {
+                    let ExtType::Array { arr_size, .. } = stack.last().unwrap().object_type else {
+                        span.fail_at_span("array designator for something that is not an array")
+                    };
+                    let index = self.expect_integer_constant(&index_expr.internal);
+                    if index < 0 || arr_size.is_some_and(|size| index as u64 >= size) {
+                        index_expr
+                            .internal
+                            .span
+                            .fail_at_span("array designator index out of bounds")
+                    }
+                    stack.last_mut().unwrap().next = index as usize;
+                }
+                Designator::Member(member) => loop {
+                    let top = stack.last().unwrap();
+                    let (ExtType::Struct { members, .. } | ExtType::Union { members, .. }) =
+                        &top.object_type
+                    else {
+                        span.fail_at_span("member designator for something that is not a struct")
+                    };
+                    if let Some(index) = members
+                        .iter()
+                        .position(|candidate| candidate.ident == member.identifier)
+                    {
+                        stack.last_mut().unwrap().next = index;
+                        break;
+                    }
+                    // members of anonymous members are named through them
+                    let Some(index) = members.iter().position(|candidate| {
+                        candidate.ident.is_empty()
+                            && candidate
+                                .member_type
+                                .find_member(&member.identifier)
+                                .is_some()
+                    }) else {
+                        span.fail_at_span(&format!("no member named '{}'", member.identifier))
+                    };
+                    let anonymous_type = (*members[index].member_type).clone();
+                    let offset = top.offset + top.object_type.member_offsets()[index];
+                    stack.last_mut().unwrap().next = index;
+                    stack.push(CurrentObject {
+                        object_type: anonymous_type,
+                        offset,
+                        next: 0,
+                    });
+                },
+            }
+        }
+    }
+}
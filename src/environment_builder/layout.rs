@@ -0,0 +1,179 @@
+/*
+System V x86-64 ABI 3.1.2 Data Representation:
+    scalars are aligned to their size, pointers are 8 bytes
+    arrays have the alignment of their element
+    structures and unions have the alignment of their most strictly aligned member,
+        each member is placed at the lowest offset that satisfies its alignment,
+        the size is rounded up to a multiple of the alignment (tail padding)
+    unions are as big as their biggest member, every member is at offset 0
+GNU: void and function types have size 1, so pointer arithmetic on them works
+*/
+
+use crate::mir::EightbyteClass;
+
+use super::ext_type::{ExtType, PrettyType, StructOrUnionMember};
+
+fn align_up(offset: u64, alignment: u64) -> u64 {
+    offset.div_ceil(alignment) * alignment
+}
+
+impl ExtType {
+    pub(crate) fn size_of(&self) -> u64 {
+        match self {
+            ExtType::Void | ExtType::Function { .. } => 1,
+            ExtType::Int { size, .. } | ExtType::Float { size, .. } => *size,
+            ExtType::Pointer { .. } => 8,
+            ExtType::Array { arr_size, to, .. } => arr_size.unwrap_or(0) * to.size_of(),
+            ExtType::Struct { members, .. } => {
+                let end = match self.member_offsets().last().zip(members.last()) {
+                    Some((offset, member)) => offset + member.member_type.size_of(),
+                    None => 0,
+                };
+                align_up(end, self.align_of())
+            }
+            ExtType::Union { members, .. } => {
+                let biggest = members
+                    .iter()
+                    .map(|member| member.member_type.size_of())
+                    .max()
+                    .unwrap_or(0);
+                align_up(biggest, self.align_of())
+            }
+        }
+    }
+
+    pub(crate) fn align_of(&self) -> u64 {
+        match self {
+            ExtType::Void | ExtType::Function { .. } => 1,
+            ExtType::Int { size, .. } | ExtType::Float { size, .. } => *size,
+            ExtType::Pointer { .. } => 8,
+            ExtType::Array { to, .. } => to.align_of(),
+            ExtType::Struct {
+                alignment, members, ..
+            }
+            | ExtType::Union {
+                alignment, members, ..
+            } => members
+                .iter()
+                .map(StructOrUnionMember::align_of)
+                .chain(*alignment)
+                .max()
+                .unwrap_or(1),
+        }
+    }
+
+    /// byte offset of every member, in declaration order
+    pub(crate) fn member_offsets(&self) -> Vec<u64> {
+        match self {
+            ExtType::Struct { members, .. } => {
+                let mut offset = 0;
+                members
+                    .iter()
+                    .map(|member| {
+                        let member_offset = align_up(offset, member.align_of());
+                        offset = member_offset + member.member_type.size_of();
+                        member_offset
+                    })
+                    .collect()
+            }
+            ExtType::Union { members, .. } => vec![0; members.len()],
+            _ => vec![],
+        }
+    }
+
+    /// offset and type of a member, looking through anonymous struct and union members
+    pub(crate) fn find_member(&self, ident: &str) -> Option<(u64, PrettyType)> {
+        let (is_const, is_volatile, members) = match self {
+            ExtType::Struct {
+                is_const,
+                is_volatile,
+                members,
+                ..
+            }
+            | ExtType::Union {
+                is_const,
+                is_volatile,
+                members,
+                ..
+            } => (*is_const, *is_volatile, members),
+            _ => return None,
+        };
+
+        for (member, offset) in members.iter().zip(self.member_offsets()) {
+            if member.ident == ident {
+                let member_type = (*member.member_type).clone();
+                return Some((
+                    offset,
+                    member_type.qualified(is_const, is_volatile).into_pretty(),
+                ));
+            }
+            if member.ident.is_empty() {
+                if let Some((inner_offset, member_type)) = member.member_type.find_member(ident) {
+                    return Some((
+                        offset + inner_offset,
+                        member_type
+                            .inner_type
+                            .qualified(is_const, is_volatile)
+                            .into_pretty(),
+                    ));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl ExtType {
+    /// System V classes of the two eightbytes an aggregate is passed in, see `EightbyteClass`
+    pub(crate) fn classify_eightbytes(&self) -> [EightbyteClass; 2] {
+        if self.size_of() > 16 {
+            return [EightbyteClass::Memory; 2];
+        }
+        let mut classes = [EightbyteClass::NoClass; 2];
+        self.classify_at(0, &mut classes);
+        // a MEMORY eightbyte makes all of it MEMORY
+        if classes.contains(&EightbyteClass::Memory) {
+            return [EightbyteClass::Memory; 2];
+        }
+        classes
+    }
+
+    fn classify_at(&self, offset: u64, classes: &mut [EightbyteClass; 2]) {
+        let class = match self {
+            ExtType::Int { .. } | ExtType::Pointer { .. } => EightbyteClass::Integer,
+            ExtType::Float { .. } => EightbyteClass::Sse,
+            ExtType::Array { arr_size, to, .. } => {
+                for index in 0..arr_size.unwrap_or(0) {
+                    to.classify_at(offset + index * to.size_of(), classes);
+                }
+                return;
+            }
+            ExtType::Struct { members, .. } | ExtType::Union { members, .. } => {
+                for (member, member_offset) in members.iter().zip(self.member_offsets()) {
+                    member
+                        .member_type
+                        .classify_at(offset + member_offset, classes);
+                }
+                return;
+            }
+            ExtType::Void | ExtType::Function { .. } => return,
+        };
+        let eightbyte = (offset / 8) as usize;
+        classes[eightbyte] = classes[eightbyte].merge(class);
+    }
+}
+
+impl StructOrUnionMember {
+    fn align_of(&self) -> u64 {
+        self.member_type.align_of().max(self.alignment.unwrap_or(0))
+    }
+}
+
+impl PrettyType {
+    pub(crate) fn size_of(&self) -> u64 {
+        self.inner_type.size_of()
+    }
+    pub(crate) fn align_of(&self) -> u64 {
+        self.inner_type.align_of()
+    }
+}
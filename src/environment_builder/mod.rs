@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use crate::mir::MIRProgramm;
 
 use self::symbol_table::BlockContainer;
@@ -7,6 +9,8 @@ mod tests;
 
 mod constant_expr;
 pub mod ext_type;
+mod initializer;
+mod layout;
 mod symbol_table;
 mod walker;
 
@@ -17,10 +21,16 @@ Need to know:
 
 */
 
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum CompileTimeValue {
     Int(i128),
     Float(f64),
     String(String),
+    /// address constant: the address of a file scope object or function plus a byte offset
+    Address {
+        symbol: String,
+        offset: i64,
+    },
 }
 
 /*
@@ -29,6 +39,8 @@ Need to Add new Type Representation that has the expr and stuff removed
 pub struct EnvironmentController {
     symbol_table: BlockContainer,
     mir_programm: MIRProgramm,
+    /// GNU: the label block ids `&&label` stands for in the static initializer being evaluated
+    label_addresses: BTreeMap<String, usize>,
 }
 
 impl EnvironmentController {
@@ -36,6 +48,7 @@ impl EnvironmentController {
         EnvironmentController {
             symbol_table: BlockContainer::new(),
             mir_programm: MIRProgramm::new(),
+            label_addresses: BTreeMap::new(),
         }
     }
     pub(crate) fn build(&mut self, ast: crate::parser::parse_nodes::TranslationUnit) {
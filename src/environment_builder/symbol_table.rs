@@ -45,6 +45,8 @@ use std::{cell::RefCell, collections::HashMap};
 use log::debug;
 use serde::{Deserialize, Serialize};
 
+use crate::mir::LocalRef;
+
 use super::ext_type::PrettyType;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -109,6 +111,18 @@ impl BlockContainer {
         }
         self.scope.tags.get(ident)
     }
+    /// the variable `ident` refers to, only if that is one declared at file scope
+    pub(crate) fn get_file_scope_variable(
+        &mut self,
+        ident: &str,
+    ) -> Option<&RefCell<VariableInstance>> {
+        if let Some(active) = &mut self.active_inner {
+            if active.get_top_variable(ident).is_some() {
+                return None;
+            }
+        }
+        self.scope.variables.get(ident)
+    }
     pub(crate) fn get_top_variable(&mut self, ident: &str) -> Option<&RefCell<VariableInstance>> {
         let result = {
             if let Some(active) = &mut self.active_inner {
@@ -150,6 +164,18 @@ pub(crate) struct VariableInstance {
     pub(crate) is_extern: bool,
     pub(crate) usage_counter: usize,
     pub(crate) associated_type: PrettyType,
+    /// the value of an enumeration constant, those are no objects
+    #[serde(default)]
+    pub(crate) constant_value: Option<i128>,
+    /// (6.7.1) the value of a constexpr object of integer type, it still is an object
+    #[serde(default)]
+    pub(crate) constexpr_value: Option<i128>,
+    /// the data object of a variable with static storage duration
+    #[serde(default)]
+    pub(crate) static_symbol: Option<String>,
+    /// the MIR local of a variable with automatic storage duration
+    #[serde(default)]
+    pub(crate) local: Option<LocalRef>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -0,0 +1,155 @@
+use super::*;
+
+use crate::{
+    environment_builder::CompileTimeValue, parser::parse_nodes::ExternalDeclaration, CStandard,
+};
+
+/// value of `expression` after the file scope declarations in `declarations`
+fn evaluate(declarations: &str, expression: &str) -> Result<CompileTimeValue, String> {
+    let mut env_controller = make_environment_controller();
+    let mut parser = run_lexer_with_return_that_init_parser(declarations);
+    for external_declaration in parser.parse() {
+        if let ExternalDeclaration::Declaration(declaration) = &*external_declaration {
+            env_controller.handle_external_declaration(declaration);
+        }
+    }
+    let expression = parser_parse_specific(expression, CParser::parse_expression);
+    env_controller.try_run_expression_at_compile_time(&expression)
+}
+
+fn evaluate_int(declarations: &str, expression: &str) -> i128 {
+    match evaluate(declarations, expression) {
+        Ok(CompileTimeValue::Int(value)) => value,
+        other => panic!("{} is no integer constant: {:?}", expression, other),
+    }
+}
+
+#[test]
+fn constant_integer_arithmetic_is_done_in_its_type() {
+    assert_eq!(evaluate_int("", "1 + 2 * 3 - 8 / 3 % 2"), 7);
+    assert_eq!(evaluate_int("", "-7 / 2"), -3);
+    assert_eq!(evaluate_int("", "(long)2147483647 + 1"), 2147483648);
+    assert_eq!(evaluate_int("", "(unsigned)0 - 1"), 4294967295);
+    assert_eq!(evaluate_int("", "-1 < (unsigned)0"), 0);
+    assert_eq!(evaluate_int("", "(long)-1 < (unsigned)0"), 1);
+    assert_eq!(evaluate_int("", "(unsigned char)300"), 44);
+    assert_eq!(evaluate_int("", "(signed char)200"), -56);
+    assert_eq!(evaluate_int("", "0xffffffff"), 4294967295);
+    assert_eq!(evaluate_int("", "sizeof(4294967296)"), 8);
+    assert_eq!(evaluate_int("", "~(unsigned)0 >> 28"), 15);
+    assert_eq!(evaluate_int("", "(1 << 4 | 3) ^ 1 & 7"), 18);
+}
+
+#[test]
+fn signed_overflow_is_not_a_constant_expression() {
+    let overflow = Err("integer overflow in constant expression".to_string());
+    assert_eq!(evaluate("", "2147483647 + 1"), overflow);
+    assert_eq!(evaluate("", "-2147483647 - 2"), overflow);
+    assert_eq!(evaluate("", "65536 * 65536"), overflow);
+    assert_eq!(evaluate("", "-(-2147483647 - 1)"), overflow);
+    assert_eq!(evaluate("", "(-2147483647 - 1) / -1"), overflow);
+    assert_eq!(evaluate("", "(-2147483647 - 1) % -1"), overflow);
+    assert_eq!(evaluate("", "1 << 31"), overflow);
+    // unsigned arithmetic wraps
+    assert_eq!(evaluate_int("", "(unsigned)4294967295 + 1"), 0);
+    assert_eq!(evaluate_int("", "(unsigned)65536 * 65536"), 0);
+}
+
+#[test]
+fn constant_floating_arithmetic_and_casts() {
+    assert_eq!(evaluate("", "1.5 * 4"), Ok(CompileTimeValue::Float(6.0)));
+    assert_eq!(evaluate_int("", "(int)(7.9 / 2)"), 3);
+    assert_eq!(evaluate_int("", "(int)-2.5"), -2);
+    assert_eq!(evaluate_int("", "(float)0.1 == 0.1"), 0);
+    assert_eq!(evaluate_int("", "sizeof((float)1 + 1)"), 4);
+}
+
+#[test]
+fn constant_logical_relational_and_ternary() {
+    assert_eq!(evaluate_int("", "3 > 2 && 2 >= 2 && !(1 != 1)"), 1);
+    assert_eq!(evaluate_int("", "0 || 0"), 0);
+    // the right side of && is not evaluated
+    assert_eq!(evaluate_int("", "0 && 1 / 0"), 0);
+    assert_eq!(evaluate_int("", "1 ? 5 : 1 / 0"), 5);
+    assert_eq!(evaluate_int("", "sizeof(0 ? 1 : (long)2)"), 8);
+}
+
+#[test]
+fn constant_sizeof_alignof_and_enums() {
+    let declarations = r#"
+struct pair { char c; long l; };
+enum sizes { SMALL = sizeof(short), BIG = SMALL * 4 };
+int table[3][5];
+"#;
+    assert_eq!(evaluate_int(declarations, "sizeof(struct pair)"), 16);
+    assert_eq!(evaluate_int(declarations, "_Alignof(struct pair)"), 8);
+    assert_eq!(evaluate_int(declarations, "BIG - SMALL"), 6);
+    assert_eq!(
+        evaluate_int(declarations, "sizeof table / sizeof table[0]"),
+        3
+    );
+    assert_eq!(evaluate_int(declarations, "sizeof(table[1][2])"), 4);
+}
+
+#[test]
+fn constant_addresses_of_file_scope_objects() {
+    let declarations = r#"
+struct pair { int first; int second[4]; };
+struct pair pairs[2];
+int global;
+"#;
+    let address = |symbol: &str, offset| {
+        Ok(CompileTimeValue::Address {
+            symbol: symbol.to_string(),
+            offset,
+        })
+    };
+    assert_eq!(evaluate(declarations, "&global"), address("global", 0));
+    assert_eq!(
+        evaluate(declarations, "(char *)&global + 4"),
+        address("global", 4)
+    );
+    assert_eq!(
+        evaluate(declarations, "&pairs[1].second[2]"),
+        address("pairs", 32)
+    );
+    assert_eq!(evaluate(declarations, "pairs + 1"), address("pairs", 20));
+    assert_eq!(evaluate_int(declarations, "&pairs[1] - pairs"), 1);
+    assert_eq!(evaluate_int(declarations, "&global != 0"), 1);
+}
+
+#[test]
+fn constant_expression_errors() {
+    let declarations = "int global;";
+    assert_eq!(evaluate("", "1 / 0"), Err("division by zero".to_string()));
+    assert!(evaluate("", "1 << 32").is_err());
+    assert!(evaluate(declarations, "global + 1").is_err());
+    assert!(evaluate(declarations, "global = 1").is_err());
+    assert!(evaluate(declarations, "(1, 2)").is_err());
+}
+
+#[test]
+fn constexpr_objects_are_constant() {
+    walk_translation_unit_with_standard(
+        r#"
+constexpr int K = 1'000;
+constexpr unsigned char small = K / 4;
+static_assert(K == 1000, "k");
+static_assert(small + 1 == 251, "small");
+int table[K / 100];
+static_assert(sizeof(table) == 40, "table");
+int f() {
+    constexpr long local = K * 2;
+    static_assert(local == 2000, "local");
+    return local;
+}
+"#,
+        CStandard::C23,
+    );
+}
+
+#[test]
+#[should_panic]
+fn constexpr_value_must_be_representable() {
+    walk_translation_unit_with_standard("constexpr unsigned char c = 256;", CStandard::C23);
+}
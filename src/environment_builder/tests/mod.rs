@@ -1,11 +1,14 @@
 use crate::{
     lexer::Lexer,
     parser::{parse_nodes::declarations::Declaration, CParser},
+    CStandard,
 };
 
 use super::EnvironmentController;
 
+mod constant_expr;
 mod pretty_type;
+mod static_storage;
 
 pub(crate) fn make_environment_controller() -> EnvironmentController {
     EnvironmentController::new()
@@ -30,3 +33,14 @@ where
     let mut simple_parser = run_lexer_with_return_that_init_parser(code);
     func(&mut simple_parser)
 }
+
+/// walks all of `code` as a translation unit in the language `standard`
+pub(crate) fn walk_translation_unit_with_standard(code: &str, standard: CStandard) {
+    init();
+
+    let lexed = Lexer::new()
+        .with_standard(standard)
+        .string_to_token_arr(code.to_string());
+    let mut parser = CParser::new(lexed).with_standard(standard);
+    make_environment_controller().walk_translation_unit(parser.parse());
+}
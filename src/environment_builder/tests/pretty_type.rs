@@ -1,5 +1,11 @@
 use super::*;
 
+use crate::{
+    environment_builder::ext_type::ExtType,
+    parser::{parse_nodes::ExternalDeclaration, partial::catch_quietly},
+    CStandard,
+};
+
 #[test]
 fn intern_basic_declaration() {
     let code = r#"const int ptr_to_constant;"#;
@@ -158,3 +164,291 @@ fn intern_simple_struct() {
         }
     }
 }
+
+#[test]
+fn intern_kr_function_signature() {
+    let code = r#"int scale(n, c, s) register n; char c; unsigned short s; { return n; }"#;
+
+    let parsed = parser_parse_specific(code, CParser::parse_function_definition);
+    let mut env_controller = make_environment_controller();
+    env_controller.walk_func(parsed);
+
+    let got_result = env_controller
+        .symbol_table
+        .get_top_variable("scale")
+        .unwrap()
+        .borrow()
+        .associated_type
+        .clone();
+
+    println!("{}", serde_yaml::to_string(&got_result).unwrap());
+
+    // char and unsigned short get the default argument promotions
+    let expected_yaml = r#"
+inner_type:
+  Function:
+    overextendable: false
+    returns:
+      Int:
+        is_const: false
+        is_volatile: false
+        signed: true
+        size: 4
+    parameters:
+      - ident: n
+        parameter_type:
+          Int:
+            is_const: false
+            is_volatile: false
+            signed: true
+            size: 4
+      - ident: c
+        parameter_type:
+          Int:
+            is_const: false
+            is_volatile: false
+            signed: true
+            size: 4
+      - ident: s
+        parameter_type:
+          Int:
+            is_const: false
+            is_volatile: false
+            signed: true
+            size: 4
+    "#;
+    let expected_result = serde_yaml::from_str(expected_yaml).unwrap();
+
+    assert_eq!(got_result, expected_result);
+}
+
+/// extracts the type of the first declarator, the struct tags in `code` are registered on the way
+fn layout_of_declaration(code: &str) -> ExtType {
+    let mut parser = run_lexer_with_return_that_init_parser(code);
+    let mut env_controller = make_environment_controller();
+    let mut last_type = None;
+    for external_declaration in parser.parse() {
+        let ExternalDeclaration::Declaration(declaration) = &*external_declaration else {
+            continue;
+        };
+        if let Declaration::Declaration { specifiers, init } = (*declaration.inner).clone() {
+            last_type = Some(match init.first() {
+                Some((declarator, _)) => {
+                    env_controller
+                        .extract_pretty_type_from_declaration_specifiers_and_derived_declarator(
+                            specifiers,
+                            declarator.derive.clone(),
+                        )
+                        .inner_type
+                }
+                None => {
+                    env_controller.extract_base_ext_type_from_declaration_specifiers(specifiers)
+                }
+            });
+        }
+    }
+    last_type.unwrap()
+}
+
+#[test]
+fn intern_struct_layout() {
+    let code = r#"
+struct point {
+    char tag;
+    long x;
+    short y;
+};"#;
+    let point = layout_of_declaration(code);
+
+    // tag at 0, x aligned to 8, y at 16, tail padding up to 24
+    assert_eq!(point.member_offsets(), vec![0, 8, 16]);
+    assert_eq!(point.size_of(), 24);
+    assert_eq!(point.align_of(), 8);
+}
+
+#[test]
+fn intern_union_and_nested_layout() {
+    let code = r#"
+union number { char c; int i; };
+struct outer {
+    char c;
+    union number n;
+    struct { short s; char t; };
+    char *p;
+};"#;
+    let outer = layout_of_declaration(code);
+
+    assert_eq!(outer.member_offsets(), vec![0, 4, 8, 16]);
+    assert_eq!(outer.size_of(), 24);
+    // through the anonymous struct
+    let (offset, member_type) = outer.find_member("t").unwrap();
+    assert_eq!(offset, 10);
+    assert_eq!(member_type.size_of(), 1);
+    assert!(outer.find_member("missing").is_none());
+}
+
+#[test]
+fn intern_struct_alignas() {
+    let code = r#"
+struct aligned {
+    char c;
+    _Alignas(16) char d;
+    int i __attribute__((aligned(8)));
+};"#;
+    let aligned = layout_of_declaration(code);
+
+    assert_eq!(aligned.member_offsets(), vec![0, 16, 24]);
+    assert_eq!(aligned.align_of(), 16);
+    assert_eq!(aligned.size_of(), 32);
+}
+
+#[test]
+fn intern_struct_eightbyte_classes() {
+    use crate::mir::EightbyteClass::*;
+
+    let small = layout_of_declaration("struct small { char c; int i; };");
+    assert_eq!(small.classify_eightbytes(), [Integer, NoClass]);
+
+    let mixed = layout_of_declaration("struct mixed { double d; long l; };");
+    assert_eq!(mixed.classify_eightbytes(), [Sse, Integer]);
+
+    let floats = layout_of_declaration("struct floats { float x; float y; int i; };");
+    assert_eq!(floats.classify_eightbytes(), [Sse, Integer]);
+
+    let big = layout_of_declaration("struct big { long x; long y; char c; };");
+    assert_eq!(big.classify_eightbytes(), [Memory, Memory]);
+}
+
+#[test]
+fn bit_fields_and_packed_are_diagnosed_at_their_span() {
+    let diagnose = |code: &str| {
+        let caught = catch_quietly(|| layout_of_declaration(code)).unwrap_err();
+        (caught.message, caught.span.unwrap().start.line)
+    };
+
+    assert_eq!(
+        diagnose("struct flags {\n    int ready;\n    int mode : 3;\n};"),
+        ("bit-fields are not supported".to_string(), 2)
+    );
+    assert_eq!(
+        diagnose("struct __attribute__((packed)) header {\n    char tag;\n    int size;\n};"),
+        ("__attribute__((packed)) is not supported".to_string(), 0)
+    );
+    assert_eq!(
+        diagnose("struct header {\n    char tag;\n    int size __attribute__((packed));\n};"),
+        ("__attribute__((packed)) is not supported".to_string(), 2)
+    );
+}
+
+#[test]
+fn variable_length_arrays_are_diagnosed_at_their_size() {
+    let code = "int f(int n) {\n    int a[n];\n    return 0;\n}";
+    let caught =
+        catch_quietly(|| walk_translation_unit_with_standard(code, CStandard::C11)).unwrap_err();
+
+    assert_eq!(
+        caught.message,
+        "variable length arrays are not supported: the value of 'n' is not constant"
+    );
+    assert_eq!(caught.span.unwrap().start.line, 1);
+}
+
+#[test]
+fn intern_multidimensional_array() {
+    let grid = layout_of_declaration("long grid[2][3];");
+    let ExtType::Array { arr_size, to, .. } = &grid else {
+        panic!("not an array: {:?}", grid)
+    };
+    assert_eq!(*arr_size, Some(2));
+    assert!(matches!(
+        **to,
+        ExtType::Array {
+            arr_size: Some(3),
+            ..
+        }
+    ));
+    assert_eq!((grid.size_of(), to.size_of(), grid.align_of()), (48, 24, 8));
+
+    let rows = layout_of_declaration("struct rows { char tag; short cells[2][5]; };");
+    assert_eq!((rows.size_of(), rows.align_of()), (22, 2));
+}
+
+#[test]
+fn intern_array_parameter_adjusted_to_pointer() {
+    let function = layout_of_declaration("long corner(long g[][3], int values[8]);");
+    let ExtType::Function { parameters, .. } = &function else {
+        panic!("not a function: {:?}", function)
+    };
+    let ExtType::Pointer { to, .. } = &*parameters[0].parameter_type else {
+        panic!("not adjusted: {:?}", parameters[0])
+    };
+    assert!(matches!(
+        **to,
+        ExtType::Array {
+            arr_size: Some(3),
+            ..
+        }
+    ));
+    assert!(matches!(
+        *parameters[1].parameter_type,
+        ExtType::Pointer { .. }
+    ));
+}
+
+#[test]
+fn intern_enum_constants_as_array_sizes() {
+    let implicit = layout_of_declaration("enum letters { A, B, C }; char buffer[C];");
+    assert_eq!(implicit.size_of(), 2);
+
+    let explicit = layout_of_declaration("enum e { X = 3, Y, Z = (Y), W }; long values[W];");
+    assert!(matches!(
+        explicit,
+        ExtType::Array {
+            arr_size: Some(5),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn intern_enum_tag_is_int() {
+    let tagged = layout_of_declaration("enum color { RED, GREEN }; const enum color c;");
+    assert_eq!(
+        tagged,
+        ExtType::Int {
+            is_const: true,
+            is_volatile: false,
+            signed: true,
+            size: 4,
+        }
+    );
+}
+
+#[test]
+fn typeof_expressions_are_not_evaluated() {
+    walk_translation_unit_with_standard(
+        r#"
+struct pair { char tag; long value; };
+int *p;
+struct pair pairs[3];
+long get(void);
+__typeof__(*p) deref;
+__typeof__(pairs[1].value) member;
+__typeof__(&pairs[0]) address;
+__typeof__(get()) returned;
+__typeof__(pairs) whole;
+_Static_assert(sizeof(deref) == 4, "*p is an int");
+_Static_assert(sizeof(member) == 8, "pairs[1].value is a long");
+_Static_assert(sizeof(*address) == 16, "&pairs[0] points to a pair");
+_Static_assert(sizeof(returned) == 8, "get() returns a long");
+_Static_assert(sizeof(whole) == 48, "arrays do not decay");
+"#,
+        CStandard::Gnu11,
+    );
+}
+
+#[test]
+#[should_panic]
+fn typeof_of_undeclared_identifier() {
+    walk_translation_unit_with_standard("__typeof__(*missing) value;", CStandard::Gnu11);
+}
@@ -0,0 +1,127 @@
+use super::*;
+
+use crate::mir::{GlobalDefinition, GlobalEntity, GlobalRelocation};
+
+/// the globals of `code` by name
+fn globals_of(code: &str) -> Vec<GlobalEntity> {
+    let mut parser = run_lexer_with_return_that_init_parser(code);
+    let mut env_controller = make_environment_controller();
+    env_controller.walk_translation_unit(parser.parse());
+    env_controller.mir_programm.globals
+}
+
+fn definition_of<'a>(globals: &'a [GlobalEntity], name: &str) -> &'a GlobalDefinition {
+    globals
+        .iter()
+        .find(|global| global.name == name)
+        .and_then(|global| global.definition.as_ref())
+        .unwrap_or_else(|| panic!("{} is not defined", name))
+}
+
+#[test]
+fn static_objects_are_zero_or_initialized_data() {
+    let globals = globals_of(
+        r#"
+int zero;
+static short answer = 40 + 2;
+const char text[] = "hi";
+extern long elsewhere;
+int zero;
+"#,
+    );
+    assert_eq!(globals.len(), 4);
+
+    let zero = definition_of(&globals, "zero");
+    assert_eq!(
+        (zero.size, zero.bytes.clone(), zero.writable),
+        (4, None, true)
+    );
+
+    let answer = definition_of(&globals, "answer");
+    assert_eq!(answer.bytes, Some(vec![42, 0]));
+    assert!(!globals[1].extern_linkage);
+
+    let text = definition_of(&globals, "text");
+    assert_eq!(
+        (text.bytes.clone(), text.writable),
+        (Some(b"hi\0".to_vec()), false)
+    );
+
+    let elsewhere = globals.iter().find(|global| global.name == "elsewhere");
+    assert_eq!(elsewhere.unwrap().definition, None);
+}
+
+#[test]
+fn static_address_initializers_are_relocations() {
+    let globals = globals_of(
+        r#"
+int values[4];
+int *third = &values[2];
+const char *name = "jank";
+"#,
+    );
+    assert_eq!(
+        definition_of(&globals, "third").relocations,
+        vec![GlobalRelocation {
+            offset: 0,
+            symbol: "values".to_string(),
+            addend: 8,
+            function: None,
+        }]
+    );
+
+    let name = &definition_of(&globals, "name").relocations[0];
+    assert_eq!(
+        definition_of(&globals, &name.symbol).bytes,
+        Some(b"jank\0".to_vec())
+    );
+}
+
+#[test]
+fn static_brace_initializers_with_designators() {
+    let globals = globals_of(
+        r#"
+struct pair { char first; short second; };
+struct pair pairs[] = { [1] = { .second = 0x0302 }, 4, 5 };
+char nested[2][2] = { 1, { 2 }, };
+"#,
+    );
+    let pairs = definition_of(&globals, "pairs");
+    assert_eq!(pairs.size, 12);
+    assert_eq!(pairs.bytes, Some(vec![0, 0, 0, 0, 0, 0, 2, 3, 4, 0, 5, 0]));
+    // after brace elision `{ 2 }` is the braced scalar nested[0][1]
+    assert_eq!(
+        definition_of(&globals, "nested").bytes,
+        Some(vec![1, 2, 0, 0])
+    );
+}
+
+#[test]
+fn file_scope_compound_literals_are_static_objects() {
+    let globals = globals_of(
+        r#"
+struct point { int x; int y; };
+int *primes = (int[]){ 2, 3, 5 };
+struct point origin = (struct point){ .y = 1 };
+"#,
+    );
+    let literal = definition_of(&globals, ".L.compoundliteral.0");
+    assert_eq!(
+        literal.bytes,
+        Some(vec![2, 0, 0, 0, 3, 0, 0, 0, 5, 0, 0, 0])
+    );
+
+    let primes = definition_of(&globals, "primes");
+    assert_eq!(
+        primes.relocations,
+        vec![GlobalRelocation {
+            offset: 0,
+            symbol: ".L.compoundliteral.0".to_string(),
+            addend: 0,
+            function: None,
+        }]
+    );
+
+    let origin = definition_of(&globals, "origin");
+    assert_eq!(origin.bytes, Some(vec![0, 0, 0, 0, 1, 0, 0, 0]));
+}
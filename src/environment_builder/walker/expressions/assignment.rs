@@ -0,0 +1,154 @@
+use crate::{
+    environment_builder::{
+        constant_expr::{promoted, usual_arithmetic_conversion},
+        ext_type::{ExtType, PrettyType},
+        EnvironmentController,
+    },
+    mir::{IntMathKind, MIRBlock, MIRInstruction, MIRType, MIRValue},
+    parser::{
+        parse_nodes::expressions::{AssignmentOperator, CExpression, IncrementType},
+        span::Spanned,
+    },
+};
+
+use super::walk_func::FunctionContext;
+
+/*
+(6.5.16.2) a op= b is a = a op b, but the lvalue a is only evaluated once:
+    its location is walked once, read, combined with b and the result is stored back to it
+    a pointer a with += or -= moves by elements, like a + b
+(6.5.3.1) ++a and --a are a += 1 and a -= 1
+(6.5.2.4) a++ and a-- store the same, but their value is the one a had before
+*/
+impl EnvironmentController {
+    pub(crate) fn walk_compound_assignment(
+        &mut self,
+        ctx: &mut FunctionContext,
+        to_assign: &Spanned<CExpression>,
+        operator: &AssignmentOperator,
+        value: &Spanned<CExpression>,
+        wanted_type: &PrettyType,
+    ) -> MIRValue {
+        let math_kind = match operator {
+            AssignmentOperator::Assign => unreachable!("plain assignment is no compound one"),
+            AssignmentOperator::AssignMult => IntMathKind::Mul,
+            AssignmentOperator::AssignDiv => IntMathKind::Div,
+            AssignmentOperator::AssignMod => IntMathKind::Mod,
+            AssignmentOperator::AssignPlus => IntMathKind::Add,
+            AssignmentOperator::AssignMinus => IntMathKind::Sub,
+            AssignmentOperator::AssignShiftLeft => IntMathKind::Shl,
+            AssignmentOperator::AssignShiftRight => IntMathKind::Shr,
+            AssignmentOperator::AssignAnd => IntMathKind::And,
+            AssignmentOperator::AssignXor => IntMathKind::Xor,
+            AssignmentOperator::AssignOr => IntMathKind::Or,
+        };
+        let lvalue = self.walk_expression_get_lvalue(ctx, to_assign.clone(), wanted_type);
+        let lvalue_type = lvalue.get_pretty_type();
+        let current = lvalue.into_rvlaue(ctx);
+
+        let result = if lvalue_type.is_pointer() {
+            if !matches!(math_kind, IntMathKind::Add | IntMathKind::Sub) {
+                to_assign
+                    .span
+                    .fail_at_span("invalid compound assignment to a pointer")
+            }
+            let offset = self.walk_expression(ctx, value.clone(), &PrettyType::ptrdiff_t());
+            self.offset_pointer(ctx, current, offset, math_kind)
+        } else {
+            let operand_type = Self::arithmetic_operand_type(&lvalue_type);
+            let operand = self.walk_expression(ctx, value.clone(), &operand_type);
+            let result = self.walk_int_math_on_values(ctx, current, operand, math_kind, value);
+            self.convert_value(ctx, result, &lvalue_type)
+        };
+        lvalue.assign_value(ctx, result);
+        result
+    }
+
+    pub(crate) fn walk_increment(
+        &mut self,
+        ctx: &mut FunctionContext,
+        increment_type: &IncrementType,
+        value: &Spanned<CExpression>,
+        is_postfix: bool,
+    ) -> MIRValue {
+        let math_kind = match increment_type {
+            IncrementType::Increment => IntMathKind::Add,
+            IncrementType::Decrement => IntMathKind::Sub,
+        };
+        let lvalue =
+            self.walk_expression_get_lvalue(ctx, value.clone(), &PrettyType::default_void());
+        let lvalue_type = lvalue.get_pretty_type();
+        let old_value = lvalue.into_rvlaue(ctx);
+
+        let new_value = match &lvalue_type.inner_type {
+            ExtType::Pointer { .. } => {
+                let one = Self::walk_one(ctx, &PrettyType::ptrdiff_t());
+                self.offset_pointer(ctx, old_value, one, math_kind)
+            }
+            ExtType::Int { .. } => {
+                let one = Self::walk_one(ctx, &lvalue_type);
+                let new_value = ctx
+                    .mir_function
+                    .make_intermediate_value_typed(lvalue_type.clone());
+                MIRBlock::ins_instr(
+                    &ctx.mir_function.current_block,
+                    MIRInstruction::IntMath(new_value, old_value, one, math_kind),
+                );
+                new_value
+            }
+            _ => value
+                .span
+                .fail_at_span("increment or decrement of something that is no integer or pointer"),
+        };
+        lvalue.assign_value(ctx, new_value);
+
+        if is_postfix {
+            old_value
+        } else {
+            new_value
+        }
+    }
+
+    fn walk_one(ctx: &mut FunctionContext, one_type: &PrettyType) -> MIRValue {
+        let one = ctx
+            .mir_function
+            .make_intermediate_value_typed(one_type.clone());
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::ConstNum(one, 1, MIRType::extract_from_pretty_type(one_type)),
+        );
+        one
+    }
+
+    /// `left` combined with `right` in the common type, shifts keep the promoted left type
+    fn walk_int_math_on_values(
+        &mut self,
+        ctx: &mut FunctionContext,
+        left: MIRValue,
+        right: MIRValue,
+        math_kind: IntMathKind,
+        expression: &Spanned<CExpression>,
+    ) -> MIRValue {
+        let left_type = &ctx.mir_function.value_type_map_pretty[&left].inner_type;
+        let right_type = &ctx.mir_function.value_type_map_pretty[&right].inner_type;
+        if !matches!(left_type, ExtType::Int { .. }) || !matches!(right_type, ExtType::Int { .. }) {
+            expression
+                .span
+                .fail_at_span("compound assignment operand is not an integer")
+        }
+        let result_type = match math_kind {
+            IntMathKind::Shl | IntMathKind::Shr => promoted(left_type),
+            _ => usual_arithmetic_conversion(left_type, right_type),
+        }
+        .into_pretty();
+        let left = self.convert_value(ctx, left, &result_type);
+        let right = self.convert_value(ctx, right, &result_type);
+
+        let result = ctx.mir_function.make_intermediate_value_typed(result_type);
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::IntMath(result, left, right, math_kind),
+        );
+        result
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{
+    environment_builder::{
+        constant_expr::{promoted, usual_arithmetic_conversion},
+        ext_type::{ExtType, PrettyType},
+        EnvironmentController,
+    },
+    mir::{IntMathKind, MIRBlock, MIRInstruction, MIRValue},
+    parser::{
+        parse_nodes::expressions::{CExpression, ShiftOperator},
+        span::Spanned,
+    },
+};
+
+use super::walk_func::FunctionContext;
+
+/*
+(6.5.10) (6.5.11) (6.5.12) & ^ | take integer operands after the usual arithmetic conversions,
+    a chain like a & b & c is folded from the left
+(6.5.7) << and >> promote both operands on their own, the result has the type of the left one,
+    >> is arithmetic for signed and logical for unsigned left operands
+(6.5.3.3) ~ complements the promoted operand
+*/
+impl EnvironmentController {
+    pub(crate) fn walk_bitwise_operator(
+        &mut self,
+        ctx: &mut FunctionContext,
+        operands: &[Spanned<CExpression>],
+        math_kind: IntMathKind,
+        wanted_type: &PrettyType,
+    ) -> MIRValue {
+        let operand_type = Self::arithmetic_operand_type(wanted_type);
+        let (first, rest) = operands.split_first().unwrap();
+        let mut result = self.walk_expression(ctx, first.clone(), &operand_type);
+        let mut result_type = Self::expect_integer_operand(ctx, result, first);
+        for operand in rest {
+            let right_value = self.walk_expression(ctx, operand.clone(), &operand_type);
+            let right_type = Self::expect_integer_operand(ctx, right_value, operand);
+            result_type = usual_arithmetic_conversion(&result_type, &right_type);
+            let common_type = result_type.clone().into_pretty();
+            let left_value = self.convert_value(ctx, result, &common_type);
+            let right_value = self.convert_value(ctx, right_value, &common_type);
+
+            result = ctx.mir_function.make_intermediate_value_typed(common_type);
+            MIRBlock::ins_instr(
+                &ctx.mir_function.current_block,
+                MIRInstruction::IntMath(result, left_value, right_value, math_kind.clone()),
+            );
+        }
+        result
+    }
+
+    pub(crate) fn walk_shift(
+        &mut self,
+        ctx: &mut FunctionContext,
+        value_expr: &Spanned<CExpression>,
+        shift_type: &ShiftOperator,
+        shift_amount: &Spanned<CExpression>,
+        wanted_type: &PrettyType,
+    ) -> MIRValue {
+        let operand_type = Self::arithmetic_operand_type(wanted_type);
+        let value = self.walk_expression(ctx, value_expr.clone(), &operand_type);
+        let result_type = promoted(&Self::expect_integer_operand(ctx, value, value_expr));
+        let value = self.convert_value(ctx, value, &result_type.clone().into_pretty());
+
+        // the backend wants the amount in the width of the shifted value
+        let amount = self.walk_expression(
+            ctx,
+            shift_amount.clone(),
+            &ExtType::Int {
+                is_const: false,
+                is_volatile: false,
+                signed: true,
+                size: 4,
+            }
+            .into_pretty(),
+        );
+        Self::expect_integer_operand(ctx, amount, shift_amount);
+        let amount = self.convert_value(ctx, amount, &result_type.clone().into_pretty());
+
+        let math_kind = match shift_type {
+            ShiftOperator::Left => IntMathKind::Shl,
+            ShiftOperator::Right => IntMathKind::Shr,
+        };
+        let result = ctx
+            .mir_function
+            .make_intermediate_value_typed(result_type.into_pretty());
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::IntMath(result, value, amount, math_kind),
+        );
+        result
+    }
+
+    pub(crate) fn walk_bitwise_invert(
+        &mut self,
+        ctx: &mut FunctionContext,
+        value: &Spanned<CExpression>,
+        wanted_type: &PrettyType,
+    ) -> MIRValue {
+        let operand_type = Self::arithmetic_operand_type(wanted_type);
+        let value_ref = self.walk_expression(ctx, value.clone(), &operand_type);
+        let result_type =
+            promoted(&Self::expect_integer_operand(ctx, value_ref, value)).into_pretty();
+        let value_ref = self.convert_value(ctx, value_ref, &result_type);
+
+        let result = ctx.mir_function.make_intermediate_value_typed(result_type);
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::IntMath(result, value_ref, value_ref, IntMathKind::Not),
+        );
+        result
+    }
+
+    /// the type of an operand that has to be an integer
+    fn expect_integer_operand(
+        ctx: &FunctionContext,
+        value: MIRValue,
+        expression: &Spanned<CExpression>,
+    ) -> ExtType {
+        let value_type = &ctx.mir_function.value_type_map_pretty[&value].inner_type;
+        if !matches!(value_type, ExtType::Int { .. }) {
+            expression
+                .span
+                .fail_at_span("bitwise operand is not an integer")
+        }
+        value_type.clone()
+    }
+}
@@ -0,0 +1,40 @@
+use crate::{
+    environment_builder::{ext_type::ExtType, EnvironmentController},
+    mir::{MIRBlock, MIRInstruction, MIRLocatorValue, MIRType},
+    parser::{parse_nodes::declarations::Initializer, span::Spanned, types::CTypeName},
+};
+
+use super::walk_func::FunctionContext;
+
+/*
+(6.5.2.5) a compound literal `(type-name){ initializer-list }` is an unnamed object:
+    inside a function it has automatic storage, a stack temporary initialized like a local,
+    it is an lvalue, so its address can be taken and it can be assigned to
+    at file scope it has static storage, the constant expression runner defines a data object for it
+*/
+impl EnvironmentController {
+    pub(crate) fn walk_compound_literal(
+        &mut self,
+        ctx: &mut FunctionContext,
+        type_name: &CTypeName,
+        initializer_list: &Spanned<Initializer>,
+    ) -> MIRLocatorValue {
+        let literal_type = self.compound_literal_type(type_name, initializer_list);
+        let address = ctx.mir_function.make_intermediate_value_typed(
+            ExtType::Pointer {
+                is_const: false,
+                is_volatile: false,
+                to: Box::new(literal_type.inner_type.clone()),
+            }
+            .into_pretty(),
+        );
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::StackAlloc(address, MIRType::extract_from_pretty_type(&literal_type)),
+        );
+
+        let literal = MIRLocatorValue::Memory(address, literal_type);
+        self.initialize_object(ctx, literal.clone(), initializer_list);
+        literal
+    }
+}
@@ -0,0 +1,306 @@
+use crate::{
+    environment_builder::{
+        constant_expr::usual_arithmetic_conversion, ext_type::ExtType, CompileTimeValue,
+        EnvironmentController,
+    },
+    mir::{IntCmpKind, MIRBlock, MIRInstruction, MIRType, MIRValue},
+    parser::{parse_nodes::expressions::CExpression, span::Spanned},
+};
+
+use super::walk_func::FunctionContext;
+
+/*
+(6.5.13) (6.5.14) a && b and a || b are int 0 or 1, b is only evaluated if a does not decide it:
+    block:  result = a != 0; brnz result next (&&) or end (||); jump end (&&) or next (||)
+    next:   result = b != 0; jump end
+    end:    read result
+(6.5.3.3) !a is a == 0
+(6.5.15) c ? a : b evaluates only one of a and b, both are converted to the common type:
+    block:  brnz c true; jump else
+    true:   result = (type) a; jump end
+    else:   result = (type) b; jump end
+    end:    read result
+the result is a temporary local, as it is assigned in more than one block
+*/
+
+fn is_arithmetic(value_type: &ExtType) -> bool {
+    matches!(value_type, ExtType::Int { .. } | ExtType::Float { .. })
+}
+
+/// const and volatile of a type, void has none
+fn qualifiers(value_type: &ExtType) -> (bool, bool) {
+    match value_type {
+        ExtType::Int {
+            is_const,
+            is_volatile,
+            ..
+        }
+        | ExtType::Float {
+            is_const,
+            is_volatile,
+            ..
+        }
+        | ExtType::Array {
+            is_const,
+            is_volatile,
+            ..
+        }
+        | ExtType::Pointer {
+            is_const,
+            is_volatile,
+            ..
+        }
+        | ExtType::Struct {
+            is_const,
+            is_volatile,
+            ..
+        }
+        | ExtType::Union {
+            is_const,
+            is_volatile,
+            ..
+        } => (*is_const, *is_volatile),
+        ExtType::Void | ExtType::Function { .. } => (false, false),
+    }
+}
+
+impl EnvironmentController {
+    pub(crate) fn walk_logical_operator(
+        &mut self,
+        ctx: &mut FunctionContext,
+        operands: &[Spanned<CExpression>],
+        is_and: bool,
+    ) -> MIRValue {
+        let int_type = ExtType::Int {
+            is_const: false,
+            is_volatile: false,
+            signed: true,
+            size: 4,
+        }
+        .into_pretty();
+        let result_local = ctx.make_temporary_local(int_type.clone());
+        let end_block = ctx.make_block();
+
+        for (index, operand) in operands.iter().enumerate() {
+            let value = self.walk_expression(ctx, operand.clone(), &int_type);
+            let truth = Self::compare_to_zero(ctx, value, IntCmpKind::UnEq);
+            MIRBlock::ins_instr(
+                &ctx.mir_function.current_block,
+                MIRInstruction::AssignLocal(result_local, truth),
+            );
+
+            if index + 1 == operands.len() {
+                ctx.jump_to_block(end_block);
+            } else {
+                let next_block = ctx.make_block();
+                if is_and {
+                    ctx.branch_on_value(truth, next_block, end_block);
+                } else {
+                    ctx.branch_on_value(truth, end_block, next_block);
+                }
+                ctx.mir_function.current_block = ctx.mir_function.blocks[next_block].clone();
+            }
+        }
+
+        ctx.mir_function.current_block = ctx.mir_function.blocks[end_block].clone();
+        let result = ctx.mir_function.make_intermediate_value_typed(int_type);
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::ReadLocal(result, result_local),
+        );
+        result
+    }
+
+    /// int 1 if the scalar `value` compares to zero with `cmp_kind`, 0 otherwise
+    pub(crate) fn compare_to_zero(
+        ctx: &mut FunctionContext,
+        value: MIRValue,
+        cmp_kind: IntCmpKind,
+    ) -> MIRValue {
+        let value_type = ctx
+            .mir_function
+            .value_type_map_pretty
+            .get(&value)
+            .unwrap()
+            .clone();
+        let zero = ctx
+            .mir_function
+            .make_intermediate_value_typed(value_type.clone());
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::ConstNum(zero, 0, MIRType::extract_from_pretty_type(&value_type)),
+        );
+        let truth = ctx.mir_function.make_intermediate_value_typed(
+            ExtType::Int {
+                is_const: false,
+                is_volatile: false,
+                signed: true,
+                size: 4,
+            }
+            .into_pretty(),
+        );
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::Compare(truth, value, zero, cmp_kind),
+        );
+        truth
+    }
+
+    pub(crate) fn walk_conditional_operator(
+        &mut self,
+        ctx: &mut FunctionContext,
+        condition: &Spanned<CExpression>,
+        if_true: &Spanned<CExpression>,
+        tern_else: &Spanned<CExpression>,
+    ) -> MIRValue {
+        let condition_value = self.walk_expression(
+            ctx,
+            condition.clone(),
+            &ExtType::Int {
+                is_const: false,
+                is_volatile: false,
+                signed: true,
+                size: 4,
+            }
+            .into_pretty(),
+        );
+        let true_block = ctx.make_block();
+        let else_block = ctx.make_block();
+        let end_block = ctx.make_block();
+        ctx.branch_on_value(condition_value, true_block, else_block);
+
+        // both operands are walked with their own types first, the result type depends on both
+        let mut operands = vec![];
+        for (block, operand) in [(true_block, if_true), (else_block, tern_else)] {
+            ctx.mir_function.current_block = ctx.mir_function.blocks[block].clone();
+            let own_type = self.type_of_expression(ctx, operand);
+            let value = self.walk_expression(
+                ctx,
+                operand.clone(),
+                &own_type.inner_type.decayed().into_pretty(),
+            );
+            let value_type = ctx
+                .mir_function
+                .value_type_map_pretty
+                .get(&value)
+                .unwrap()
+                .clone();
+            operands.push((ctx.mir_function.current_block.clone(), value, value_type));
+        }
+        let result_type = self
+            .conditional_result_type(
+                (&operands[0].2.inner_type, if_true),
+                (&operands[1].2.inner_type, tern_else),
+            )
+            .into_pretty();
+
+        // aggregate values are addresses, so the local holds one, void has no local
+        let result_local = (result_type.inner_type != ExtType::Void).then(|| {
+            let local_type = if MIRType::extract_from_pretty_type(&result_type).is_aggregate() {
+                ExtType::Pointer {
+                    is_const: false,
+                    is_volatile: false,
+                    to: Box::new(result_type.inner_type.clone()),
+                }
+                .into_pretty()
+            } else {
+                result_type.clone()
+            };
+            ctx.make_temporary_local(local_type)
+        });
+
+        for (operand_end, value, _) in operands {
+            ctx.mir_function.current_block = operand_end;
+            if let Some(result_local) = result_local {
+                let value = self.convert_value(ctx, value, &result_type);
+                MIRBlock::ins_instr(
+                    &ctx.mir_function.current_block,
+                    MIRInstruction::AssignLocal(result_local, value),
+                );
+            }
+            if !ctx.mir_function.current_block.borrow().is_exit_block {
+                ctx.jump_to_block(end_block);
+            }
+        }
+
+        ctx.mir_function.current_block = ctx.mir_function.blocks[end_block].clone();
+        match result_local {
+            Some(result_local) => {
+                let result = ctx.mir_function.make_intermediate_value_typed(result_type);
+                MIRBlock::ins_instr(
+                    &ctx.mir_function.current_block,
+                    MIRInstruction::ReadLocal(result, result_local),
+                );
+                result
+            }
+            // there is no void value, nothing may use it anyway
+            None => {
+                let result = ctx.mir_function.make_void_value();
+                MIRBlock::ins_instr(
+                    &ctx.mir_function.current_block,
+                    MIRInstruction::ConstNum(result, 0, MIRType::I32),
+                );
+                result
+            }
+        }
+    }
+
+    /// (6.5.15) the common type of the second and third operand of ?:
+    fn conditional_result_type(
+        &mut self,
+        (left, left_expr): (&ExtType, &Spanned<CExpression>),
+        (right, right_expr): (&ExtType, &Spanned<CExpression>),
+    ) -> ExtType {
+        match (left, right) {
+            (ExtType::Void, _) | (_, ExtType::Void) => ExtType::Void,
+            (left, right) if is_arithmetic(left) && is_arithmetic(right) => {
+                usual_arithmetic_conversion(left, right)
+            }
+            (ExtType::Pointer { to: left_to, .. }, ExtType::Pointer { to: right_to, .. }) => {
+                // a null pointer constant takes the type of the other pointer,
+                // otherwise void * wins and the pointed to qualifiers are merged
+                let pointee = if **left_to == ExtType::Void
+                    && self.is_null_pointer_constant(left_expr)
+                {
+                    (**right_to).clone()
+                } else if **right_to == ExtType::Void && self.is_null_pointer_constant(right_expr) {
+                    (**left_to).clone()
+                } else if **left_to == ExtType::Void || **right_to == ExtType::Void {
+                    ExtType::Void
+                } else {
+                    (**left_to).clone()
+                };
+                let (left_const, left_volatile) = qualifiers(left_to);
+                let (right_const, right_volatile) = qualifiers(right_to);
+                ExtType::Pointer {
+                    is_const: false,
+                    is_volatile: false,
+                    to: Box::new(
+                        pointee
+                            .qualified(left_const || right_const, left_volatile || right_volatile),
+                    ),
+                }
+            }
+            // the integer is a null pointer constant
+            (pointer @ ExtType::Pointer { .. }, ExtType::Int { .. })
+            | (ExtType::Int { .. }, pointer @ ExtType::Pointer { .. }) => {
+                pointer.clone().unqualified()
+            }
+            (left, right) if left.clone().unqualified() == right.clone().unqualified() => {
+                left.clone().unqualified()
+            }
+            _ => left_expr.span.fail_at_span(&format!(
+                "type mismatch in conditional expression: {:?} and {:?}",
+                left, right
+            )),
+        }
+    }
+
+    /// (6.3.2.3) an integer constant expression with the value 0, maybe cast to void *
+    fn is_null_pointer_constant(&mut self, expr: &Spanned<CExpression>) -> bool {
+        matches!(
+            self.try_run_expression_at_compile_time(expr),
+            Ok(CompileTimeValue::Int(0))
+        )
+    }
+}
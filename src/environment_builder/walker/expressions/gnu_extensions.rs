@@ -0,0 +1,116 @@
+use crate::parser::{
+    parse_nodes::{
+        declarations::Initializer,
+        statements::{CompoundItem, Statement},
+        Identifier,
+    },
+    span::Span,
+    visitor::{self, Visitor},
+};
+
+use super::*;
+
+impl EnvironmentController {
+    /// GNU: ({ ... }), the value is the one of the last expression statement
+    pub(crate) fn walk_statement_expression(
+        &mut self,
+        ctx: &mut FunctionContext,
+        items: &[CompoundItem],
+        wanted_type: &PrettyType,
+    ) -> MIRValue {
+        self.symbol_table.enter_new_level();
+
+        let (trailing_expr, other_items) = match items.split_last() {
+            Some((CompoundItem::Statement(last_statement), other_items)) => {
+                match &*last_statement.inner {
+                    Statement::CExpression(expr) => (Some(expr), other_items),
+                    _ => (None, items),
+                }
+            }
+            _ => (None, items),
+        };
+
+        for item in other_items {
+            self.walk_compound_item(ctx, item);
+        }
+
+        let value = if let Some(expr) = trailing_expr {
+            self.walk_expression(ctx, expr.clone(), wanted_type)
+        } else {
+            // without a trailing expression the type is void, so the value is never read
+            let void_value = ctx.mir_function.make_intermediate_value_typed(
+                ExtType::Int {
+                    is_const: false,
+                    is_volatile: false,
+                    signed: true,
+                    size: 4,
+                }
+                .into_pretty(),
+            );
+            MIRBlock::ins_instr(
+                &ctx.mir_function.current_block,
+                MIRInstruction::ConstNum(void_value, 0, MIRType::I32),
+            );
+            void_value
+        };
+
+        self.symbol_table.exit_new_level();
+        value
+    }
+
+    /// GNU: &&label, the id of the label block as a `void *`
+    pub(crate) fn walk_label_address(
+        &mut self,
+        ctx: &mut FunctionContext,
+        label: &Identifier,
+        span: &Span,
+    ) -> MIRValue {
+        let label_block_id = Self::take_label_address(ctx, &label.identifier, span);
+
+        let value = ctx.mir_function.make_intermediate_value_typed(
+            ExtType::Pointer {
+                is_const: false,
+                is_volatile: false,
+                to: Box::new(ExtType::Void),
+            }
+            .into_pretty(),
+        );
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::ConstNum(value, label_block_id as i64, MIRType::I64),
+        );
+        value
+    }
+
+    fn take_label_address(ctx: &mut FunctionContext, label: &str, span: &Span) -> usize {
+        let label_block_id = ctx.use_label(label, span);
+        ctx.address_taken_labels.insert(label_block_id);
+        label_block_id
+    }
+
+    /// GNU: a static initializer in a function may take label addresses, they are constant ids
+    pub(crate) fn take_label_addresses(
+        &mut self,
+        ctx: &mut FunctionContext,
+        initializer: &Spanned<Initializer>,
+    ) {
+        let mut collector = LabelAddressCollector(vec![]);
+        collector.visit_initializer(initializer);
+        for (label, span) in collector.0 {
+            let label_block_id = Self::take_label_address(ctx, &label, &span);
+            self.label_addresses.insert(label, label_block_id);
+        }
+    }
+}
+
+/// every `&&label` below a node
+struct LabelAddressCollector(Vec<(String, Span)>);
+
+impl Visitor for LabelAddressCollector {
+    fn visit_expression(&mut self, expr: &Spanned<CExpression>) {
+        if let CExpression::LabelAddress(label) = &*expr.inner {
+            self.0.push((label.identifier.clone(), expr.span.clone()));
+        }
+        visitor::walk_expression(self, expr)
+    }
+}
@@ -2,19 +2,26 @@ use super::*;
 
 use crate::{
     environment_builder::{
-        ext_type::{ExtType, FunctionParameter, PrettyType},
-        EnvironmentController,
+        ext_type::{ExtType, PrettyType},
+        CompileTimeValue, EnvironmentController,
     },
     mir::{IntMathKind, MIRBlock, MIRConstant, MIRInstruction, MIRSignature, MIRType, MIRValue},
     parser::{
         parse_nodes::{
             declarations::{CFunctionSpecifier, CStorageClass, DeclarationSpecifiers},
-            expressions::CExpression,
+            expressions::{AssignmentOperator, CExpression},
         },
         span::Spanned,
     },
 };
 
+mod assignment;
+mod bitwise;
+mod compound_literal;
+mod conditional;
+mod gnu_extensions;
+mod pointer_arithmetic;
+mod sizeof;
 mod walk_get_lvalue;
 
 use super::walk_func::FunctionContext;
@@ -37,31 +44,53 @@ impl EnvironmentController {
                 operator,
                 value,
             } => {
+                if *operator != AssignmentOperator::Assign {
+                    return self.walk_compound_assignment(
+                        ctx,
+                        to_assign,
+                        operator,
+                        value,
+                        wanted_type,
+                    );
+                }
                 let lvalue = self.walk_expression_get_lvalue(ctx, to_assign.clone(), wanted_type);
 
                 let rvalue = self.walk_expression(ctx, value.clone(), &lvalue.get_pretty_type());
+                let rvalue = self.convert_value(ctx, rvalue, &lvalue.get_pretty_type());
                 lvalue.assign_value(ctx, rvalue);
                 lvalue.into_rvlaue(ctx)
             }
             CExpression::Ternary {
-                condition: _,
-                if_true: _,
-                tern_else: _,
-            } => todo!(),
-            CExpression::LogicalOr(_) => todo!(),
-            CExpression::LogicalAnd(_) => todo!(),
-            CExpression::InclusiveOr(_) => todo!(),
-            CExpression::ExlusiveOr(_) => todo!(),
-            CExpression::And(_) => todo!(),
+                condition,
+                if_true,
+                tern_else,
+            } => self.walk_conditional_operator(ctx, condition, if_true, tern_else),
+            CExpression::LogicalOr(operands) => self.walk_logical_operator(ctx, operands, false),
+            CExpression::LogicalAnd(operands) => self.walk_logical_operator(ctx, operands, true),
+            CExpression::InclusiveOr(operands) => {
+                self.walk_bitwise_operator(ctx, operands, IntMathKind::Or, wanted_type)
+            }
+            CExpression::ExlusiveOr(operands) => {
+                self.walk_bitwise_operator(ctx, operands, IntMathKind::Xor, wanted_type)
+            }
+            CExpression::And(operands) => {
+                self.walk_bitwise_operator(ctx, operands, IntMathKind::And, wanted_type)
+            }
             CExpression::Equality {
                 left_piece,
                 equality_op,
                 right_piece,
             } => {
-                let left_value = self.walk_expression(ctx, left_piece.clone(), wanted_type);
-                let right_value = self.walk_expression(ctx, right_piece.clone(), wanted_type);
-                let (left_value, right_value) =
-                    self.arithmatic_conversion(ctx, left_value, right_value);
+                let cmp_kind = match equality_op {
+                    crate::parser::parse_nodes::expressions::EqualityOperator::Equal => {
+                        crate::mir::IntCmpKind::Eq
+                    }
+                    crate::parser::parse_nodes::expressions::EqualityOperator::NotEqual => {
+                        crate::mir::IntCmpKind::UnEq
+                    }
+                };
+                let (left_value, right_value, cmp_kind) =
+                    self.walk_comparison_operands(ctx, left_piece, right_piece, cmp_kind);
 
                 // Equality always returns an int
                 let output_value = ctx.mir_function.make_intermediate_value_typed(
@@ -75,19 +104,7 @@ impl EnvironmentController {
                 );
                 MIRBlock::ins_instr(
                     &ctx.mir_function.current_block,
-                    MIRInstruction::Compare(
-                        output_value,
-                        left_value,
-                        right_value,
-                        match equality_op {
-                            crate::parser::parse_nodes::expressions::EqualityOperator::Equal => {
-                                crate::mir::IntCmpKind::Eq
-                            }
-                            crate::parser::parse_nodes::expressions::EqualityOperator::NotEqual => {
-                                crate::mir::IntCmpKind::UnEq
-                            }
-                        },
-                    ),
+                    MIRInstruction::Compare(output_value, left_value, right_value, cmp_kind),
                 );
                 output_value
             }
@@ -96,10 +113,22 @@ impl EnvironmentController {
                 equality_op,
                 right_piece,
             } => {
-                let left_value = self.walk_expression(ctx, left_piece.clone(), wanted_type);
-                let right_value = self.walk_expression(ctx, right_piece.clone(), wanted_type);
-                let (left_value, right_value) =
-                    self.arithmatic_conversion(ctx, left_value, right_value);
+                let cmp_kind = match equality_op {
+                    crate::parser::parse_nodes::expressions::RelationalOperator::Lesser => {
+                        crate::mir::IntCmpKind::LT
+                    }
+                    crate::parser::parse_nodes::expressions::RelationalOperator::Greater => {
+                        crate::mir::IntCmpKind::GT
+                    }
+                    crate::parser::parse_nodes::expressions::RelationalOperator::LesserEqual => {
+                        crate::mir::IntCmpKind::LET
+                    }
+                    crate::parser::parse_nodes::expressions::RelationalOperator::GreaterEqual => {
+                        crate::mir::IntCmpKind::GET
+                    }
+                };
+                let (left_value, right_value, cmp_kind) =
+                    self.walk_comparison_operands(ctx, left_piece, right_piece, cmp_kind);
 
                 // Relational always returns an int
                 let output_value = ctx.mir_function.make_intermediate_value_typed(
@@ -113,20 +142,15 @@ impl EnvironmentController {
                 );
                 MIRBlock::ins_instr(
                     &ctx.mir_function.current_block,
-                    MIRInstruction::Compare(output_value, left_value, right_value,match equality_op{
-                        crate::parser::parse_nodes::expressions::RelationalOperator::Lesser => crate::mir::IntCmpKind::LT,
-                        crate::parser::parse_nodes::expressions::RelationalOperator::Greater => crate::mir::IntCmpKind::GT,
-                        crate::parser::parse_nodes::expressions::RelationalOperator::LesserEqual => crate::mir::IntCmpKind::LET,
-                        crate::parser::parse_nodes::expressions::RelationalOperator::GreaterEqual => crate::mir::IntCmpKind::GET,
-                    }),
+                    MIRInstruction::Compare(output_value, left_value, right_value, cmp_kind),
                 );
                 output_value
             }
             CExpression::Shift {
-                value: _,
-                shift_type: _,
-                shift_amount: _,
-            } => todo!(),
+                value,
+                shift_type,
+                shift_amount,
+            } => self.walk_shift(ctx, value, shift_type, shift_amount, wanted_type),
             CExpression::Additive {
                 left_value,
                 op,
@@ -142,10 +166,19 @@ impl EnvironmentController {
                     }
                 };
                 //
-                let left_value = self.walk_expression(ctx, left_value.clone(), wanted_type);
-                let right_value = self.walk_expression(ctx, right_value.clone(), wanted_type);
                 let (left_value, right_value) =
-                    self.arithmatic_conversion(ctx, left_value, right_value);
+                    self.walk_additive_operands(ctx, left_value, right_value, wanted_type);
+                if Self::is_pointer_value(ctx, left_value)
+                    || Self::is_pointer_value(ctx, right_value)
+                {
+                    return self.walk_pointer_additive(
+                        ctx,
+                        left_value,
+                        right_value,
+                        math_kind,
+                        &expression,
+                    );
+                }
 
                 let output_value = ctx.mir_function.make_intermediate_value_typed(
                     ctx.mir_function
@@ -207,6 +240,7 @@ impl EnvironmentController {
                                 thread_local_c: false,
                                 auto_c: false,
                                 register_c: false,
+                                constexpr_c: false,
                             },
                             qualifiers: type_name.inner.base.qualifiers.clone(),
                             specifiers: type_name.inner.base.specifier.clone(),
@@ -215,11 +249,44 @@ impl EnvironmentController {
                                 no_return: false,
                             },
                             alignment: None,
+                            attributes: vec![],
                         },
                         *type_name.inner.declarator.inner.clone(),
                     );
 
-                let base = self.walk_expression(ctx, value.clone(), &PrettyType::default_void());
+                // (6.3.2.2) a cast to void only evaluates the operand
+                if target_type.inner_type == ExtType::Void {
+                    self.walk_expression_with_own_type(ctx, value);
+                    let void_value = ctx.mir_function.make_void_value();
+                    MIRBlock::ins_instr(
+                        &ctx.mir_function.current_block,
+                        MIRInstruction::ConstNum(void_value, 0, MIRType::I32),
+                    );
+                    return void_value;
+                }
+                // (6.3.2.3) an integer constant 0 converted to a pointer is a null pointer
+                let operand_type = self.type_of_expression(ctx, value);
+                if target_type.is_pointer()
+                    && matches!(operand_type.inner_type, ExtType::Int { .. })
+                    && matches!(
+                        self.try_run_expression_at_compile_time(value),
+                        Ok(CompileTimeValue::Int(0))
+                    )
+                {
+                    let null_pointer = ctx
+                        .mir_function
+                        .make_intermediate_value_typed(target_type.clone());
+                    MIRBlock::ins_instr(
+                        &ctx.mir_function.current_block,
+                        MIRInstruction::ConstNum(
+                            null_pointer,
+                            0,
+                            MIRType::extract_from_pretty_type(&target_type),
+                        ),
+                    );
+                    return null_pointer;
+                }
+                let base = self.walk_expression(ctx, value.clone(), &operand_type);
 
                 let output_value = ctx
                     .mir_function
@@ -235,9 +302,9 @@ impl EnvironmentController {
                 output_value
             }
             CExpression::PrefixIncrement {
-                increment_type: _,
-                value: _,
-            } => todo!(),
+                increment_type,
+                value,
+            } => self.walk_increment(ctx, increment_type, value, false),
             CExpression::Unary { unary_op, value } => {
                 use crate::parser::parse_nodes::expressions::*;
                 match unary_op {
@@ -248,60 +315,42 @@ impl EnvironmentController {
                             &ExtType::Void.into_pretty(),
                         );
 
-                        match lvalue {
-                            crate::mir::MIRLocatorValue::LocalVar(local_ref, pretty_type) => {
-                                let output_value = ctx.mir_function.make_intermediate_value_typed(
-                                    ExtType::Pointer {
-                                        is_const: false,
-                                        is_volatile: false,
-                                        to: Box::new(pretty_type.inner_type),
-                                    }
-                                    .into_pretty(),
-                                );
-                                MIRBlock::ins_instr(
-                                    &ctx.mir_function.current_block,
-                                    MIRInstruction::GetAddrOfLocal(output_value, local_ref),
-                                );
-                                output_value
-                            }
-                        }
+                        lvalue.get_address(ctx)
                     }
                     UnaryOperator::DEREF => {
                         let value_to_deref = self.walk_expression(ctx, value.clone(), wanted_type);
-                        let type_of_deref = ctx
-                            .mir_function
-                            .value_type_map_pretty
-                            .get(&value_to_deref)
-                            .unwrap();
-                        if let ExtType::Pointer {
-                            is_const,
-                            is_volatile,
-                            to,
-                        } = &type_of_deref.inner_type
-                        {
-                            let output_value = ctx
-                                .mir_function
-                                .make_intermediate_value_typed(to.into_pretty());
-                            MIRBlock::ins_instr(
-                                &ctx.mir_function.current_block,
-                                MIRInstruction::Deref(output_value, value_to_deref, MIRType::I32),
-                            );
-                            output_value
-                        } else {
-                            expression.span.error_at_span("cannot deref not pointer!");
-                            panic!()
-                        }
+                        let pointee = self.get_pointee_type(ctx, value_to_deref, &expression);
+                        crate::mir::MIRLocatorValue::Memory(value_to_deref, pointee)
+                            .into_rvlaue(ctx)
                     }
                     UnaryOperator::VALUE => todo!(),
                     UnaryOperator::NEGATIVE => todo!(),
-                    UnaryOperator::BITWISEINVERT => todo!(),
-                    UnaryOperator::BOOLEANINVERT => todo!(),
+                    UnaryOperator::BITWISEINVERT => {
+                        self.walk_bitwise_invert(ctx, value, wanted_type)
+                    }
+                    UnaryOperator::BOOLEANINVERT => {
+                        let value = self.walk_expression(ctx, value.clone(), wanted_type);
+                        Self::compare_to_zero(ctx, value, crate::mir::IntCmpKind::Eq)
+                    }
                 }
             }
-            CExpression::SizeOf { value: _ } => todo!(),
-            CExpression::SizeOfType { type_name: _ } => todo!(),
-            CExpression::AlignOfType { type_name: _ } => todo!(),
-            CExpression::ArraySubscription { array: _, index: _ } => todo!(),
+            CExpression::SizeOf { value } => {
+                let value_type = self.type_of_expression(ctx, value);
+                self.walk_type_property(ctx, value_type.size_of(), wanted_type)
+            }
+            CExpression::SizeOfType { type_name } => {
+                let type_name = self.extract_pretty_type_from_type_name(&type_name.inner);
+                let type_name = self.complete_type(type_name);
+                self.walk_type_property(ctx, type_name.size_of(), wanted_type)
+            }
+            CExpression::AlignOfType { type_name } => {
+                let type_name = self.extract_pretty_type_from_type_name(&type_name.inner);
+                let type_name = self.complete_type(type_name);
+                self.walk_type_property(ctx, type_name.align_of(), wanted_type)
+            }
+            CExpression::ArraySubscription { .. } => self
+                .walk_expression_get_lvalue(ctx, expression.clone(), wanted_type)
+                .into_rvlaue(ctx),
             CExpression::FunctionCall {
                 function,
                 arguments,
@@ -322,40 +371,30 @@ impl EnvironmentController {
                         parameters,
                     } = &function_type.inner_type
                     {
-                        // this is for variadic functions so if there is a variadic function we can extend the type iter and dont care about overloading the function
-                        let temp = vec![FunctionParameter {
-                            ident: String::new(),
-                            parameter_type: Box::new(ExtType::Int {
-                                is_const: false,
-                                is_volatile: false,
-                                signed: true,
-                                size: 4,
-                            }), // todo!(this needs better default handling or something if we dont have a wanted type);
-                        }];
-                        let temp_non_extending = vec![];
-                        let iter_extension = if *overextendable {
-                            temp.iter().cycle()
-                        } else {
-                            temp_non_extending.iter().cycle()
-                        };
-
-                        // collect all arg values by walking the ast and requiring the given type
+                        // (6.5.2.2) an argument is converted to its parameter type, the ones
+                        // after the fixed parameters of a variadic function are default promoted
                         let mut args = vec![];
-                        for (arg, param_type) in arguments
-                            .iter()
-                            .zip(parameters.iter().chain(iter_extension))
-                        {
-                            args.push(self.walk_expression(
-                                ctx,
-                                arg.clone(),
-                                &param_type.parameter_type.clone().into_pretty(),
-                            ));
+                        for (index, arg) in arguments.iter().enumerate() {
+                            let param_type = match parameters.get(index) {
+                                Some(parameter) => parameter.parameter_type.clone().into_pretty(),
+                                None if *overextendable => self
+                                    .type_of_expression(ctx, arg)
+                                    .inner_type
+                                    .default_argument_promoted()
+                                    .into_pretty(),
+                                None => break,
+                            };
+                            let value = self.walk_expression(ctx, arg.clone(), &param_type);
+                            args.push(self.convert_value(ctx, value, &param_type));
                         }
 
                         // push the actual call and return the MIRValue that results from that :)
-                        let output_value = ctx
-                            .mir_function
-                            .make_intermediate_value_typed(returns.into_pretty());
+                        let output_value = if **returns == ExtType::Void {
+                            ctx.mir_function.make_void_value()
+                        } else {
+                            ctx.mir_function
+                                .make_intermediate_value_typed(returns.into_pretty())
+                        };
                         MIRBlock::ins_instr(
                             &ctx.mir_function.current_block,
                             MIRInstruction::Call(
@@ -370,41 +409,68 @@ impl EnvironmentController {
                         panic!("cannot make MIR function signature out of not function PrettyType")
                     }
                 } else {
-                    function.span.error_at_span("function name unknown!");
-                    panic!();
+                    function.span.fail_at_span("function name unknown!");
                 }
             }
-            CExpression::DirectMemberAccess {
-                to_access: _,
-                member: _,
-            } => todo!(),
-            CExpression::IndirectMemberAccess {
-                to_access: _,
-                member: _,
-            } => todo!(),
+            CExpression::DirectMemberAccess { .. } | CExpression::IndirectMemberAccess { .. } => {
+                self.walk_expression_get_lvalue(ctx, expression.clone(), wanted_type)
+                    .into_rvlaue(ctx)
+            }
             CExpression::PostfixIncrement {
-                increment_type: _,
-                value: _,
-            } => todo!(),
+                increment_type,
+                value,
+            } => self.walk_increment(ctx, increment_type, value, true),
             CExpression::TypeInitializer {
-                type_name: _,
-                initializer_list: _,
-            } => todo!(),
+                type_name,
+                initializer_list,
+            } => self
+                .walk_compound_literal(ctx, &type_name.inner, initializer_list)
+                .into_rvlaue(ctx),
             CExpression::Identifier(ident) => {
-                // get local_ref
-                let local_ref = *ctx
-                    .mir_function
-                    .var_name_id_map
-                    .get_by_right(&ident.identifier)
-                    .unwrap_or_else(|| panic!("using undeclared variable"));
-                let _mir_var_type = ctx.mir_function.var_type_map.get(&local_ref).unwrap();
-                let var_type = self
+                if let Some(value) = self.get_enumeration_constant(&ident.identifier) {
+                    let constant_type = match wanted_type.inner_type {
+                        ExtType::Int { .. } => wanted_type.clone(),
+                        _ => ExtType::Int {
+                            is_const: false,
+                            is_volatile: false,
+                            signed: true,
+                            size: 4,
+                        }
+                        .into_pretty(),
+                    };
+                    let value_ref = ctx
+                        .mir_function
+                        .make_intermediate_value_typed(constant_type.clone());
+                    MIRBlock::ins_instr(
+                        &ctx.mir_function.current_block,
+                        MIRInstruction::ConstNum(
+                            value_ref,
+                            value as i64,
+                            MIRType::extract_from_pretty_type(&constant_type),
+                        ),
+                    );
+                    return value_ref;
+                }
+                if self
+                    .symbol_table
+                    .get_top_variable(&ident.identifier)
+                    .is_some_and(|variable| variable.borrow().static_symbol.is_some())
+                {
+                    return self
+                        .walk_expression_get_lvalue(ctx, expression.clone(), wanted_type)
+                        .into_rvlaue(ctx);
+                }
+                let variable = self
                     .symbol_table
                     .get_top_variable(&ident.identifier)
                     .unwrap_or_else(|| panic!("using undeclared variable"))
                     .borrow()
-                    .associated_type
                     .clone();
+                // get local_ref
+                let local_ref = variable
+                    .local
+                    .unwrap_or_else(|| panic!("using undeclared variable"));
+                let var_type = variable.associated_type;
 
                 if &var_type != wanted_type {
                     // todo!(: fix this)
@@ -424,6 +490,10 @@ impl EnvironmentController {
                         // panic!("var type different from wanted type!");
                     }
                 }
+                if MIRType::extract_from_pretty_type(&var_type).is_aggregate() {
+                    return crate::mir::MIRLocatorValue::LocalVar(local_ref, var_type)
+                        .into_rvlaue(ctx);
+                }
                 // insert load local instruction
                 let value_ref = ctx.mir_function.make_intermediate_value_typed(var_type);
                 MIRBlock::ins_instr(
@@ -448,7 +518,24 @@ impl EnvironmentController {
                         &ctx.mir_function.current_block,
                         MIRInstruction::ConstNum(
                             value_ref,
-                            numberlike.from.parse::<i64>().unwrap(),
+                            numberlike.to_integer().unwrap() as i64,
+                            mir_type,
+                        ),
+                    );
+                    value_ref
+                }
+                // true, false and nullptr are just 1 and 0 of the wanted type
+                crate::parser::parse_nodes::Constant::Bool(_)
+                | crate::parser::parse_nodes::Constant::Nullptr => {
+                    let mir_type = MIRType::extract_from_pretty_type(wanted_type);
+                    let value_ref = ctx
+                        .mir_function
+                        .make_intermediate_value_typed(wanted_type.clone());
+                    MIRBlock::ins_instr(
+                        &ctx.mir_function.current_block,
+                        MIRInstruction::ConstNum(
+                            value_ref,
+                            (*constant == crate::parser::parse_nodes::Constant::Bool(true)) as i64,
                             mir_type,
                         ),
                     );
@@ -480,6 +567,12 @@ impl EnvironmentController {
                 self.walk_expression(ctx, expr.clone(), wanted_type)
             }
             CExpression::GenericSelection(_) => todo!(),
+            CExpression::StatementExpression(items) => {
+                self.walk_statement_expression(ctx, items, wanted_type)
+            }
+            CExpression::LabelAddress(label) => {
+                self.walk_label_address(ctx, label, &expression.span)
+            }
         }
     }
 }
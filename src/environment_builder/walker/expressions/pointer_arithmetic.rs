@@ -0,0 +1,253 @@
+use crate::{
+    environment_builder::{
+        ext_type::{ExtType, PrettyType},
+        EnvironmentController,
+    },
+    mir::{IntCmpKind, IntMathKind, MIRBlock, MIRInstruction, MIRType, MIRValue},
+    parser::{parse_nodes::expressions::CExpression, span::Spanned},
+};
+
+use super::walk_func::FunctionContext;
+
+/*
+(6.5.6) adding an integer to a pointer moves it by that many elements,
+so the integer is scaled by the size of the pointed to type first,
+subtracting two pointers gives the distance in elements as ptrdiff_t
+(6.5.8, 6.5.9) pointers compare as unsigned addresses, an integer compared to a pointer
+(the null pointer constant 0) is converted to the pointer type first
+the left operand is walked first, if it is a pointer the right one is walked as
+ptrdiff_t or as the same pointer type, so constants on the right get the correct type
+*/
+impl EnvironmentController {
+    /// `pointer` moved `offset` elements forward (Add) or backward (Sub), typed like `pointer`
+    pub(crate) fn offset_pointer(
+        &mut self,
+        ctx: &mut FunctionContext,
+        pointer: MIRValue,
+        offset: MIRValue,
+        math_kind: IntMathKind,
+    ) -> MIRValue {
+        let pointer_type = ctx
+            .mir_function
+            .value_type_map_pretty
+            .get(&pointer)
+            .unwrap()
+            .clone();
+        let element_size = match &pointer_type.inner_type {
+            ExtType::Pointer { to, .. } => self.complete_type(to.into_pretty()).size_of(),
+            _ => panic!("offset_pointer on something that is not a pointer"),
+        };
+
+        let ptrdiff_t = PrettyType::ptrdiff_t();
+        let offset = self.convert_value(ctx, offset, &ptrdiff_t);
+        let size_value = ctx
+            .mir_function
+            .make_intermediate_value_typed(ptrdiff_t.clone());
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::ConstNum(size_value, element_size as i64, MIRType::I64),
+        );
+        let byte_offset = ctx.mir_function.make_intermediate_value_typed(ptrdiff_t);
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::IntMath(byte_offset, offset, size_value, IntMathKind::Mul),
+        );
+
+        let output_value = ctx.mir_function.make_intermediate_value_typed(pointer_type);
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::IntMath(output_value, pointer, byte_offset, math_kind),
+        );
+        output_value
+    }
+}
+
+impl EnvironmentController {
+    /// the address `offset` bytes after `base_address`, typed as pointer to `object_type`
+    pub(crate) fn address_at_offset(
+        ctx: &mut FunctionContext,
+        base_address: MIRValue,
+        offset: u64,
+        object_type: &ExtType,
+    ) -> MIRValue {
+        let offset_value = ctx
+            .mir_function
+            .make_intermediate_value_typed(PrettyType::ptrdiff_t());
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::ConstNum(offset_value, offset as i64, MIRType::I64),
+        );
+        let address = ctx.mir_function.make_intermediate_value_typed(
+            ExtType::Pointer {
+                is_const: false,
+                is_volatile: false,
+                to: Box::new(object_type.clone()),
+            }
+            .into_pretty(),
+        );
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::IntMath(address, base_address, offset_value, IntMathKind::Add),
+        );
+        address
+    }
+
+    pub(crate) fn is_pointer_value(ctx: &FunctionContext, value: MIRValue) -> bool {
+        ctx.mir_function
+            .value_type_map_pretty
+            .get(&value)
+            .unwrap()
+            .is_pointer()
+    }
+
+    /// the wanted type for operands of arithmetic, constants never become pointers on their own
+    pub(crate) fn arithmetic_operand_type(wanted_type: &PrettyType) -> PrettyType {
+        match wanted_type.inner_type {
+            ExtType::Int { .. } | ExtType::Float { .. } => wanted_type.clone(),
+            _ => ExtType::Int {
+                is_const: false,
+                is_volatile: false,
+                signed: true,
+                size: 4,
+            }
+            .into_pretty(),
+        }
+    }
+
+    /// walks both operands of + or -, integers get the usual arithmetic conversions
+    pub(crate) fn walk_additive_operands(
+        &mut self,
+        ctx: &mut FunctionContext,
+        left: &Spanned<CExpression>,
+        right: &Spanned<CExpression>,
+        wanted_type: &PrettyType,
+    ) -> (MIRValue, MIRValue) {
+        let operand_type = Self::arithmetic_operand_type(wanted_type);
+        let left_value = self.walk_expression(ctx, left.clone(), &operand_type);
+        let right_value = if Self::is_pointer_value(ctx, left_value) {
+            self.walk_expression(ctx, right.clone(), &PrettyType::ptrdiff_t())
+        } else {
+            self.walk_expression(ctx, right.clone(), &operand_type)
+        };
+        if Self::is_pointer_value(ctx, left_value) || Self::is_pointer_value(ctx, right_value) {
+            return (left_value, right_value);
+        }
+        self.arithmatic_conversion(ctx, left_value, right_value)
+    }
+
+    /// `+` or `-` with at least one pointer operand
+    pub(crate) fn walk_pointer_additive(
+        &mut self,
+        ctx: &mut FunctionContext,
+        left_value: MIRValue,
+        right_value: MIRValue,
+        math_kind: IntMathKind,
+        expression: &Spanned<CExpression>,
+    ) -> MIRValue {
+        match (
+            Self::is_pointer_value(ctx, left_value),
+            Self::is_pointer_value(ctx, right_value),
+            &math_kind,
+        ) {
+            (true, false, _) => self.offset_pointer(ctx, left_value, right_value, math_kind),
+            (false, true, IntMathKind::Add) => {
+                self.offset_pointer(ctx, right_value, left_value, math_kind)
+            }
+            (true, true, IntMathKind::Sub) => {
+                let element_type = self.get_pointee_type(ctx, left_value, expression);
+                let right_element_type = self.get_pointee_type(ctx, right_value, expression);
+                if element_type.inner_type.clone().unqualified()
+                    != right_element_type.inner_type.unqualified()
+                {
+                    expression
+                        .span
+                        .fail_at_span("subtracting pointers to different types")
+                }
+
+                let byte_distance = ctx
+                    .mir_function
+                    .make_intermediate_value_typed(PrettyType::ptrdiff_t());
+                MIRBlock::ins_instr(
+                    &ctx.mir_function.current_block,
+                    MIRInstruction::IntMath(
+                        byte_distance,
+                        left_value,
+                        right_value,
+                        IntMathKind::Sub,
+                    ),
+                );
+                let size_value = ctx
+                    .mir_function
+                    .make_intermediate_value_typed(PrettyType::ptrdiff_t());
+                MIRBlock::ins_instr(
+                    &ctx.mir_function.current_block,
+                    MIRInstruction::ConstNum(
+                        size_value,
+                        element_type.size_of() as i64,
+                        MIRType::I64,
+                    ),
+                );
+                let output_value = ctx
+                    .mir_function
+                    .make_intermediate_value_typed(PrettyType::ptrdiff_t());
+                MIRBlock::ins_instr(
+                    &ctx.mir_function.current_block,
+                    MIRInstruction::IntMath(
+                        output_value,
+                        byte_distance,
+                        size_value,
+                        IntMathKind::Div,
+                    ),
+                );
+                output_value
+            }
+            _ => expression
+                .span
+                .fail_at_span("invalid operands to pointer arithmetic"),
+        }
+    }
+
+    /// walks both operands of a comparison with their own types, (6.5.8) integers get
+    /// the usual arithmetic conversions, the returned kind compares unsigned if the common type is
+    pub(crate) fn walk_comparison_operands(
+        &mut self,
+        ctx: &mut FunctionContext,
+        left: &Spanned<CExpression>,
+        right: &Spanned<CExpression>,
+        cmp_kind: IntCmpKind,
+    ) -> (MIRValue, MIRValue, IntCmpKind) {
+        let left_value = self.walk_expression_with_own_type(ctx, left);
+        let left_type = ctx
+            .mir_function
+            .value_type_map_pretty
+            .get(&left_value)
+            .unwrap()
+            .clone();
+        let right_value = self.walk_expression_with_own_type(ctx, right);
+        let right_type = ctx
+            .mir_function
+            .value_type_map_pretty
+            .get(&right_value)
+            .unwrap()
+            .clone();
+
+        let (left_value, right_value) = match (left_type.is_pointer(), right_type.is_pointer()) {
+            (false, false) => self.arithmatic_conversion(ctx, left_value, right_value),
+            (true, true) => (left_value, right_value),
+            (true, false) => (left_value, self.convert_value(ctx, right_value, &left_type)),
+            (false, true) => (
+                self.convert_value(ctx, left_value, &right_type),
+                right_value,
+            ),
+        };
+        let is_unsigned = matches!(
+            ctx.mir_function
+                .value_type_map_pretty
+                .get(&left_value)
+                .unwrap()
+                .inner_type,
+            ExtType::Pointer { .. } | ExtType::Int { signed: false, .. }
+        );
+        (left_value, right_value, cmp_kind.with_unsigned(is_unsigned))
+    }
+}
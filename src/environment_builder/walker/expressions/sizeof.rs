@@ -0,0 +1,122 @@
+use crate::{
+    environment_builder::{
+        ext_type::{ExtType, PrettyType},
+        EnvironmentController,
+    },
+    mir::{MIRBlock, MIRConstant, MIRInstruction, MIRType, MIRValue},
+    parser::{
+        parse_nodes::expressions::{CExpression, UnaryOperator},
+        span::Spanned,
+    },
+};
+
+use super::walk_func::FunctionContext;
+
+/*
+(6.5.3.4) the operand of sizeof is not evaluated, only its type matters:
+the expression is walked into a block that is never part of the function,
+so are the blocks it makes,
+lvalues are kept as such so arrays do not decay
+*/
+impl EnvironmentController {
+    pub(crate) fn type_of_expression(
+        &mut self,
+        ctx: &mut FunctionContext,
+        expression: &Spanned<CExpression>,
+    ) -> PrettyType {
+        match &*expression.inner {
+            CExpression::Paranthesised(inner) => return self.type_of_expression(ctx, inner),
+            CExpression::Identifier(ident) => {
+                if let Some(variable) = self.symbol_table.get_top_variable(&ident.identifier) {
+                    let variable_type = variable.borrow().associated_type.clone();
+                    return self.complete_type(variable_type);
+                }
+            }
+            CExpression::StringLiteral(literal) => {
+                let constant = MIRConstant::from_string(literal.value.clone());
+                return ExtType::Array {
+                    is_const: false,
+                    is_volatile: false,
+                    arr_size: Some(constant.value.len() as u64),
+                    to: Box::new(ExtType::Int {
+                        is_const: false,
+                        is_volatile: false,
+                        signed: false,
+                        size: 1,
+                    }),
+                }
+                .into_pretty();
+            }
+            _ => {}
+        }
+
+        let current_block = ctx.mir_function.current_block.clone();
+        let block_count = ctx.mir_function.blocks.len();
+        ctx.mir_function.current_block = MIRBlock::new_wrapped();
+        let int_type = ExtType::Int {
+            is_const: false,
+            is_volatile: false,
+            signed: true,
+            size: 4,
+        }
+        .into_pretty();
+        let expression_type = match &*expression.inner {
+            CExpression::ArraySubscription { .. }
+            | CExpression::DirectMemberAccess { .. }
+            | CExpression::IndirectMemberAccess { .. }
+            | CExpression::Unary {
+                unary_op: UnaryOperator::DEREF,
+                ..
+            } => self
+                .walk_expression_get_lvalue(ctx, expression.clone(), &int_type)
+                .get_pretty_type(),
+            _ => {
+                let value = self.walk_expression(ctx, expression.clone(), &int_type);
+                ctx.mir_function
+                    .value_type_map_pretty
+                    .get(&value)
+                    .unwrap()
+                    .clone()
+            }
+        };
+        // blocks of && || and ?: in the operand are never reached either
+        ctx.mir_function.blocks.truncate(block_count);
+        ctx.mir_function.current_block = current_block;
+        self.complete_type(expression_type)
+    }
+
+    /// walks `expression` with its own type as the wanted one, so nothing is converted
+    pub(crate) fn walk_expression_with_own_type(
+        &mut self,
+        ctx: &mut FunctionContext,
+        expression: &Spanned<CExpression>,
+    ) -> MIRValue {
+        let own_type = self.type_of_expression(ctx, expression);
+        self.walk_expression(ctx, expression.clone(), &own_type)
+    }
+
+    /// a size or alignment as constant of the wanted integer type, size_t otherwise
+    pub(crate) fn walk_type_property(
+        &mut self,
+        ctx: &mut FunctionContext,
+        property: u64,
+        wanted_type: &PrettyType,
+    ) -> MIRValue {
+        let result_type = match wanted_type.inner_type {
+            ExtType::Int { .. } => wanted_type.clone(),
+            _ => PrettyType::size_t(),
+        };
+        let value_ref = ctx
+            .mir_function
+            .make_intermediate_value_typed(result_type.clone());
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::ConstNum(
+                value_ref,
+                property as i64,
+                MIRType::extract_from_pretty_type(&result_type),
+            ),
+        );
+        value_ref
+    }
+}
@@ -9,7 +9,10 @@ use crate::{
         IntMathKind, MIRBlock, MIRConstant, MIRInstruction, MIRLocatorValue, MIRSignature, MIRType,
         MIRValue,
     },
-    parser::{parse_nodes::expressions::CExpression, span::Spanned},
+    parser::{
+        parse_nodes::{expressions::CExpression, Identifier},
+        span::Spanned,
+    },
 };
 
 use super::walk_func::FunctionContext;
@@ -18,10 +21,47 @@ we need a lvalue for things like lvalue = 4;
 
 */
 
+/*
+aggregates are never loaded into values, reading one copies it into a stack temporary
+and its address is the value, assigning one copies the memory the value points to
+(6.3.2.1) arrays are not copied, their value is the address of the first element
+*/
 impl MIRLocatorValue {
     pub(crate) fn into_rvlaue(&self, ctx: &mut FunctionContext) -> MIRValue {
+        if let ExtType::Array { to, .. } = &self.get_pretty_type().inner_type {
+            let address = self.get_address(ctx);
+            let decayed = ctx.mir_function.make_intermediate_value_typed(
+                ExtType::Pointer {
+                    is_const: false,
+                    is_volatile: false,
+                    to: to.clone(),
+                }
+                .into_pretty(),
+            );
+            MIRBlock::ins_instr(
+                &ctx.mir_function.current_block,
+                MIRInstruction::IntConvert(decayed, address, MIRType::I64),
+            );
+            return decayed;
+        }
+        let mir_type = MIRType::extract_from_pretty_type(&self.get_pretty_type());
+        if mir_type.is_aggregate() {
+            let source = self.get_address(ctx);
+            let value_ref = ctx
+                .mir_function
+                .make_intermediate_value_typed(self.get_pretty_type());
+            MIRBlock::ins_instr(
+                &ctx.mir_function.current_block,
+                MIRInstruction::StackAlloc(value_ref, mir_type),
+            );
+            MIRBlock::ins_instr(
+                &ctx.mir_function.current_block,
+                MIRInstruction::CopyMemory(value_ref, source, mir_type),
+            );
+            return value_ref;
+        }
         match self {
-            MIRLocatorValue::LocalVar(local_ref, pretty_type) => {
+            MIRLocatorValue::LocalVar(local_ref, _pretty_type) => {
                 let var_type = ctx.mir_function.var_type_map_pretty.get(local_ref).unwrap();
                 let value_ref = ctx
                     .mir_function
@@ -32,21 +72,77 @@ impl MIRLocatorValue {
                 );
                 value_ref
             }
+            MIRLocatorValue::Memory(address, pretty_type) => {
+                let value_ref = ctx
+                    .mir_function
+                    .make_intermediate_value_typed(pretty_type.clone());
+                MIRBlock::ins_instr(
+                    &ctx.mir_function.current_block,
+                    MIRInstruction::Deref(
+                        value_ref,
+                        *address,
+                        MIRType::extract_from_pretty_type(pretty_type),
+                    ),
+                );
+                value_ref
+            }
         }
     }
     pub(crate) fn assign_value(&self, ctx: &mut FunctionContext, assign_value: MIRValue) {
+        let mir_type = MIRType::extract_from_pretty_type(&self.get_pretty_type());
+        if mir_type.is_aggregate() {
+            let destination = self.get_address(ctx);
+            MIRBlock::ins_instr(
+                &ctx.mir_function.current_block,
+                MIRInstruction::CopyMemory(destination, assign_value, mir_type),
+            );
+            return;
+        }
         match self {
-            MIRLocatorValue::LocalVar(local_ref, pretty_type) => {
+            MIRLocatorValue::LocalVar(local_ref, _pretty_type) => {
                 MIRBlock::ins_instr(
                     &ctx.mir_function.current_block,
                     MIRInstruction::AssignLocal(*local_ref, assign_value),
                 );
             }
+            MIRLocatorValue::Memory(address, pretty_type) => {
+                MIRBlock::ins_instr(
+                    &ctx.mir_function.current_block,
+                    MIRInstruction::StoreAtAddr(
+                        *address,
+                        assign_value,
+                        MIRType::extract_from_pretty_type(pretty_type),
+                    ),
+                );
+            }
+        }
+    }
+    /// the address of the object, typed as pointer to it
+    pub(crate) fn get_address(&self, ctx: &mut FunctionContext) -> MIRValue {
+        match self {
+            MIRLocatorValue::LocalVar(local_ref, pretty_type) => {
+                let output_value = ctx.mir_function.make_intermediate_value_typed(
+                    ExtType::Pointer {
+                        is_const: false,
+                        is_volatile: false,
+                        to: Box::new(pretty_type.inner_type.clone()),
+                    }
+                    .into_pretty(),
+                );
+                MIRBlock::ins_instr(
+                    &ctx.mir_function.current_block,
+                    MIRInstruction::GetAddrOfLocal(output_value, *local_ref),
+                );
+                output_value
+            }
+            MIRLocatorValue::Memory(address, _pretty_type) => *address,
         }
     }
     pub(crate) fn get_pretty_type(&self) -> PrettyType {
         match self {
-            MIRLocatorValue::LocalVar(local_ref, pretty_type) => pretty_type.clone(),
+            MIRLocatorValue::LocalVar(_, pretty_type) | MIRLocatorValue::Memory(_, pretty_type) => {
+                pretty_type.clone()
+            }
         }
     }
 }
@@ -56,7 +152,7 @@ impl EnvironmentController {
         &mut self,
         ctx: &mut FunctionContext,
         expression: Spanned<CExpression>,
-        wanted_type: &PrettyType,
+        _wanted_type: &PrettyType,
     ) -> MIRLocatorValue {
         match &*expression.inner {
             CExpression::Expression(_) => todo!(),
@@ -105,17 +201,84 @@ impl EnvironmentController {
                 increment_type,
                 value,
             } => todo!(),
-            CExpression::Unary { unary_op, value } => todo!(),
+            CExpression::Unary { unary_op, value } => match unary_op {
+                crate::parser::parse_nodes::expressions::UnaryOperator::DEREF => {
+                    let address =
+                        self.walk_expression(ctx, value.clone(), &PrettyType::default_void());
+                    let pointee = self.get_pointee_type(ctx, address, &expression);
+                    MIRLocatorValue::Memory(address, pointee)
+                }
+                _ => expression.span.fail_at_span("expression is not assignable"),
+            },
             CExpression::SizeOf { value } => todo!(),
             CExpression::SizeOfType { type_name } => todo!(),
             CExpression::AlignOfType { type_name } => todo!(),
-            CExpression::ArraySubscription { array, index } => todo!(),
-            CExpression::FunctionCall {
-                function,
-                arguments,
-            } => todo!(),
-            CExpression::DirectMemberAccess { to_access, member } => todo!(),
-            CExpression::IndirectMemberAccess { to_access, member } => todo!(),
+            // (6.5.2.1) a[i] is *(a + i), so i[a] works as well
+            CExpression::ArraySubscription { array, index } => {
+                let mut base = self.walk_expression_with_own_type(ctx, array);
+                let mut offset = self.walk_expression_with_own_type(ctx, index);
+                let base_is_pointer = matches!(
+                    ctx.mir_function
+                        .value_type_map_pretty
+                        .get(&base)
+                        .unwrap()
+                        .inner_type,
+                    ExtType::Pointer { .. }
+                );
+                if !base_is_pointer {
+                    std::mem::swap(&mut base, &mut offset);
+                }
+                let element_type = self.get_pointee_type(ctx, base, &expression);
+                let address = self.offset_pointer(ctx, base, offset, IntMathKind::Add);
+                MIRLocatorValue::Memory(address, element_type)
+            }
+            CExpression::FunctionCall { .. } => {
+                // (6.5.2.2) the result is no object, it is kept in a stack temporary
+                // so a member of a returned struct can be accessed
+                let result_type = self.type_of_expression(ctx, &expression);
+                if result_type.inner_type == ExtType::Void {
+                    expression
+                        .span
+                        .fail_at_span("the result of a void function is not an object")
+                }
+                let value = self.walk_expression(ctx, expression.clone(), &result_type);
+                let mir_type = MIRType::extract_from_pretty_type(&result_type);
+                if mir_type.is_aggregate() {
+                    // an aggregate value is the address of a stack temporary already
+                    return MIRLocatorValue::Memory(value, result_type);
+                }
+                let address = ctx.mir_function.make_intermediate_value_typed(
+                    ExtType::Pointer {
+                        is_const: false,
+                        is_volatile: false,
+                        to: Box::new(result_type.inner_type.clone()),
+                    }
+                    .into_pretty(),
+                );
+                MIRBlock::ins_instr(
+                    &ctx.mir_function.current_block,
+                    MIRInstruction::StackAlloc(address, mir_type),
+                );
+                let temporary = MIRLocatorValue::Memory(address, result_type);
+                temporary.assign_value(ctx, value);
+                temporary
+            }
+            CExpression::DirectMemberAccess { to_access, member } => {
+                let aggregate = self.walk_expression_get_lvalue(
+                    ctx,
+                    to_access.clone(),
+                    &PrettyType::default_void(),
+                );
+                let aggregate_type = self.complete_type(aggregate.get_pretty_type());
+                let base_address = aggregate.get_address(ctx);
+                self.walk_member_access(ctx, base_address, &aggregate_type, member, &expression)
+            }
+            CExpression::IndirectMemberAccess { to_access, member } => {
+                let base_address =
+                    self.walk_expression(ctx, to_access.clone(), &PrettyType::default_void());
+                let aggregate_type = self.get_pointee_type(ctx, base_address, &expression);
+                self.walk_member_access(ctx, base_address, &aggregate_type, member, &expression)
+            }
             CExpression::PostfixIncrement {
                 increment_type,
                 value,
@@ -123,27 +286,107 @@ impl EnvironmentController {
             CExpression::TypeInitializer {
                 type_name,
                 initializer_list,
-            } => todo!(),
+            } => self.walk_compound_literal(ctx, &type_name.inner, initializer_list),
             CExpression::Identifier(ident) => {
-                let local_ref = *ctx
-                    .mir_function
-                    .var_name_id_map
-                    .get_by_right(&ident.identifier)
-                    .unwrap_or_else(|| panic!("using undeclared variable"));
-
-                let var_type = self
+                if self.get_enumeration_constant(&ident.identifier).is_some() {
+                    expression
+                        .span
+                        .fail_at_span("enumeration constant is not assignable")
+                }
+                let variable = self
                     .symbol_table
                     .get_top_variable(&ident.identifier)
                     .unwrap_or_else(|| panic!("using undeclared variable"))
                     .borrow()
-                    .associated_type
                     .clone();
+                let var_type = variable.associated_type;
+
+                // objects with static storage duration are reached through their symbol
+                if let Some(symbol) = variable.static_symbol {
+                    let address = ctx.mir_function.make_intermediate_value_typed(
+                        ExtType::Pointer {
+                            is_const: false,
+                            is_volatile: false,
+                            to: Box::new(var_type.inner_type.clone()),
+                        }
+                        .into_pretty(),
+                    );
+                    MIRBlock::ins_instr(
+                        &ctx.mir_function.current_block,
+                        MIRInstruction::GetAddrOfGlobal(address, symbol),
+                    );
+                    return MIRLocatorValue::Memory(address, var_type);
+                }
+
+                let local_ref = variable
+                    .local
+                    .unwrap_or_else(|| panic!("using undeclared variable"));
                 MIRLocatorValue::LocalVar(local_ref, var_type)
             }
             CExpression::Constant(_) => todo!(),
             CExpression::StringLiteral(_) => todo!(),
-            CExpression::Paranthesised(_) => todo!(),
+            CExpression::Paranthesised(expr) => {
+                self.walk_expression_get_lvalue(ctx, expr.clone(), _wanted_type)
+            }
             CExpression::GenericSelection(_) => todo!(),
+            CExpression::StatementExpression(_) => expression
+                .span
+                .fail_at_span("a statement expression is not an lvalue"),
+            CExpression::LabelAddress(_) => expression
+                .span
+                .fail_at_span("a label address is not an lvalue"),
+        }
+    }
+}
+
+impl EnvironmentController {
+    /// the completed type `address` points to
+    pub(crate) fn get_pointee_type(
+        &mut self,
+        ctx: &mut FunctionContext,
+        address: MIRValue,
+        expression: &Spanned<CExpression>,
+    ) -> PrettyType {
+        let address_type = ctx
+            .mir_function
+            .value_type_map_pretty
+            .get(&address)
+            .unwrap();
+        if let ExtType::Pointer { to, .. } = &address_type.inner_type {
+            let pointee = to.into_pretty();
+            self.complete_type(pointee)
+        } else {
+            expression.span.fail_at_span("cannot deref not pointer!")
         }
     }
+
+    /// `base_address` + offset of `member`, the member as an object in memory
+    pub(crate) fn walk_member_access(
+        &mut self,
+        ctx: &mut FunctionContext,
+        base_address: MIRValue,
+        aggregate_type: &PrettyType,
+        member: &Identifier,
+        expression: &Spanned<CExpression>,
+    ) -> MIRLocatorValue {
+        if !matches!(
+            aggregate_type.inner_type,
+            ExtType::Struct { .. } | ExtType::Union { .. }
+        ) {
+            expression
+                .span
+                .fail_at_span("member access into something that is not a struct or union")
+        }
+        let Some((offset, member_type)) = aggregate_type.inner_type.find_member(&member.identifier)
+        else {
+            expression.span.fail_at_span(&format!(
+                "no member named '{}' in {:?}",
+                member.identifier, aggregate_type.inner_type
+            ))
+        };
+
+        let member_address =
+            Self::address_at_offset(ctx, base_address, offset, &member_type.inner_type);
+        MIRLocatorValue::Memory(member_address, member_type)
+    }
 }
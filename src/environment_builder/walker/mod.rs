@@ -2,6 +2,7 @@ use std::cell::RefCell;
 
 pub(crate) use log::*;
 
+use crate::environment_builder::ext_type::ExtType;
 use crate::environment_builder::symbol_table::TypedefInstance;
 use crate::environment_builder::symbol_table::VariableInstance;
 use crate::parser::parse_nodes::declarations::Declaration;
@@ -14,6 +15,7 @@ pub(crate) use crate::parser::span::Spanned;
 mod expressions;
 mod statements;
 mod static_and_constant_expr;
+mod static_storage;
 pub mod walk_func;
 
 impl EnvironmentController {
@@ -28,6 +30,10 @@ impl EnvironmentController {
     pub(crate) fn handle_external_declaration(&mut self, declaration: &Spanned<Declaration>) {
         match &*declaration.inner {
             Declaration::Declaration { specifiers, init } => {
+                // `struct tag {...};` only declares the tag
+                if init.is_empty() {
+                    self.extract_base_ext_type_from_declaration_specifiers(specifiers.clone());
+                }
                 if specifiers.storage.typedef_c {
                     for typedefed_name in init {
                         let extracted_type = self
@@ -47,24 +53,50 @@ impl EnvironmentController {
                         );
                     }
                 } else {
-                    for variable_name in init {
-                        let extracted_type = self
+                    for (declarator, initializer) in init {
+                        let mut extracted_type = self
                             .extract_pretty_type_from_declaration_specifiers_and_derived_declarator(
                                 specifiers.clone(),
-                                variable_name.0.derive.clone(),
+                                declarator.derive.clone(),
                             );
+                        if let Some(initializer) = initializer {
+                            extracted_type =
+                                self.type_completed_by_initializer(extracted_type, initializer);
+                        }
+                        let name = declarator.base.identifier.clone();
+                        // functions are no data objects
+                        let is_object =
+                            !matches!(extracted_type.inner_type, ExtType::Function { .. });
+                        let constexpr_value = self.constexpr_value(
+                            specifiers,
+                            &extracted_type,
+                            initializer.as_ref(),
+                            &declarator.span,
+                        );
                         self.symbol_table.scope.variables.insert(
-                            variable_name.0.base.identifier.clone(),
+                            name.clone(),
                             RefCell::new(VariableInstance {
                                 is_extern: specifiers.storage.extern_c,
                                 usage_counter: 0,
                                 associated_type: extracted_type.clone(),
+                                constant_value: None,
+                                constexpr_value,
+                                static_symbol: is_object.then(|| name.clone()),
+                                local: None,
                             }),
                         );
-                        debug!(
-                            "Variable def: {:?} -> {:?}",
-                            variable_name.0.base.identifier, extracted_type
-                        );
+                        if is_object {
+                            // `int x;` is a tentative definition, `extern int x;` is none
+                            self.define_static_object(
+                                &name,
+                                !specifiers.storage.static_c,
+                                !specifiers.storage.extern_c || initializer.is_some(),
+                                &extracted_type,
+                                initializer.as_ref(),
+                                &declarator.span,
+                            );
+                        }
+                        debug!("Variable def: {:?} -> {:?}", name, extracted_type);
                     }
                 }
             }
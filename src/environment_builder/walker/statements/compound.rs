@@ -1,5 +1,9 @@
 use crate::{
-    mir::{MIRBlock, MIRInstruction, MIRType},
+    environment_builder::{
+        ext_type::{ExtType, PrettyType},
+        initializer::{is_character_array, string_literal},
+    },
+    mir::{MIRBlock, MIRConstant, MIRInstruction, MIRLocatorValue, MIRType},
     parser::parse_nodes::declarations::{DeclarationSpecifiers, Declarator, Initializer},
 };
 
@@ -13,25 +17,29 @@ impl EnvironmentController {
     ) {
         self.symbol_table.enter_new_level();
         for statement in compound_statement_list {
-            match statement {
-                statements::CompoundItem::Statement(statement) => {
-                    self.walk_statement(ctx, statement.clone())
-                }
-                statements::CompoundItem::Declaration(declaration) => {
-                    use crate::parser::parse_nodes::declarations::*;
-                    match &*declaration.inner {
-                        Declaration::Declaration { specifiers, init } => {
-                            // actual declaration
-                            self.handle_declaration(ctx, specifiers, init);
-                        }
-                        Declaration::StaticAssertDeclaration(static_assert) => {
-                            self.handle_static_assert(static_assert)
-                        }
+            self.walk_compound_item(ctx, statement);
+        }
+        self.symbol_table.exit_new_level();
+    }
+
+    pub(crate) fn walk_compound_item(&mut self, ctx: &mut FunctionContext, item: &CompoundItem) {
+        match item {
+            statements::CompoundItem::Statement(statement) => {
+                self.walk_statement(ctx, statement.clone())
+            }
+            statements::CompoundItem::Declaration(declaration) => {
+                use crate::parser::parse_nodes::declarations::*;
+                match &*declaration.inner {
+                    Declaration::Declaration { specifiers, init } => {
+                        // actual declaration
+                        self.handle_declaration(ctx, specifiers, init);
+                    }
+                    Declaration::StaticAssertDeclaration(static_assert) => {
+                        self.handle_static_assert(static_assert)
                     }
                 }
             }
         }
-        self.symbol_table.exit_new_level();
     }
 }
 
@@ -42,6 +50,10 @@ impl EnvironmentController {
         specifiers: &DeclarationSpecifiers,
         init: &[(Spanned<Declarator>, Option<Spanned<Initializer>>)],
     ) {
+        // `struct tag {...};` only declares the tag
+        if init.is_empty() {
+            self.extract_base_ext_type_from_declaration_specifiers(specifiers.clone());
+        }
         for var_that_is_declared in init {
             // get var type
             let extracted_type = self
@@ -49,42 +61,178 @@ impl EnvironmentController {
                     specifiers.clone(),
                     var_that_is_declared.0.derive.clone(),
                 );
+            let extracted_type = match &var_that_is_declared.1 {
+                Some(initializer) => {
+                    self.type_completed_by_initializer(extracted_type, initializer)
+                }
+                None => extracted_type,
+            };
             let var_name = var_that_is_declared.0.base.identifier.clone();
 
+            // (6.2.4) `static` and `extern` objects get no stack slot, they are data objects
+            if specifiers.storage.static_c || specifiers.storage.extern_c {
+                self.handle_block_scope_static(
+                    ctx,
+                    specifiers,
+                    var_that_is_declared,
+                    extracted_type,
+                );
+                continue;
+            }
+
+            let constexpr_value = self.constexpr_value(
+                specifiers,
+                &extracted_type,
+                var_that_is_declared.1.as_ref(),
+                &var_that_is_declared.0.span,
+            );
+            // make local_ref
+            let local_ref = ctx
+                .mir_function
+                .insert_variable(var_name.clone(), extracted_type.clone());
+
             //insert into symbol table
             self.symbol_table.get_current_scope().variables.insert(
-                var_name.clone(),
+                var_name,
                 RefCell::new(VariableInstance {
                     is_extern: specifiers.storage.extern_c,
                     usage_counter: 0,
                     associated_type: extracted_type.clone(),
+                    constant_value: None,
+                    constexpr_value,
+                    static_symbol: None,
+                    local: Some(local_ref),
                 }),
             );
 
-            // make local_ref
-            let local_ref = ctx
-                .mir_function
-                .insert_variable(var_name, extracted_type.clone());
-
             debug!(
                 "Variable decl as Compound Item: {:?} -> {:?}",
                 var_that_is_declared.0.base.identifier, extracted_type
             );
 
-            // if initialized handle that <- Todo: better here
             if let Some(initializer) = &var_that_is_declared.1 {
-                match &*initializer.inner {
-                    Initializer::Single(single) => {
-                        let expr_result =
-                            self.walk_expression(ctx, single.clone(), &extracted_type);
-                        MIRBlock::ins_instr(
-                            &ctx.mir_function.current_block,
-                            MIRInstruction::AssignLocal(local_ref, expr_result),
-                        );
-                    }
-                    Initializer::Compound(_) => todo!(),
-                }
+                self.initialize_object(
+                    ctx,
+                    MIRLocatorValue::LocalVar(local_ref, extracted_type.clone()),
+                    initializer,
+                );
+            }
+        }
+    }
+
+    fn handle_block_scope_static(
+        &mut self,
+        ctx: &mut FunctionContext,
+        specifiers: &DeclarationSpecifiers,
+        (declarator, initializer): &(Spanned<Declarator>, Option<Spanned<Initializer>>),
+        extracted_type: PrettyType,
+    ) {
+        let name = declarator.base.identifier.clone();
+        let is_object = !matches!(extracted_type.inner_type, ExtType::Function { .. });
+        let constexpr_value = self.constexpr_value(
+            specifiers,
+            &extracted_type,
+            initializer.as_ref(),
+            &declarator.span,
+        );
+        // a static local gets a symbol of its own, `extern` refers to the file scope one
+        let symbol = if specifiers.storage.static_c {
+            format!("{}.{}", name, self.mir_programm.globals.len())
+        } else {
+            name.clone()
+        };
+        self.symbol_table.get_current_scope().variables.insert(
+            name,
+            RefCell::new(VariableInstance {
+                is_extern: specifiers.storage.extern_c,
+                usage_counter: 0,
+                associated_type: extracted_type.clone(),
+                constant_value: None,
+                constexpr_value,
+                static_symbol: is_object.then(|| symbol.clone()),
+                local: None,
+            }),
+        );
+        if is_object {
+            if let Some(initializer) = initializer {
+                self.take_label_addresses(ctx, initializer);
             }
+            self.define_static_object(
+                &symbol,
+                specifiers.storage.extern_c,
+                specifiers.storage.static_c,
+                &extracted_type,
+                initializer.as_ref(),
+                &declarator.span,
+            );
+            self.label_addresses.clear();
+        }
+    }
+
+    /// (6.7.10) stores `initializer` into `object`, whatever a brace list leaves out is zero
+    pub(crate) fn initialize_object(
+        &mut self,
+        ctx: &mut FunctionContext,
+        object: MIRLocatorValue,
+        initializer: &Spanned<Initializer>,
+    ) {
+        let object_type = self.complete_type(object.get_pretty_type());
+        if let Initializer::Single(value) = &*initializer.inner {
+            if !(is_character_array(&object_type.inner_type) && string_literal(value).is_some()) {
+                let expr_result = self.walk_expression(ctx, value.clone(), &object_type);
+                let expr_result = self.convert_value(ctx, expr_result, &object_type);
+                object.assign_value(ctx, expr_result);
+                return;
+            }
+        }
+
+        let (elements, _) = self.flatten_initializer(&object_type.inner_type, initializer);
+        // (6.7.10) whatever the initializer leaves out is zero, also behind a string literal
+        let address = object.get_address(ctx);
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::ZeroMemory(address, MIRType::extract_from_pretty_type(&object_type)),
+        );
+        for element in elements {
+            let element_address =
+                Self::address_at_offset(ctx, address, element.offset, &element.element_type);
+            let element_type = element.element_type.into_pretty();
+
+            // the characters of a string literal, as many as fit
+            if let Some(literal) = string_literal(&element.value)
+                .filter(|_| is_character_array(&element_type.inner_type))
+            {
+                let ExtType::Array { to, .. } = &element_type.inner_type else {
+                    unreachable!()
+                };
+                let characters = MIRConstant::from_string(literal.to_string()).value.len() as u64;
+                let copied_type = ExtType::Array {
+                    is_const: false,
+                    is_volatile: false,
+                    arr_size: Some(characters.min(element_type.size_of())),
+                    to: to.clone(),
+                };
+                let source_type = ExtType::Pointer {
+                    is_const: false,
+                    is_volatile: false,
+                    to: to.clone(),
+                };
+                let source =
+                    self.walk_expression(ctx, element.value.clone(), &source_type.into_pretty());
+                MIRBlock::ins_instr(
+                    &ctx.mir_function.current_block,
+                    MIRInstruction::CopyMemory(
+                        element_address,
+                        source,
+                        MIRType::extract_from_pretty_type(&copied_type.into_pretty()),
+                    ),
+                );
+                continue;
+            }
+
+            let value = self.walk_expression(ctx, element.value.clone(), &element_type);
+            let value = self.convert_value(ctx, value, &element_type);
+            MIRLocatorValue::Memory(element_address, element_type).assign_value(ctx, value);
         }
     }
 }
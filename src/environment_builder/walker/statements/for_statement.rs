@@ -1,10 +1,6 @@
 use crate::{
-    environment_builder::ext_type::ExtType,
-    mir::{MIRBlock, MIRBranch, MIRInstruction, MIRType, MIRValue},
-    parser::parse_nodes::{
-        declarations::{DeclarationSpecifiers, Declarator, Initializer},
-        expressions::CExpression,
-    },
+    environment_builder::{ext_type::ExtType, walker::walk_func::JumpTargets},
+    parser::parse_nodes::expressions::CExpression,
 };
 
 use super::{statements::CompoundItem, *};
@@ -35,79 +31,38 @@ impl EnvironmentController {
             self.walk_expression(ctx, expr_clause.clone(), &ExtType::Void.into_pretty());
         }
 
-        // make header_block
-        let header_block_id = ctx.mir_function.blocks.len();
-        let header_block = MIRBlock::new_wrapped();
-        ctx.mir_function.blocks.push(header_block.clone());
+        let header_block = ctx.make_block();
+        let body_block = ctx.make_block();
+        let latch_block = ctx.make_block();
+        let ending_block = ctx.make_block();
+        ctx.fall_through_to_block(header_block);
 
-        // ending_block
-        let ending_block_id = ctx.mir_function.blocks.len();
-        let ending_block = MIRBlock::new_wrapped();
-        ctx.mir_function.blocks.push(ending_block.clone());
-
-        //  body_block
-        let body_block_id = ctx.mir_function.blocks.len();
-        let body_block = MIRBlock::new_wrapped();
-        ctx.mir_function.blocks.push(body_block.clone());
-
-        ctx.mir_function.current_block.borrow_mut().branches = Some((
-            MIRValue { opaque_ref: 0 },
-            vec![MIRBranch {
-                is_default: true,
-                value_needed: 0,
-                to_block: header_block_id,
-            }],
-        ));
-
-        // set current block
-        ctx.mir_function.current_block = header_block.clone();
-
-        if let Some(controlling_expr) = controlling_expr {
-            let control_value = self.walk_expression(
-                ctx,
-                controlling_expr.clone(),
-                &ExtType::Int {
-                    is_const: false,
-                    is_volatile: false,
-                    signed: true,
-                    size: 4,
-                }
-                .into_pretty(),
-            );
-
-            ctx.mir_function.current_block.borrow_mut().branches = Some((
-                control_value,
-                vec![
-                    MIRBranch {
-                        is_default: false,
-                        value_needed: 1,
-                        to_block: body_block_id,
-                    },
-                    MIRBranch {
-                        is_default: true,
-                        value_needed: 0,
-                        to_block: ending_block_id,
-                    },
-                ],
-            ));
+        // without a controlling expression the loop only ends through break, return or goto
+        ctx.mir_function.current_block = ctx.mir_function.blocks[header_block].clone();
+        match controlling_expr {
+            Some(controlling_expr) => {
+                self.walk_loop_condition(ctx, controlling_expr, body_block, ending_block)
+            }
+            None => ctx.jump_to_block(body_block),
         }
-        ctx.mir_function.current_block = body_block;
 
-        self.walk_statement(ctx, body.clone());
+        ctx.mir_function.current_block = ctx.mir_function.blocks[body_block].clone();
+        self.walk_loop_body(
+            ctx,
+            body,
+            JumpTargets {
+                break_block: ending_block,
+                continue_block: Some(latch_block),
+            },
+        );
+        ctx.fall_through_to_block(latch_block);
 
-        ctx.mir_function.current_block.borrow_mut().branches = Some((
-            MIRValue { opaque_ref: 0 },
-            vec![MIRBranch {
-                is_default: true,
-                value_needed: 0,
-                to_block: header_block_id,
-            }],
-        ));
-
-        // after_expr
+        ctx.mir_function.current_block = ctx.mir_function.blocks[latch_block].clone();
         if let Some(after_expr) = after_expr {
             self.walk_expression(ctx, after_expr.clone(), &ExtType::Void.into_pretty());
         }
-        ctx.mir_function.current_block = ending_block;
+        ctx.jump_to_block(header_block);
+
+        ctx.mir_function.current_block = ctx.mir_function.blocks[ending_block].clone();
     }
 }
@@ -0,0 +1,74 @@
+use crate::{
+    environment_builder::ext_type::ExtType,
+    mir::MIRBlock,
+    parser::{
+        parse_nodes::{expressions::CExpression, Identifier},
+        span::Span,
+    },
+};
+
+use super::*;
+
+impl EnvironmentController {
+    pub(crate) fn handle_labeled_statement(
+        &mut self,
+        ctx: &mut FunctionContext,
+        label: &Identifier,
+        body: &Spanned<Statement>,
+        span: &Span,
+    ) {
+        if !ctx.defined_labels.insert(label.identifier.clone()) {
+            span.fail_at_span(&format!("redefinition of label '{}'", label.identifier));
+        }
+
+        let label_block_id = ctx.get_label_block(&label.identifier);
+
+        // fall through into the label
+        if !ctx.mir_function.current_block.borrow().is_exit_block {
+            ctx.jump_to_block(label_block_id);
+        }
+
+        ctx.mir_function.current_block = ctx.mir_function.blocks[label_block_id].clone();
+        self.walk_statement(ctx, body.clone());
+    }
+
+    /// (6.8.6.1) labels have function scope, so the jump may go forward to a label defined later,
+    /// variable length arrays are rejected, so there is no variably modified type whose scope it could enter
+    pub(crate) fn handle_goto_statement(
+        &mut self,
+        ctx: &mut FunctionContext,
+        label: &Identifier,
+        span: &Span,
+    ) {
+        let label_block_id = ctx.use_label(&label.identifier, span);
+        ctx.leave_to_block(label_block_id);
+    }
+
+    /// GNU: goto * expression ;
+    pub(crate) fn handle_goto_indirect_statement(
+        &mut self,
+        ctx: &mut FunctionContext,
+        target: &Spanned<CExpression>,
+    ) {
+        let target_value = self.walk_expression(
+            ctx,
+            target.clone(),
+            &ExtType::Pointer {
+                is_const: false,
+                is_volatile: false,
+                to: Box::new(ExtType::Void),
+            }
+            .into_pretty(),
+        );
+
+        // the branches are only known after the whole function was walked
+        ctx.indirect_gotos
+            .push((ctx.mir_function.current_block.clone(), target_value));
+        ctx.mir_function.current_block.borrow_mut().is_exit_block = true;
+
+        // anything up to the next label is unreachable
+        let unreachable_block = MIRBlock::new_wrapped();
+        ctx.mir_function.blocks.push(unreachable_block.clone());
+        ctx.mir_function.current_block = unreachable_block;
+    }
+}
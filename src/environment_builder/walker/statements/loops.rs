@@ -0,0 +1,104 @@
+use crate::{
+    environment_builder::{ext_type::ExtType, walker::walk_func::JumpTargets},
+    parser::{parse_nodes::expressions::CExpression, span::Span},
+};
+
+use super::*;
+
+/*
+(6.8.5) the loops are lowered into blocks like this:
+    while:      header: brnz cond body; jump end    body: ...; jump header
+    do-while:   body: ...; jump condition           condition: brnz cond body; jump end
+    for:        header: brnz cond body; jump end    body: ...; jump latch
+                latch: after_expr; jump header
+(6.8.6.2) continue jumps to the header, the condition or the latch of the innermost loop
+(6.8.6.3) break jumps to the end of the innermost loop or switch
+*/
+impl EnvironmentController {
+    pub(crate) fn handle_while_statement(
+        &mut self,
+        ctx: &mut FunctionContext,
+        is_do_while: bool,
+        controlling_expr: &Spanned<CExpression>,
+        body: &Spanned<Statement>,
+    ) {
+        let body_block = ctx.make_block();
+        let condition_block = ctx.make_block();
+        let end_block = ctx.make_block();
+
+        ctx.fall_through_to_block(if is_do_while {
+            body_block
+        } else {
+            condition_block
+        });
+
+        ctx.mir_function.current_block = ctx.mir_function.blocks[condition_block].clone();
+        self.walk_loop_condition(ctx, controlling_expr, body_block, end_block);
+
+        ctx.mir_function.current_block = ctx.mir_function.blocks[body_block].clone();
+        self.walk_loop_body(
+            ctx,
+            body,
+            JumpTargets {
+                break_block: end_block,
+                continue_block: Some(condition_block),
+            },
+        );
+        ctx.fall_through_to_block(condition_block);
+
+        ctx.mir_function.current_block = ctx.mir_function.blocks[end_block].clone();
+    }
+
+    /// branches to `body_block` while `controlling_expr` is not zero, to `end_block` after that
+    pub(crate) fn walk_loop_condition(
+        &mut self,
+        ctx: &mut FunctionContext,
+        controlling_expr: &Spanned<CExpression>,
+        body_block: usize,
+        end_block: usize,
+    ) {
+        let control_value = self.walk_expression(
+            ctx,
+            controlling_expr.clone(),
+            &ExtType::Int {
+                is_const: false,
+                is_volatile: false,
+                signed: true,
+                size: 4,
+            }
+            .into_pretty(),
+        );
+        ctx.branch_on_value(control_value, body_block, end_block);
+    }
+
+    /// the body of a loop or switch, `break` and `continue` in it go to `targets`
+    pub(crate) fn walk_loop_body(
+        &mut self,
+        ctx: &mut FunctionContext,
+        body: &Spanned<Statement>,
+        targets: JumpTargets,
+    ) {
+        ctx.jump_targets.push(targets);
+        self.walk_statement(ctx, body.clone());
+        ctx.jump_targets.pop();
+    }
+
+    pub(crate) fn handle_break_statement(&mut self, ctx: &mut FunctionContext, span: &Span) {
+        let Some(targets) = ctx.jump_targets.last() else {
+            span.fail_at_span("'break' statement not in loop or switch statement")
+        };
+        ctx.leave_to_block(targets.break_block);
+    }
+
+    pub(crate) fn handle_continue_statement(&mut self, ctx: &mut FunctionContext, span: &Span) {
+        let Some(continue_block) = ctx
+            .jump_targets
+            .iter()
+            .rev()
+            .find_map(|targets| targets.continue_block)
+        else {
+            span.fail_at_span("'continue' statement not in loop statement")
+        };
+        ctx.leave_to_block(continue_block);
+    }
+}
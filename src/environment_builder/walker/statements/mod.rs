@@ -12,7 +12,10 @@ pub(crate) use crate::{
 mod compound;
 mod for_statement;
 mod if_statement;
+mod labels;
+mod loops;
 mod r#return;
+mod switch;
 
 impl EnvironmentController {
     pub(crate) fn walk_statement(
@@ -22,12 +25,18 @@ impl EnvironmentController {
     ) {
         debug!("{}", serde_yaml::to_string(&statement).unwrap());
         match &*statement.inner {
-            Statement::Labeled { label: _, body: _ } => todo!(),
+            Statement::Labeled { label, body } => {
+                self.handle_labeled_statement(ctx, label, body, &statement.span);
+            }
             Statement::SwitchCase {
-                const_expr: _,
-                statement: _,
-            } => todo!(),
-            Statement::SwitchDefault { statement: _ } => todo!(),
+                const_expr,
+                statement: body,
+            } => {
+                self.handle_switch_case(ctx, const_expr, body, &statement.span);
+            }
+            Statement::SwitchDefault { statement: body } => {
+                self.handle_switch_default(ctx, body, &statement.span);
+            }
             Statement::Compound(compound_statement_list) => {
                 self.handle_compound_statement(ctx, compound_statement_list);
             }
@@ -43,14 +52,18 @@ impl EnvironmentController {
                 self.handle_if_statement(ctx, controlling_expr, true_body, else_body);
             }
             Statement::Switch {
-                controlling_expr: _,
-                body: _,
-            } => todo!(),
+                controlling_expr,
+                body,
+            } => {
+                self.handle_switch_statement(ctx, controlling_expr, body);
+            }
             Statement::While {
-                while_type: _,
-                controlling_expr: _,
-                body: _,
-            } => todo!(),
+                while_type,
+                controlling_expr,
+                body,
+            } => {
+                self.handle_while_statement(ctx, *while_type, controlling_expr, body);
+            }
             Statement::For {
                 decl_clause,
                 expr_clause,
@@ -67,11 +80,20 @@ impl EnvironmentController {
                     body,
                 );
             }
-            Statement::Goto(_) => todo!(),
-            Statement::Continue => todo!(),
-            Statement::Break => todo!(),
+            Statement::Goto(label) => {
+                self.handle_goto_statement(ctx, label, &statement.span);
+            }
+            Statement::GotoIndirect(target) => {
+                self.handle_goto_indirect_statement(ctx, target);
+            }
+            Statement::Continue => {
+                self.handle_continue_statement(ctx, &statement.span);
+            }
+            Statement::Break => {
+                self.handle_break_statement(ctx, &statement.span);
+            }
             Statement::Return(return_expr) => {
-                self.handle_return_statement(ctx, return_expr);
+                self.handle_return_statement(ctx, return_expr, &statement.span);
             }
         }
     }
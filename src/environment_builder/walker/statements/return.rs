@@ -1,31 +1,38 @@
+use crate::environment_builder::ext_type::ExtType;
 use crate::mir::{MIRBlock, MIRInstruction};
 use crate::parser::parse_nodes::expressions::CExpression;
+use crate::parser::span::Span;
 
 use super::*;
 
 impl EnvironmentController {
+    /* (6.8.6.4) only a function returning void has a return without expression */
     pub(crate) fn handle_return_statement(
         &mut self,
         ctx: &mut FunctionContext,
         return_expr: &Option<Spanned<CExpression>>,
+        span: &Span,
     ) {
         debug!("return statement!");
-        if let Some(expr) = return_expr {
-            let return_value =
-                self.walk_expression(ctx, expr.clone(), &ctx.pretty_return_type.clone());
-            MIRBlock::ins_instr(
-                &ctx.mir_function.current_block,
-                MIRInstruction::Return(return_value),
-            );
-            ctx.mir_function.current_block.borrow_mut().is_exit_block = true; // no branching from here on out we already returned!
-        } else {
-            warn!("return without parameter is probably not functioning correctly!");
-            // ctx.mir_function.blocks[0]
-            //     .instr
-            //     .push(MIR_Instruction::Return(MIR_Location::Constant(
-            //         0,
-            //         MIRType::i64,
-            //     )));
-        }
+        let return_type = ctx.pretty_return_type.clone();
+        let returns_void = return_type.inner_type == ExtType::Void;
+        let return_value = match return_expr {
+            // like gcc, a void function may return a void expression
+            Some(expr) if returns_void => {
+                self.walk_expression_with_own_type(ctx, expr);
+                None
+            }
+            Some(expr) => {
+                let return_value = self.walk_expression(ctx, expr.clone(), &return_type);
+                Some(self.convert_value(ctx, return_value, &return_type))
+            }
+            None if returns_void => None,
+            None => span.fail_at_span("return without a value in a function returning non-void"),
+        };
+        MIRBlock::ins_instr(
+            &ctx.mir_function.current_block,
+            MIRInstruction::Return(return_value),
+        );
+        ctx.mir_function.current_block.borrow_mut().is_exit_block = true; // no branching from here on out we already returned!
     }
 }
@@ -0,0 +1,150 @@
+use crate::{
+    environment_builder::{
+        constant_expr::promoted,
+        ext_type::ExtType,
+        walker::walk_func::{JumpTargets, SwitchCases},
+    },
+    mir::MIRBranch,
+    parser::{
+        parse_nodes::expressions::{CExpression, ConstantExpression},
+        span::Span,
+    },
+};
+
+use super::*;
+
+/*
+(6.8.4.2) the controlling expression of a switch is promoted, the case values are converted to its type:
+    dispatch:   value = (promoted) controlling_expr; switch value: case_0 -> label_0, ... default -> default or end
+    body:       code before the first label is unreachable
+    label_n:    labels fall through into each other, break jumps to end
+    end:
+the case labels belong to the innermost switch around them, even inside nested statements,
+so they are only known once the body was walked and the dispatch block is filled last
+*/
+impl EnvironmentController {
+    pub(crate) fn handle_switch_statement(
+        &mut self,
+        ctx: &mut FunctionContext,
+        controlling_expr: &Spanned<CExpression>,
+        body: &Spanned<Statement>,
+    ) {
+        let value = self.walk_expression(
+            ctx,
+            controlling_expr.clone(),
+            &ExtType::Int {
+                is_const: false,
+                is_volatile: false,
+                signed: true,
+                size: 4,
+            }
+            .into_pretty(),
+        );
+        let value_type = &ctx.mir_function.value_type_map_pretty[&value].inner_type;
+        if !matches!(value_type, ExtType::Int { .. }) {
+            controlling_expr
+                .span
+                .fail_at_span("switch quantity is not an integer")
+        }
+        let case_type = promoted(value_type);
+        let value = self.convert_value(ctx, value, &case_type.clone().into_pretty());
+        let dispatch_block = ctx.mir_function.current_block.clone();
+
+        let body_block = ctx.make_block();
+        let end_block = ctx.make_block();
+        ctx.mir_function.current_block = ctx.mir_function.blocks[body_block].clone();
+        ctx.switches.push(SwitchCases {
+            case_type,
+            cases: vec![],
+            default_block: None,
+        });
+        self.walk_loop_body(
+            ctx,
+            body,
+            JumpTargets {
+                break_block: end_block,
+                continue_block: None,
+            },
+        );
+        let switch_cases = ctx.switches.pop().unwrap();
+        ctx.fall_through_to_block(end_block);
+
+        let mut branches: Vec<MIRBranch> = switch_cases
+            .cases
+            .iter()
+            .map(|(case_value, case_block)| MIRBranch {
+                is_default: false,
+                value_needed: *case_value,
+                to_block: *case_block,
+            })
+            .collect();
+        branches.push(MIRBranch {
+            is_default: true,
+            value_needed: 0,
+            to_block: switch_cases.default_block.unwrap_or(end_block),
+        });
+        let mut dispatch_block = dispatch_block.borrow_mut();
+        dispatch_block.branches = Some((value, branches));
+        dispatch_block.is_switch = true;
+        drop(dispatch_block);
+
+        ctx.mir_function.current_block = ctx.mir_function.blocks[end_block].clone();
+    }
+
+    pub(crate) fn handle_switch_case(
+        &mut self,
+        ctx: &mut FunctionContext,
+        const_expr: &ConstantExpression,
+        statement: &Spanned<Statement>,
+        span: &Span,
+    ) {
+        let Some(switch_cases) = ctx.switches.last() else {
+            span.fail_at_span("'case' label not in switch statement")
+        };
+        let value = self.expect_integer_constant(&const_expr.internal);
+        let case_value = match &switch_cases.case_type {
+            ExtType::Int { size: 8, .. } => value as u64,
+            ExtType::Int { size, .. } => value as u64 & ((1u64 << (size * 8)) - 1),
+            _ => unreachable!("the controlling expression is a promoted integer"),
+        };
+        if switch_cases
+            .cases
+            .iter()
+            .any(|(other_value, _)| *other_value == case_value)
+        {
+            // the label from `case` to the end of its value
+            Span::new(span.start.clone(), const_expr.internal.span.end.clone())
+                .fail_at_span(&format!("duplicate case value '{}'", value))
+        }
+
+        let case_block = ctx.make_block();
+        ctx.fall_through_to_block(case_block);
+        ctx.switches
+            .last_mut()
+            .unwrap()
+            .cases
+            .push((case_value, case_block));
+        ctx.mir_function.current_block = ctx.mir_function.blocks[case_block].clone();
+        self.walk_statement(ctx, statement.clone());
+    }
+
+    pub(crate) fn handle_switch_default(
+        &mut self,
+        ctx: &mut FunctionContext,
+        statement: &Spanned<Statement>,
+        span: &Span,
+    ) {
+        let Some(switch_cases) = ctx.switches.last() else {
+            span.fail_at_span("'default' label not in switch statement")
+        };
+        if switch_cases.default_block.is_some() {
+            span.fail_at_span("multiple default labels in one switch")
+        }
+
+        let default_block = ctx.make_block();
+        ctx.fall_through_to_block(default_block);
+        ctx.switches.last_mut().unwrap().default_block = Some(default_block);
+        ctx.mir_function.current_block = ctx.mir_function.blocks[default_block].clone();
+        self.walk_statement(ctx, statement.clone());
+    }
+}
@@ -1,12 +1,35 @@
 use crate::{
-    environment_builder::EnvironmentController,
+    environment_builder::{CompileTimeValue, EnvironmentController},
     parser::{parse_nodes::declarations::StaticAssertDeclaration, span::Spanned},
 };
 
+/*
+(6.7.11) static_assert-declaration:
+    static_assert ( constant-expression , string-literal ) ;
+    static_assert ( constant-expression ) ;
+the declaration has no effect if the constant expression is not 0
+*/
 impl EnvironmentController {
     pub(crate) fn handle_static_assert(
         &mut self,
-        _static_assert: &Spanned<StaticAssertDeclaration>,
+        static_assert: &Spanned<StaticAssertDeclaration>,
     ) {
+        let holds = match self.run_constant_expression(&static_assert.expression) {
+            Ok(CompileTimeValue::Int(value)) => value != 0,
+            Ok(_) => static_assert
+                .span
+                .fail_at_span("static assertion expression is not an integer constant"),
+            Err(reason) => static_assert.span.fail_at_span(&format!(
+                "static assertion expression is not constant: {}",
+                reason
+            )),
+        };
+        if !holds {
+            let message = match &static_assert.string_literal {
+                Some(literal) => format!("static assertion failed: {}", literal.value),
+                None => "static assertion failed".to_string(),
+            };
+            static_assert.span.fail_at_span(&message)
+        }
     }
 }
@@ -0,0 +1,249 @@
+use crate::{
+    environment_builder::{
+        ext_type::{ExtType, PrettyType},
+        initializer::{is_character_array, string_literal},
+        CompileTimeValue, EnvironmentController,
+    },
+    mir::{GlobalDefinition, GlobalEntity, GlobalRelocation, MIRConstant, MIRSignature},
+    parser::{
+        parse_nodes::{declarations::Initializer, expressions::CExpression},
+        span::Span,
+    },
+};
+
+use super::Spanned;
+
+/*
+(6.2.4) objects declared at file scope or with `static` have static storage duration:
+    each one is a data object of its own, functions reach it through the address of its symbol
+    (6.7.10) its initializer has to be made of constant expressions, without one it is zero
+    `extern` only declares an object that is defined somewhere else
+*/
+
+/// const objects can go to read only memory, arrays are as const as their elements
+fn is_const_object(object_type: &ExtType) -> bool {
+    match object_type {
+        ExtType::Array { is_const, to, .. } => *is_const || is_const_object(to),
+        ExtType::Int { is_const, .. }
+        | ExtType::Float { is_const, .. }
+        | ExtType::Pointer { is_const, .. }
+        | ExtType::Struct { is_const, .. }
+        | ExtType::Union { is_const, .. } => *is_const,
+        ExtType::Void | ExtType::Function { .. } => false,
+    }
+}
+
+impl EnvironmentController {
+    /// (6.7.10) an array of unknown size gets its size from the initializer
+    pub(crate) fn type_completed_by_initializer(
+        &mut self,
+        object_type: PrettyType,
+        initializer: &Spanned<Initializer>,
+    ) -> PrettyType {
+        match object_type.inner_type {
+            ExtType::Array {
+                is_const,
+                is_volatile,
+                arr_size: None,
+                to,
+            } => {
+                let incomplete = ExtType::Array {
+                    is_const,
+                    is_volatile,
+                    arr_size: None,
+                    to: to.clone(),
+                };
+                let (_, count) = self.flatten_initializer(&incomplete, initializer);
+                ExtType::Array {
+                    is_const,
+                    is_volatile,
+                    arr_size: Some(count),
+                    to,
+                }
+                .into_pretty()
+            }
+            inner_type => inner_type.into_pretty(),
+        }
+    }
+
+    /// records the data object behind `symbol`, a declaration without definition imports it
+    pub(crate) fn define_static_object(
+        &mut self,
+        symbol: &str,
+        extern_linkage: bool,
+        is_definition: bool,
+        object_type: &PrettyType,
+        initializer: Option<&Spanned<Initializer>>,
+        span: &Span,
+    ) {
+        let definition = is_definition.then(|| self.static_initial_data(object_type, initializer));
+        let global = GlobalEntity {
+            name: symbol.to_string(),
+            extern_linkage,
+            definition,
+        };
+
+        let Some(existing) = self
+            .mir_programm
+            .globals
+            .iter_mut()
+            .find(|existing| existing.name == global.name)
+        else {
+            self.mir_programm.globals.push(global);
+            return;
+        };
+        // tentative definitions and declarations can be repeated, initializers can not
+        let is_initialized = |global: &GlobalEntity| {
+            global
+                .definition
+                .as_ref()
+                .is_some_and(|definition| definition.bytes.is_some())
+        };
+        if is_initialized(existing) && is_initialized(&global) {
+            span.fail_at_span(&format!("redefinition of '{}'", symbol))
+        }
+        if global.definition.is_some() && !is_initialized(existing) {
+            existing.definition = global.definition;
+        }
+        existing.extern_linkage &= global.extern_linkage;
+    }
+
+    fn static_initial_data(
+        &mut self,
+        object_type: &PrettyType,
+        initializer: Option<&Spanned<Initializer>>,
+    ) -> GlobalDefinition {
+        let object_type = self.complete_type(object_type.clone()).inner_type;
+        let mut definition = GlobalDefinition {
+            size: object_type.size_of(),
+            alignment: object_type.align_of(),
+            writable: !is_const_object(&object_type),
+            bytes: None,
+            relocations: vec![],
+        };
+        if let Some(initializer) = initializer {
+            definition.bytes = Some(vec![0; object_type.size_of() as usize]);
+            self.write_static_initializer(&mut definition, 0, &object_type, initializer);
+        }
+        definition
+    }
+
+    fn write_static_initializer(
+        &mut self,
+        definition: &mut GlobalDefinition,
+        offset: u64,
+        object_type: &ExtType,
+        initializer: &Spanned<Initializer>,
+    ) {
+        let (elements, _) = self.flatten_initializer(object_type, initializer);
+        for element in elements {
+            self.write_static_value(
+                definition,
+                offset + element.offset,
+                &element.element_type,
+                &element.value,
+            );
+        }
+    }
+
+    /// stores the value of the constant `expr` converted to `object_type` at `offset`
+    fn write_static_value(
+        &mut self,
+        definition: &mut GlobalDefinition,
+        offset: u64,
+        object_type: &ExtType,
+        expr: &Spanned<CExpression>,
+    ) {
+        let start = offset as usize;
+        let size = object_type.size_of() as usize;
+
+        // a later initializer for the same element replaces an address written before
+        definition.relocations.retain(|relocation| {
+            !(start as u64..(start + size) as u64).contains(&relocation.offset)
+        });
+
+        // a character array is initialized by the characters of a string literal
+        if let Some(literal) = string_literal(expr).filter(|_| is_character_array(object_type)) {
+            let characters = MIRConstant::from_string(literal.to_string()).value;
+            let copied = characters.len().min(size);
+            definition.bytes.as_mut().unwrap()[start..start + copied]
+                .copy_from_slice(&characters[..copied]);
+            return;
+        }
+
+        // GNU: a compound literal of a struct or union type is written in place of the object
+        if let CExpression::TypeInitializer {
+            initializer_list, ..
+        } = &*expr.inner
+        {
+            if matches!(object_type, ExtType::Struct { .. } | ExtType::Union { .. }) {
+                self.write_static_initializer(definition, offset, object_type, initializer_list);
+                return;
+            }
+        }
+
+        let value = match self.evaluate_constant_as(expr, object_type) {
+            Ok(value) => value,
+            Err(reason) => expr.span.fail_at_span(&format!(
+                "initializer element is not a constant expression: {}",
+                reason
+            )),
+        };
+        let bytes = definition.bytes.as_mut().unwrap();
+        match value {
+            CompileTimeValue::Int(int) => {
+                bytes[start..start + size].copy_from_slice(&int.to_le_bytes()[..size])
+            }
+            CompileTimeValue::Float(float) if size == 4 => {
+                bytes[start..start + size].copy_from_slice(&(float as f32).to_le_bytes())
+            }
+            CompileTimeValue::Float(float) => {
+                bytes[start..start + size].copy_from_slice(&float.to_le_bytes())
+            }
+            CompileTimeValue::Address { symbol, offset } => {
+                let function = self.function_signature_of(&symbol);
+                definition.relocations.push(GlobalRelocation {
+                    offset: start as u64,
+                    symbol,
+                    addend: offset,
+                    function,
+                });
+            }
+            CompileTimeValue::String(literal) => {
+                let symbol = self.define_string_literal(literal);
+                definition.relocations.push(GlobalRelocation {
+                    offset: start as u64,
+                    symbol,
+                    addend: 0,
+                    function: None,
+                });
+            }
+        }
+    }
+
+    /// the signature of `symbol` if it names a function
+    fn function_signature_of(&mut self, symbol: &str) -> Option<MIRSignature> {
+        let variable = self.symbol_table.get_file_scope_variable(symbol)?;
+        let symbol_type = variable.borrow().associated_type.clone();
+        matches!(symbol_type.inner_type, ExtType::Function { .. })
+            .then(|| MIRSignature::from_function_pretty_type(&symbol_type))
+    }
+
+    /// a read only data object holding a string literal that a pointer is initialized to
+    fn define_string_literal(&mut self, literal: String) -> String {
+        let symbol = format!(".L.str.{}", self.mir_programm.globals.len());
+        let characters = MIRConstant::from_string(literal).value;
+        self.mir_programm.globals.push(GlobalEntity {
+            name: symbol.clone(),
+            extern_linkage: false,
+            definition: Some(GlobalDefinition {
+                size: characters.len() as u64,
+                alignment: 1,
+                writable: false,
+                bytes: Some(characters),
+                relocations: vec![],
+            }),
+        });
+        symbol
+    }
+}
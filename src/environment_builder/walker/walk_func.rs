@@ -1,4 +1,9 @@
-use std::{borrow::Borrow, cell::RefCell};
+use std::{
+    borrow::Borrow,
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
 
 use log::{debug, info};
 
@@ -8,23 +13,35 @@ use crate::{
         symbol_table::VariableInstance,
         EnvironmentController,
     },
-    mir::{GlobalEntity, MIRFunction, MIRSignature, MIRType},
-    parser::{parse_nodes::FunctionDefinition, span::Spanned},
+    mir::{
+        IntCmpKind, LocalRef, MIRBlock, MIRBranch, MIRFunction, MIRInstruction, MIRLocatorValue,
+        MIRSignature, MIRType, MIRValue,
+    },
+    parser::{
+        parse_nodes::{declarations::Declaration, FunctionDefinition},
+        span::{Span, Spanned},
+    },
 };
 
 impl EnvironmentController {
     pub(crate) fn walk_func(&mut self, func: Spanned<FunctionDefinition>) {
-        let extracted_type = self
+        let mut extracted_type = self
             .extract_pretty_type_from_declaration_specifiers_and_derived_declarator(
                 func.function_specifiers.clone(),
                 func.declarator.derive.clone(),
             );
+        let declared_types =
+            self.apply_parameter_declarations(&mut extracted_type, &func.declarations);
         self.symbol_table.scope.variables.insert(
             func.declarator.base.identifier.clone(),
             RefCell::new(VariableInstance {
                 is_extern: false,
                 usage_counter: 0,
                 associated_type: extracted_type.clone(),
+                constant_value: None,
+                constexpr_value: None,
+                static_symbol: None,
+                local: None,
             }),
         );
         info!(
@@ -42,24 +59,54 @@ impl EnvironmentController {
             parameters,
         } = &extracted_type.inner_type
         {
+            // the parameters are only visible inside the function
+            self.symbol_table.enter_new_level();
             for parameter_name in parameters {
+                let passed_type = parameter_name.parameter_type.into_pretty();
+                let declared_type = declared_types
+                    .get(&parameter_name.ident)
+                    .cloned()
+                    .unwrap_or_else(|| passed_type.clone());
+                // a K&R parameter is passed promoted, the body sees the declared type
+                let is_converted = declared_type != passed_type;
+                let passed_name = if is_converted {
+                    format!(".param.{}", parameter_name.ident)
+                } else {
+                    parameter_name.ident.clone()
+                };
                 // param name for later
                 func_ctx
                     .mir_function
                     .parameter_names
-                    .push(parameter_name.ident.clone());
+                    .push(passed_name.clone());
                 // param is also a local variable
-                func_ctx.mir_function.insert_variable(
-                    parameter_name.ident.clone(),
-                    parameter_name.parameter_type.into_pretty(),
-                );
+                let passed_local = func_ctx
+                    .mir_function
+                    .insert_variable(passed_name, passed_type.clone());
+                let mut local = passed_local;
+                if is_converted {
+                    let declared_local = func_ctx
+                        .mir_function
+                        .insert_variable(parameter_name.ident.clone(), declared_type.clone());
+                    let passed_value = MIRLocatorValue::LocalVar(passed_local, passed_type)
+                        .into_rvlaue(&mut func_ctx);
+                    let declared_value =
+                        self.convert_value(&mut func_ctx, passed_value, &declared_type);
+                    MIRLocatorValue::LocalVar(declared_local, declared_type.clone())
+                        .assign_value(&mut func_ctx, declared_value);
+                    local = declared_local;
+                }
                 // it is also in the symbol table
                 self.symbol_table.get_current_scope().variables.insert(
                     parameter_name.ident.clone(),
                     RefCell::new(VariableInstance {
                         is_extern: false,
                         usage_counter: 0,
-                        associated_type: parameter_name.parameter_type.into_pretty(),
+                        associated_type: declared_type,
+                        constant_value: None,
+                        constexpr_value: None,
+                        static_symbol: None,
+                        local: Some(local),
                     }),
                 );
             }
@@ -69,6 +116,23 @@ impl EnvironmentController {
         }
 
         self.walk_statement(&mut func_ctx, func.body.clone());
+        // (6.9.1) reaching the closing } of a void function returns
+        if func_ctx.pretty_return_type.inner_type == ExtType::Void
+            && !RefCell::borrow(&func_ctx.mir_function.current_block).is_exit_block
+        {
+            MIRBlock::ins_instr(
+                &func_ctx.mir_function.current_block,
+                MIRInstruction::Return(None),
+            );
+            func_ctx
+                .mir_function
+                .current_block
+                .borrow_mut()
+                .is_exit_block = true;
+        }
+        func_ctx.check_used_labels();
+        func_ctx.resolve_indirect_gotos();
+        self.symbol_table.exit_new_level();
 
         let used_vars = self
             .symbol_table
@@ -89,31 +153,281 @@ impl EnvironmentController {
         debug!("{:#?}", func_ctx.mir_function.value_type_map);
 
         self.mir_programm.functions.push(func_ctx.mir_function);
+    }
+}
 
-        // TODO: move this
-        self.mir_programm.globals.extend(
-            self.symbol_table
-                .scope
-                .variables
-                .iter()
-                .filter(|var| var.1.borrow().usage_counter > 0)
-                .map(|extern_var| GlobalEntity {
-                    name: extern_var.0.clone(),
-                    extern_linkage: extern_var.1.borrow().is_extern,
-                }),
-        );
+impl EnvironmentController {
+    /// K&R definitions declare the parameter types between declarator and body:
+    /// `int f(a, b) char a; double b; { ... }`, undeclared parameters stay int,
+    /// the function type gets the promoted types, the declared ones are returned by name
+    fn apply_parameter_declarations(
+        &mut self,
+        function_type: &mut PrettyType,
+        declarations: &[Spanned<Declaration>],
+    ) -> BTreeMap<String, PrettyType> {
+        let ExtType::Function { parameters, .. } = &mut function_type.inner_type else {
+            unreachable!()
+        };
+        let mut declared_types = BTreeMap::new();
+
+        for declaration in declarations {
+            let Declaration::Declaration { specifiers, init } = &*declaration.inner else {
+                declaration
+                    .span
+                    .fail_at_span("only parameter declarations may precede a K&R function body");
+            };
+            for (declarator, initializer) in init {
+                let name = &declarator.base.identifier;
+                if initializer.is_some() {
+                    declarator
+                        .span
+                        .fail_at_span(&format!("parameter '{}' is initialized", name));
+                }
+                let Some(parameter) = parameters.iter_mut().find(|param| &param.ident == name)
+                else {
+                    declarator.span.fail_at_span(&format!(
+                        "declaration for '{}', which is no parameter",
+                        name
+                    ));
+                };
+
+                // (6.5.2.2) callers pass promoted values
+                let declared_type = self
+                    .extract_pretty_type_from_declaration_specifiers_and_derived_declarator(
+                        specifiers.clone(),
+                        declarator.derive.clone(),
+                    );
+                *parameter.parameter_type =
+                    declared_type.inner_type.clone().default_argument_promoted();
+                declared_types.insert(name.clone(), declared_type);
+            }
+        }
+        declared_types
     }
 }
 
 pub(crate) struct FunctionContext {
     pub(crate) mir_function: MIRFunction,
     pub(crate) pretty_return_type: PrettyType,
+    /// labels have function scope, so blocks are made on first use: definition, goto or &&label
+    pub(crate) label_blocks: BTreeMap<String, usize>,
+    pub(crate) defined_labels: BTreeSet<String>,
+    /// where each label was first used by goto or &&label, it has to be defined somewhere
+    pub(crate) used_labels: BTreeMap<String, Span>,
+    /// blocks of labels whose address was taken, the possible targets of `goto *`
+    pub(crate) address_taken_labels: BTreeSet<usize>,
+    /// block ending in `goto *` and the target value, resolved once all labels are known
+    pub(crate) indirect_gotos: Vec<(Rc<RefCell<MIRBlock>>, MIRValue)>,
+    /// the loops and switches around the current statement, the innermost one last
+    pub(crate) jump_targets: Vec<JumpTargets>,
+    /// the switches around the current statement, the innermost one last
+    pub(crate) switches: Vec<SwitchCases>,
+}
+
+/// where `break` and `continue` inside a loop or switch go to
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct JumpTargets {
+    pub(crate) break_block: usize,
+    /// a switch has none, `continue` belongs to the loop around it
+    pub(crate) continue_block: Option<usize>,
+}
+
+/// the case labels of a switch, collected while its body is walked
+#[derive(Debug, Clone)]
+pub(crate) struct SwitchCases {
+    /// the promoted type of the controlling expression, the case values are converted to it
+    pub(crate) case_type: ExtType,
+    /// the case value as the bits of `case_type` and the block of its label
+    pub(crate) cases: Vec<(u64, usize)>,
+    pub(crate) default_block: Option<usize>,
 }
 impl FunctionContext {
     pub(crate) fn new() -> FunctionContext {
         FunctionContext {
             mir_function: MIRFunction::new(),
             pretty_return_type: PrettyType::default_void(),
+            label_blocks: BTreeMap::new(),
+            defined_labels: BTreeSet::new(),
+            used_labels: BTreeMap::new(),
+            address_taken_labels: BTreeSet::new(),
+            indirect_gotos: vec![],
+            jump_targets: vec![],
+            switches: vec![],
+        }
+    }
+
+    pub(crate) fn get_label_block(&mut self, label: &str) -> usize {
+        if let Some(block_id) = self.label_blocks.get(label) {
+            return *block_id;
+        }
+        let block_id = self.mir_function.blocks.len();
+        self.mir_function.blocks.push(MIRBlock::new_wrapped());
+        self.label_blocks.insert(label.to_string(), block_id);
+        block_id
+    }
+
+    /// the block of `label` for a jump to it, the label may only be defined later
+    pub(crate) fn use_label(&mut self, label: &str, span: &Span) -> usize {
+        self.used_labels
+            .entry(label.to_string())
+            .or_insert_with(|| span.clone());
+        self.get_label_block(label)
+    }
+
+    /// (6.8.6.1) every label that is jumped to has to be defined in the function
+    pub(crate) fn check_used_labels(&self) {
+        for (label, span) in &self.used_labels {
+            if !self.defined_labels.contains(label) {
+                span.fail_at_span(&format!("label '{}' used but not defined", label));
+            }
+        }
+    }
+
+    /// unconditionally continue in `to_block` from the current block
+    pub(crate) fn jump_to_block(&mut self, to_block: usize) {
+        let unused_value = self.mir_function.make_intermediate_value_typed(
+            ExtType::Int {
+                is_const: false,
+                is_volatile: false,
+                signed: true,
+                size: 4,
+            }
+            .into_pretty(),
+        );
+        MIRBlock::ins_instr(
+            &self.mir_function.current_block,
+            MIRInstruction::ConstNum(unused_value, 0, MIRType::I32),
+        );
+        self.mir_function.current_block.borrow_mut().branches = Some((
+            unused_value,
+            vec![MIRBranch {
+                is_default: true,
+                value_needed: 0,
+                to_block,
+            }],
+        ));
+    }
+
+    /// jump to `to_block` unless the current block already left, like after a return
+    pub(crate) fn fall_through_to_block(&mut self, to_block: usize) {
+        if !RefCell::borrow(&self.mir_function.current_block).is_exit_block {
+            self.jump_to_block(to_block);
+        }
+    }
+
+    /// jump to `to_block` and go on in a new block, anything up to the next label is unreachable
+    pub(crate) fn leave_to_block(&mut self, to_block: usize) {
+        self.fall_through_to_block(to_block);
+        self.mir_function.current_block.borrow_mut().is_exit_block = true;
+        let unreachable_block = self.make_block();
+        self.mir_function.current_block = self.mir_function.blocks[unreachable_block].clone();
+    }
+
+    /// a new empty block, not reachable until something branches to it
+    pub(crate) fn make_block(&mut self) -> usize {
+        let block_id = self.mir_function.blocks.len();
+        self.mir_function.blocks.push(MIRBlock::new_wrapped());
+        block_id
+    }
+
+    /// continue in `nonzero_block` if `condition` is not zero, in `zero_block` otherwise
+    pub(crate) fn branch_on_value(
+        &mut self,
+        condition: MIRValue,
+        nonzero_block: usize,
+        zero_block: usize,
+    ) {
+        self.mir_function.current_block.borrow_mut().branches = Some((
+            condition,
+            vec![
+                MIRBranch {
+                    is_default: false,
+                    value_needed: 1,
+                    to_block: nonzero_block,
+                },
+                MIRBranch {
+                    is_default: true,
+                    value_needed: 0,
+                    to_block: zero_block,
+                },
+            ],
+        ));
+    }
+
+    /// an unnamed local, for a value that is assigned in more than one block
+    pub(crate) fn make_temporary_local(&mut self, local_type: PrettyType) -> LocalRef {
+        let name = format!(".tmp.{}", self.mir_function.ctx_gen.var_ref_counter);
+        self.mir_function.insert_variable(name, local_type)
+    }
+
+    /*
+    Label addresses are the ids of the label blocks, as there are no block addresses in the backend.
+    So `goto *target` compares target against every label whose address was taken:
+        block:  cond = target == label_0; brnz cond label_0; jump next
+        next:   cond = target == label_1; brnz cond label_1; jump next
+        ...     jump label_n
+    */
+    pub(crate) fn resolve_indirect_gotos(&mut self) {
+        let targets: Vec<usize> = self.address_taken_labels.iter().cloned().collect();
+        for (goto_block, target_value) in self.indirect_gotos.clone() {
+            let Some((last_target, compared_targets)) = targets.split_last() else {
+                panic!(
+                    "goto * used in function '{}', but no label address was taken",
+                    self.mir_function.name
+                );
+            };
+
+            self.mir_function.current_block = goto_block;
+            for target in compared_targets {
+                let target_id = self.mir_function.make_intermediate_value_typed(
+                    ExtType::Pointer {
+                        is_const: false,
+                        is_volatile: false,
+                        to: Box::new(ExtType::Void),
+                    }
+                    .into_pretty(),
+                );
+                MIRBlock::ins_instr(
+                    &self.mir_function.current_block,
+                    MIRInstruction::ConstNum(target_id, *target as i64, MIRType::I64),
+                );
+                let is_target = self.mir_function.make_intermediate_value_typed(
+                    ExtType::Int {
+                        is_const: false,
+                        is_volatile: false,
+                        signed: true,
+                        size: 4,
+                    }
+                    .into_pretty(),
+                );
+                MIRBlock::ins_instr(
+                    &self.mir_function.current_block,
+                    MIRInstruction::Compare(is_target, target_value, target_id, IntCmpKind::Eq),
+                );
+
+                let next_block_id = self.mir_function.blocks.len();
+                let next_block = MIRBlock::new_wrapped();
+                self.mir_function.blocks.push(next_block.clone());
+
+                self.mir_function.current_block.borrow_mut().branches = Some((
+                    is_target,
+                    vec![
+                        MIRBranch {
+                            is_default: false,
+                            value_needed: 1,
+                            to_block: *target,
+                        },
+                        MIRBranch {
+                            is_default: true,
+                            value_needed: 0,
+                            to_block: next_block_id,
+                        },
+                    ],
+                ));
+                self.mir_function.current_block = next_block;
+            }
+            // target has to be the last one left, everything else is undefined behavior
+            self.jump_to_block(*last_target);
         }
     }
 }
@@ -13,6 +13,8 @@ use log::{debug, error, trace};
 use serde::{Deserialize, Serialize};
 use token_types::*;
 
+use crate::standard::CStandard;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CToken {
     pub t_type: CTokenType,
@@ -39,11 +41,13 @@ impl fmt::Display for CToken {
 pub struct OriginalLocation {
     pub file: String,
     pub line: usize,
+    #[serde(rename = "column")]
     pub collumn: usize,
 }
 
 pub struct Lexer {
     current_loc: OriginalLocation,
+    standard: CStandard,
 }
 impl Lexer {
     pub fn new() -> Self {
@@ -53,8 +57,18 @@ impl Lexer {
                 line: 0,
                 collumn: 0,
             },
+            standard: CStandard::default(),
         }
     }
+    pub fn with_standard(mut self, standard: CStandard) -> Self {
+        self.standard = standard;
+        self
+    }
+    /// the file of the tokens, until a line marker of the preprocessor names another
+    pub(crate) fn with_file(mut self, file: &str) -> Self {
+        self.current_loc.file = file.to_string();
+        self
+    }
 
     pub fn string_to_token_arr(&mut self, string_to_lexer: String) -> Vec<CToken> {
         let mut buf = vec![];
@@ -67,6 +81,8 @@ impl Lexer {
                     error!("error while trying to execute line resync directive from preprocessor!: {:?}",&err);
                     panic!();
                 }
+                // the marker gives the number of the next line
+                continue;
             } else {
                 let mut current_token_string = String::new();
 
@@ -157,7 +173,9 @@ impl Lexer {
                                 break;
                             }
                         }
-                        if let Some(keyword) = CKeyword::to_keyword(&current_token_string) {
+                        if let Some(keyword) =
+                            CKeyword::to_keyword_for_standard(&current_token_string, self.standard)
+                        {
                             buf.push(CToken {
                                 t_type: CTokenType::Keyword(keyword),
                                 original: current_token_string.clone(),
@@ -180,9 +198,13 @@ impl Lexer {
                         let mut end_char = '`';
                         let mut point_seperator_reached = false;
                         let mut hex_x_reached = false;
+                        let mut binary_b_reached = false;
                         for character in char_line_iter.by_ref() {
                             // as long as we have digit or nondigit
-                            if (hex_x_reached
+                            if character == '\'' && self.standard.is_c23() {
+                                // digit separator 1'000'000, not part of the value
+                                continue;
+                            } else if (hex_x_reached
                                 && ['a', 'b', 'c', 'd', 'e', 'f']
                                     .contains(&character.to_ascii_lowercase()))
                                 || helper_funcs::is_digit(character)
@@ -194,13 +216,19 @@ impl Lexer {
                                 }
                                 point_seperator_reached = true;
                                 current_token_string.push(character);
+                            } else if character.eq_ignore_ascii_case(&'b')
+                                && current_token_string == "0"
+                                && self.standard.has_binary_literals()
+                            {
+                                current_token_string.push(character);
+                                binary_b_reached = true;
                             } else if character.to_ascii_lowercase() == 'x' {
                                 if hex_x_reached {
                                     panic!("Second Hex 'x' seperator in number")
                                 }
                                 current_token_string.push(character);
                                 hex_x_reached = true;
-                            } else if hex_x_reached
+                            } else if (hex_x_reached || binary_b_reached)
                                 && (character.to_ascii_uppercase() == 'L'
                                     || character.to_ascii_uppercase() == 'U')
                             {
@@ -329,7 +357,8 @@ impl Lexer {
         let get_resync_line_num: usize = split_sync_string[1].parse()?;
         let file_resync = split_sync_string[2];
 
-        self.current_loc.line = get_resync_line_num;
+        // the marker numbers the next line from 1, lines are counted from 0 here
+        self.current_loc.line = get_resync_line_num.saturating_sub(1);
         self.current_loc.collumn = 0;
         self.current_loc.file = file_resync.to_string();
 
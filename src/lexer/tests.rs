@@ -3,6 +3,7 @@ use crate::lexer::CToken;
 use crate::lexer::CTokenType::*;
 use crate::lexer::Lexer;
 use crate::lexer::OriginalLocation;
+use crate::standard::CStandard;
 
 #[cfg(test)]
 use pretty_assertions::assert_eq;
@@ -420,3 +421,57 @@ fn b_plus_a_ext() {
         expected_output
     );
 }
+
+#[test]
+fn test_lexer_c23() {
+    let input =
+        r#"bool b = true; constexpr int x = 1'000'000 + 0b1010u; typeof_unqual(x) *p = nullptr;"#;
+
+    let lex = |standard| {
+        Lexer::new()
+            .with_standard(standard)
+            .string_to_token_arr(input.to_string())
+            .into_iter()
+            .map(|token| (token.t_type, token.original))
+            .collect::<Vec<_>>()
+    };
+
+    let c23 = lex(CStandard::C23);
+    assert_eq!(c23[0], (Keyword(BOOL), "bool".to_string()));
+    assert_eq!(c23[3], (Keyword(TRUE), "true".to_string()));
+    assert_eq!(c23[5], (Keyword(CONSTEXPR), "constexpr".to_string()));
+    // digit separators are not part of the constant
+    assert_eq!(c23[9], (Constant, "1000000".to_string()));
+    assert_eq!(c23[11], (Constant, "0b1010u".to_string()));
+    assert_eq!(
+        c23[13],
+        (Keyword(TYPEOF_UNQUAL), "typeof_unqual".to_string())
+    );
+    assert_eq!(c23[20], (Keyword(NULLPTR), "nullptr".to_string()));
+
+    // C11 knows none of the new keywords
+    let c11 = lex(CStandard::C11);
+    assert_eq!(c11[0], (Identifier, "bool".to_string()));
+    assert_eq!(c11[3], (Identifier, "true".to_string()));
+    assert_eq!(c11[5], (Identifier, "constexpr".to_string()));
+}
+
+#[test]
+fn line_markers_number_the_next_line() {
+    let input = "# 1 \"fix.h\" 1\ntypedef int a;\n# 7 \"main.c\"\nint b;\n\nint c;";
+
+    let lines = Lexer::new()
+        .string_to_token_arr(input.to_string())
+        .into_iter()
+        .filter(|token| token.original != ";")
+        .map(|token| (token.original, token.loc.file, token.loc.line))
+        .collect::<Vec<_>>();
+
+    // lines are counted from 0, a marker counts from 1
+    assert_eq!(
+        lines[0],
+        ("typedef".to_string(), "\"fix.h\"".to_string(), 0)
+    );
+    assert_eq!(lines[3], ("int".to_string(), "\"main.c\"".to_string(), 6));
+    assert_eq!(lines[5], ("int".to_string(), "\"main.c\"".to_string(), 8));
+}
@@ -1,3 +1,5 @@
+use crate::standard::CStandard;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CTokenType {
     /// one of CKeyword
@@ -63,8 +65,45 @@ pub enum CKeyword {
     NORETURN,
     STATIC_ASSERT,
     THREAD_LOCAL,
+    // GNU extensions
+    ATTRIBUTE,
+    ASM,
+    EXTENSION,
+    TYPEOF,
+    // C23
+    TRUE,
+    FALSE,
+    NULLPTR,
+    TYPEOF_UNQUAL,
+    CONSTEXPR,
 }
 impl CKeyword {
+    /// keywords depend on the standard, in C23 `bool` is a keyword and in C11 an identifier
+    pub fn to_keyword_for_standard(string: &str, standard: CStandard) -> Option<CKeyword> {
+        use CKeyword::*;
+
+        if standard.is_c23() {
+            match string {
+                "bool" => return Some(BOOL),
+                "true" => return Some(TRUE),
+                "false" => return Some(FALSE),
+                "nullptr" => return Some(NULLPTR),
+                "typeof" => return Some(TYPEOF),
+                "typeof_unqual" => return Some(TYPEOF_UNQUAL),
+                "constexpr" => return Some(CONSTEXPR),
+                "static_assert" => return Some(STATIC_ASSERT),
+                "alignas" => return Some(ALIGNAS),
+                "alignof" => return Some(ALIGNOF),
+                "thread_local" => return Some(THREAD_LOCAL),
+                _ => {}
+            }
+        } else if standard.is_gnu() && string == "typeof" {
+            return Some(TYPEOF);
+        }
+
+        CKeyword::to_keyword(string)
+    }
+
     pub fn to_keyword(string: &str) -> Option<CKeyword> {
         use CKeyword::*;
 
@@ -113,6 +152,17 @@ impl CKeyword {
             "_NORETURN" => Some(NORETURN),
             "_STATIC_ASSERT" => Some(STATIC_ASSERT),
             "_THREAD_LOCAL" => Some(THREAD_LOCAL),
+            // GNU extensions and alternate spellings
+            "__ATTRIBUTE__" | "__ATTRIBUTE" => Some(ATTRIBUTE),
+            "__ASM__" | "__ASM" => Some(ASM),
+            "__EXTENSION__" => Some(EXTENSION),
+            "__TYPEOF__" | "__TYPEOF" => Some(TYPEOF),
+            "__RESTRICT__" | "__RESTRICT" => Some(RESTRICT),
+            "__INLINE__" | "__INLINE" => Some(INLINE),
+            "__CONST__" | "__CONST" => Some(CONST),
+            "__VOLATILE__" | "__VOLATILE" => Some(VOLATILE),
+            "__SIGNED__" | "__SIGNED" => Some(SIGNED),
+            "__ALIGNOF__" | "__ALIGNOF" => Some(ALIGNOF),
             _ => None,
         }
     }
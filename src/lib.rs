@@ -0,0 +1,34 @@
+//! JankCC a hobby WIP C Compiler
+//!
+//! ```no_run
+//! use jankcc::{CStandard, Compiler};
+//!
+//! let compiler = Compiler::new().with_standard(CStandard::Gnu11);
+//! let mut session = compiler.session_from_file("main.c").unwrap();
+//! match session.object() {
+//!     Some(object) => std::fs::write("main.o", object).unwrap(),
+//!     None => session.diagnostics().iter().for_each(|d| eprintln!("{}", d)),
+//! }
+//! ```
+
+mod cranelift_backend;
+mod environment_builder;
+mod lexer;
+mod linker;
+mod mir;
+mod parser;
+mod preprocessor;
+mod session;
+mod standard;
+
+pub use lexer::{
+    token_types::{CKeyword, CTokenType},
+    CToken, OriginalLocation,
+};
+pub use linker::Linker;
+pub use parser::{
+    partial::{ParseDiagnostic, ParsedNode, PartialParse},
+    CParser,
+};
+pub use session::{Ast, Compiler, Diagnostic, Mir, Phase, PreprocessorKind, Session};
+pub use standard::CStandard;
@@ -0,0 +1,101 @@
+use std::{path::PathBuf, process::Command};
+
+use log::{debug, error};
+
+/*
+Links objects into an executable by handing them to the system C compiler driver,
+it knows where crt1.o, libc and the dynamic loader live.
+The objects from cranelift are not position independent, so it is always `-no-pie`.
+*/
+#[derive(Debug, Clone)]
+pub struct Linker {
+    linker: String,
+    objects: Vec<PathBuf>,
+    libraries: Vec<String>,
+    library_paths: Vec<String>,
+    static_link: bool,
+    output: PathBuf,
+}
+
+impl Default for Linker {
+    fn default() -> Self {
+        Linker {
+            linker: "cc".to_string(),
+            objects: vec![],
+            libraries: vec![],
+            library_paths: vec![],
+            static_link: false,
+            output: PathBuf::from("a.out"),
+        }
+    }
+}
+
+impl Linker {
+    pub fn new() -> Self {
+        Linker::default()
+    }
+    /// the driver that is invoked, `cc` by default
+    pub fn with_linker(mut self, linker: impl Into<String>) -> Self {
+        self.linker = linker.into();
+        self
+    }
+    pub fn with_output(mut self, output: impl Into<PathBuf>) -> Self {
+        self.output = output.into();
+        self
+    }
+    pub fn with_static(mut self, static_link: bool) -> Self {
+        self.static_link = static_link;
+        self
+    }
+    pub fn add_object(mut self, object: impl Into<PathBuf>) -> Self {
+        self.objects.push(object.into());
+        self
+    }
+    /// `-l<library>`
+    pub fn add_library(mut self, library: impl Into<String>) -> Self {
+        self.libraries.push(library.into());
+        self
+    }
+    /// `-L<path>`
+    pub fn add_library_path(mut self, library_path: impl Into<String>) -> Self {
+        self.library_paths.push(library_path.into());
+        self
+    }
+
+    pub fn command(&self) -> Command {
+        let mut command = Command::new(&self.linker);
+        command.args(&self.objects);
+        command.arg("-o").arg(&self.output);
+        command.args(self.library_paths.iter().map(|path| format!("-L{}", path)));
+        // libraries after the objects, so their symbols get resolved
+        command.args(
+            self.libraries
+                .iter()
+                .map(|library| format!("-l{}", library)),
+        );
+        command.arg("-no-pie");
+        if self.static_link {
+            command.arg("-static");
+        }
+        command
+    }
+
+    pub fn link(&self) -> Result<(), String> {
+        let mut command = self.command();
+        debug!("linking: {:?}", command);
+
+        let output = command
+            .output()
+            .map_err(|err| format!("could not run linker '{}': {}", self.linker, err))?;
+        if !output.stderr.is_empty() {
+            error!("{}", String::from_utf8_lossy(&output.stderr));
+        }
+        if !output.status.success() {
+            return Err(format!(
+                "linker '{}' failed: {}",
+                self.linker, output.status
+            ));
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,8 @@
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use std::{
-    fs::{read_to_string, File},
+    fs::{remove_file, File},
     io::Write,
-    path::Path,
-    process::Command,
+    path::{Path, PathBuf},
     time::Instant,
 };
 use structopt::StructOpt;
@@ -21,32 +20,58 @@ struct Opt {
     /// Use Internal preprocessor
     #[structopt(short = "ipp", long = "internal-preprocessor")]
     internal_preprocessor: bool,
-    /// Use gcc preprocessor
+    /// Use gcc preprocessor, the default
     #[structopt(short = "gccpp", long = "gcc-preprocessor")]
     gcc_preprocessor: bool,
-    /// Input file
-    input_file_path: String,
+    /// Input files, .c and preprocessed .i files are compiled, .o and .a files are passed to the linker
+    #[structopt(required = true)]
+    inputs: Vec<String>,
+    /// Place the output into <file>, a.out by default when linking
+    #[structopt(short = "o")]
+    output: Option<String>,
+    /// Compile to an object file, but do not link
+    #[structopt(short = "c")]
+    compile_only: bool,
+    /// Not supported, there is no assembly `as` accepts, use -c
+    #[structopt(short = "S")]
+    assembly_only: bool,
+    /// Preprocess only, to stdout or the -o file
+    #[structopt(short = "E")]
+    preprocess_only: bool,
+    /// Link against lib<library>
+    #[structopt(short = "l", number_of_values = 1)]
+    libraries: Vec<String>,
+    /// Add <dir> to the library search path
+    #[structopt(short = "L", number_of_values = 1)]
+    library_paths: Vec<String>,
+    /// Link statically (-static)
+    #[structopt(long = "static")]
+    static_link: bool,
     #[structopt(short = "f", long = "flush-all")]
     flush_all_intermediate: bool,
+    /// Write the AST including spans as JSON to <input>.ast.json
+    #[structopt(long = "ast-json")]
+    ast_json: bool,
+    /// Write C source regenerated from the AST to <input>.gen.c
+    #[structopt(long = "emit-c")]
+    emit_c: bool,
+    /// Language standard: c11, gnu11, c23 or gnu23, GNU extensions like statement expressions need a gnu mode
+    #[structopt(long = "std", default_value = "c11")]
+    standard: CStandard,
 }
 
-mod cranelift_backend;
-mod environment_builder;
-mod lexer;
-mod mir;
-mod parser;
-mod preprocessor;
+use jankcc::{CStandard, Compiler, Linker, Phase, PreprocessorKind, Session};
 
-use preprocessor::Preprocessor;
-
-use crate::{
-    cranelift_backend::CraneliftBackend, environment_builder::EnvironmentController, lexer::Lexer,
-    parser::CParser,
-};
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Preprocess,
+    Object,
+    Link,
+}
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let timer_start = Instant::now();
-    let opt = Opt::from_args();
+    let opt = Opt::from_iter(gcc_style_args(std::env::args()));
 
     let log_level = if opt.quiet {
         log::LevelFilter::Off
@@ -74,8 +99,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     //     .init()
     //     .unwrap();
 
-    if !opt.quiet {
-        println!(
+    if opt.assembly_only {
+        return Err("-S is not supported, cranelift gives no assembly `as` accepts, use -c".into());
+    }
+
+    let mode = if opt.preprocess_only {
+        Mode::Preprocess
+    } else if opt.compile_only {
+        Mode::Object
+    } else {
+        Mode::Link
+    };
+
+    // the preprocessed source might go to stdout
+    if !opt.quiet && mode != Mode::Preprocess {
+        eprintln!(
             r#"      _             _       ____ ____
     | | __ _ _ __ | | __  / ___/ ___|
  _  | |/ _` | '_ \| |/ / | |  | |
@@ -83,139 +121,219 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
  \___/ \__,_|_| |_|_|\_\  \____\____|
                                      "#
         );
-        println!("by Iquiji --- v0.0.4");
-    }
-
-    let in_file_path = opt.input_file_path;
-
-    let read_in_file = read_to_string(in_file_path.clone())?;
-
-    let preprocessed_file: String;
-
-    let timer_start_preprocessing = Instant::now();
-    info!("Starting Preprocessing of file: {:?}", in_file_path);
-
-    if opt.internal_preprocessor {
-        let mut preprocessor = Preprocessor::new();
-        let preprocessed_file_temp =
-            preprocessor.preprocess_code_string(read_in_file, in_file_path.clone());
-        preprocessed_file = preprocessor.replace_final(preprocessed_file_temp);
-
-        // println!("-------\n{}\n-------", preprocessed_file);
-
-        // let mut file = File::create(Path::new(&in_file_path).with_extension("j.i"))?;
-        // file.write_all(preprocessed_file.as_bytes())?;
-    } else if opt.gcc_preprocessor {
-        let output_from_gcc = Command::new("gcc")
-            .args(&[
-                "-E",
-                "-std=c11",
-                "-undef",
-                "-fno-builtin",
-                "-include",
-                "./header_fixes/fix.h",
-                &in_file_path,
-            ])
-            .output()?;
-        if !output_from_gcc.stderr.is_empty() {
-            error!("{}", String::from_utf8(output_from_gcc.stderr,)?)
-        }
-        preprocessed_file = String::from_utf8(output_from_gcc.stdout)?;
+        eprintln!("by Iquiji --- v0.0.4");
+    }
+
+    // the internal preprocessor does not get through the system headers yet
+    let preprocessor = if opt.internal_preprocessor && !opt.gcc_preprocessor {
+        PreprocessorKind::Internal
     } else {
-        error!("require either Internal or GCC preprocessor! see -h for help!");
-        return Ok(());
+        PreprocessorKind::Gcc
+    };
+    let compiler = Compiler::new()
+        .with_standard(opt.standard)
+        .with_preprocessor(preprocessor);
+
+    let is_link_input = |input: &&String| input.ends_with(".o") || input.ends_with(".a");
+    let c_input_count = opt.inputs.iter().filter(|i| !is_link_input(i)).count();
+    if mode != Mode::Link && opt.output.is_some() && c_input_count > 1 {
+        return Err("cannot specify -o with -c or -E with multiple files".into());
     }
-    if opt.flush_all_intermediate {
-        let timer_start_flushing_preprocessed_file = Instant::now();
-        info!(
-            "Starting Flushing of Preprocessed File: {:?}",
-            Path::new(&in_file_path).with_extension("i")
-        );
-        let mut preprocessed_file_handle =
-            File::create(Path::new(&in_file_path).with_extension("i")).unwrap();
-
-        // Write preprocessed_file_handle
-        preprocessed_file_handle
-            .write_all(preprocessed_file.as_bytes())
-            .unwrap();
-        let timer_end_flushing_preprocessed_file = timer_start_flushing_preprocessed_file.elapsed();
-        info!(
-            "Flushing Flushing of Preprocessed File took: {:?}",
-            timer_end_flushing_preprocessed_file
+    if mode != Mode::Link && c_input_count < opt.inputs.len() {
+        let link_inputs: Vec<&String> = opt.inputs.iter().filter(is_link_input).collect();
+        warn!(
+            "linker input unused because linking not done: {:?}",
+            link_inputs
         );
     }
 
-    let timer_end_preprocessing = timer_start_preprocessing.elapsed();
-    info!("Preprocessing of file took: {:?}", timer_end_preprocessing);
+    let mut linker = Linker::new().with_static(opt.static_link);
+    let mut temporary_objects = vec![];
+    let mut result = Ok(());
 
-    let timer_start_lexing = Instant::now();
-    info!("Starting Lexing of file: {:?}", in_file_path);
+    // objects are linked in command line order, like gcc does
+    for (index, input) in opt.inputs.iter().enumerate() {
+        if is_link_input(&input) {
+            linker = linker.add_object(input);
+            continue;
+        }
+        match compile_input(&compiler, &opt, mode, input, index) {
+            Ok(Some(object_path)) => {
+                linker = linker.add_object(&object_path);
+                temporary_objects.push(object_path);
+            }
+            Ok(None) => {}
+            Err(err) => {
+                result = Err(err);
+                break;
+            }
+        }
+    }
 
-    // call lexer
-    let mut lexer = Lexer::new();
-    let token_arr = lexer.string_to_token_arr(preprocessed_file);
+    if result.is_ok() && mode == Mode::Link {
+        let timer_start_linking = Instant::now();
+        for library_path in &opt.library_paths {
+            linker = linker.add_library_path(library_path);
+        }
+        for library in &opt.libraries {
+            linker = linker.add_library(library);
+        }
+        linker = linker.with_output(opt.output.as_deref().unwrap_or("a.out"));
 
-    for token in &token_arr {
-        debug!("{}", token);
+        result = linker.link().map_err(|err| err.into());
+        info!("Linking took: {:?}", timer_start_linking.elapsed());
     }
 
-    let timer_end_lexing = timer_start_lexing.elapsed();
-    info!("Lexing of file took: {:?}", timer_end_lexing);
+    for object_path in temporary_objects {
+        let _ = remove_file(object_path);
+    }
 
-    let timer_start_parsing = Instant::now();
-    info!("Starting Parsing of file: {:?}", in_file_path);
+    let timer_end = timer_start.elapsed();
+    info!("Compiling took {:?} in Total", timer_end);
 
-    // do da stuff
-    let mut parser = CParser::new(token_arr);
-    let parsed = parser.parse();
+    result
+}
 
-    let timer_end_parsing = timer_start_parsing.elapsed();
-    info!("Parsing of file took: {:?}", timer_end_parsing);
+/// runs one .c or .i file through the phases `mode` needs, returns the temporary object when linking
+fn compile_input(
+    compiler: &Compiler,
+    opt: &Opt,
+    mode: Mode,
+    in_file_path: &str,
+    input_index: usize,
+) -> Result<Option<PathBuf>, Box<dyn std::error::Error>> {
+    // a .i file is preprocessed already, like gcc does it is not preprocessed again
+    let mut session = if in_file_path.ends_with(".i") {
+        compiler
+            .clone()
+            .with_preprocessor(PreprocessorKind::None)
+            .session_from_file(in_file_path)?
+    } else {
+        compiler.session_from_file(in_file_path)?
+    };
 
+    run_phase(&mut session, Phase::Preprocessing)?;
+    let preprocessed_file = session.preprocessed().unwrap_or_default().to_string();
     if opt.flush_all_intermediate {
-        let timer_start_flushing_ast = Instant::now();
-        info!(
-            "Starting Flushing of Ast: {:?}",
-            Path::new(&in_file_path).with_extension("ast")
-        );
-        let ast_file_handle = File::create(Path::new(&in_file_path).with_extension("ast")).unwrap();
+        flush(in_file_path, "i", preprocessed_file.as_bytes())?;
+    }
+    if mode == Mode::Preprocess {
+        match &opt.output {
+            Some(output) => File::create(output)?.write_all(preprocessed_file.as_bytes())?,
+            None => std::io::stdout().write_all(preprocessed_file.as_bytes())?,
+        }
+        return Ok(None);
+    }
+
+    run_phase(&mut session, Phase::Lexing)?;
 
-        // Write ast_file_handle
-        serde_yaml::to_writer(ast_file_handle, &parsed).unwrap();
-        let timer_end_flushing_ast = timer_start_flushing_ast.elapsed();
-        info!("Flushing of Ast took: {:?}", timer_end_flushing_ast);
+    run_phase(&mut session, Phase::Parsing)?;
+    let ast = session.ast().cloned().unwrap();
+    if opt.flush_all_intermediate {
+        flush(in_file_path, "ast", ast.to_yaml()?.as_bytes())?;
+    }
+    if opt.flush_all_intermediate || opt.ast_json {
+        flush(in_file_path, "ast.json", ast.to_json()?.as_bytes())?;
+    }
+    if opt.emit_c {
+        flush(in_file_path, "gen.c", ast.to_c().as_bytes())?;
     }
 
-    let timer_start_environment = Instant::now();
-    info!("Starting Environment of file: {:?}", in_file_path);
+    run_phase(&mut session, Phase::Lowering)?;
+    if let Some(mir) = session.mir() {
+        debug!("{}", mir.to_yaml()?);
+    }
 
-    let mut controller = EnvironmentController::new();
-    controller.build(parsed);
+    run_phase(&mut session, Phase::Codegen)?;
+    let object_file_data = session.object().unwrap_or_default().to_vec();
 
-    let timer_end_environment = timer_start_environment.elapsed();
-    info!("Building of Environment took: {:?}", timer_end_environment);
+    if mode == Mode::Object {
+        File::create(output_path(opt, in_file_path, "o"))?.write_all(&object_file_data)?;
+        return Ok(None);
+    }
 
-    let timer_start_codegen = Instant::now();
-    info!("Starting Codegen of file: {:?}", in_file_path);
+    let stem = Path::new(in_file_path).file_stem().unwrap_or_default();
+    // the index keeps a/x.c and b/x.c apart
+    let object_path = std::env::temp_dir().join(format!(
+        "jankcc-{}-{}-{}.o",
+        std::process::id(),
+        input_index,
+        stem.to_string_lossy()
+    ));
+    File::create(&object_path)?.write_all(&object_file_data)?;
+    Ok(Some(object_path))
+}
 
-    let mir_programm = controller.get_mir();
-    debug!("{}", serde_yaml::to_string(&mir_programm).unwrap());
+fn run_phase(session: &mut Session, phase: Phase) -> Result<(), Box<dyn std::error::Error>> {
+    let timer_start_phase = Instant::now();
 
-    let mut cranelift_backend = CraneliftBackend::default();
-    cranelift_backend.compile(mir_programm);
+    if !session.run_until(phase) {
+        return Err(report_failure(session, phase));
+    }
 
-    let object_file_data = cranelift_backend.finish();
-    let mut object_file_handle =
-        File::create(Path::new(&in_file_path).with_extension("o")).unwrap();
+    info!(
+        "{:?} of file took: {:?}",
+        phase,
+        timer_start_phase.elapsed()
+    );
+    Ok(())
+}
 
-    // Write object_file
-    object_file_handle.write_all(&object_file_data).unwrap();
+/// writes an intermediate result next to the input file
+fn flush(
+    in_file_path: &str,
+    extension: &str,
+    data: &[u8],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let timer_start_flushing = Instant::now();
+    let out_file_path = Path::new(in_file_path).with_extension(extension);
+    info!("Starting Flushing of: {:?}", out_file_path);
+
+    File::create(&out_file_path)?.write_all(data)?;
+
+    info!(
+        "Flushing of {:?} took: {:?}",
+        out_file_path,
+        timer_start_flushing.elapsed()
+    );
+    Ok(())
+}
 
-    let timer_end_codegen = timer_start_codegen.elapsed();
-    info!("Building of Codegen took: {:?}", timer_end_codegen);
+fn report_failure(session: &Session, phase: Phase) -> Box<dyn std::error::Error> {
+    for diagnostic in session.diagnostics() {
+        error!("{}", diagnostic);
+    }
+    format!("{:?} of {:?} failed", phase, session.file_path()).into()
+}
 
-    let timer_end = timer_start.elapsed();
-    info!("Compiling took {:?} in Total", timer_end);
+/// like gcc, -o or the input name with `extension` in the current directory
+fn output_path(opt: &Opt, in_file_path: &str, extension: &str) -> PathBuf {
+    match &opt.output {
+        Some(output) => PathBuf::from(output),
+        None => PathBuf::from(
+            Path::new(in_file_path)
+                .with_extension(extension)
+                .file_name()
+                .unwrap_or_default(),
+        ),
+    }
+}
 
-    Ok(())
+/// rewrites gcc spellings structopt can not parse, flags without effect here are dropped
+fn gcc_style_args(args: impl Iterator<Item = String>) -> Vec<String> {
+    args.filter_map(|arg| {
+        if arg.starts_with("-std=") || arg == "-static" {
+            Some(format!("-{}", arg))
+        } else if arg.starts_with("-O")
+            || arg.starts_with("-W")
+            || (arg.starts_with("-f") && arg.len() > 2)
+            || ["-pipe", "-no-pie", "-fno-pie"].contains(&arg.as_str())
+        {
+            debug!("ignoring gcc option: {}", arg);
+            None
+        } else {
+            Some(arg)
+        }
+    })
+    .collect()
 }
@@ -5,6 +5,8 @@ use super::*;
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) struct MIRBlock {
     pub(crate) is_exit_block: bool,
+    /// the non-default branches compare the value against `value_needed` instead of testing it for not zero
+    pub(crate) is_switch: bool,
     pub(crate) instr: Vec<MIRInstruction>,
     pub(crate) branches: Option<(MIRValue, Vec<MIRBranch>)>,
 }
@@ -23,6 +25,7 @@ impl MIRBlock {
             instr: vec![],
             branches: None,
             is_exit_block: false,
+            is_switch: false,
         }
     }
     pub(crate) fn new_wrapped() -> Rc<RefCell<Self>> {
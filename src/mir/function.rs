@@ -25,7 +25,7 @@ impl MIRFunction {
         MIRFunction {
             name: String::new(),
             signature: MIRSignature {
-                return_type: MIRType::I64,
+                return_type: Some(MIRType::I64),
                 args: vec![],
                 overloadable: false,
             },
@@ -90,14 +90,28 @@ impl MIRFunction {
         self.value_type_map_pretty.insert(value, p_type);
         value
     }
+    /// stands for the result of a void expression, there is nothing to read from it
+    pub(crate) fn make_void_value(&mut self) -> MIRValue {
+        let value = self.ctx_gen.make_intermediate_value();
+        self.value_type_map.insert(value, MIRType::I32);
+        self.value_type_map_pretty
+            .insert(value, ExtType::Void.into_pretty());
+        value
+    }
     pub(crate) fn insert_constant(&mut self, constant: MIRConstant) -> DataConstantRef {
         let c_ref = self.ctx_gen.make_data_const_ref();
         self.data_const_id_map.insert(c_ref, constant);
         //.expect("internal data const ref error");
         c_ref
     }
+    /// a new local, named `var` or `var.<ref>` if a local of another scope took `var` already
     pub(crate) fn insert_variable(&mut self, var: String, var_type: PrettyType) -> LocalRef {
         let var_ref = self.ctx_gen.make_var_ref();
+        let var = if self.var_name_id_map.contains_right(&var) {
+            format!("{}.{}", var, var_ref.opaque_ref)
+        } else {
+            var
+        };
         self.var_name_id_map
             .insert_no_overwrite(var_ref, var)
             .expect("internal var ref error");
@@ -3,6 +3,8 @@ use super::*;
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) enum MIRLocatorValue {
     LocalVar(LocalRef, PrettyType),
+    /// an object behind an address, like `*ptr` or `s.member`
+    Memory(MIRValue, PrettyType),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord, Copy)]
@@ -13,6 +13,8 @@ pub(crate) enum MIRInstruction {
     ReadLocal(MIRValue, LocalRef),
     AssignLocal(LocalRef, MIRValue),
     GetAddrOfLocal(MIRValue, LocalRef),
+    /// address of a global object or function by its symbol
+    GetAddrOfGlobal(MIRValue, String),
     /// return value, input value, wanted type
     Deref(MIRValue, MIRValue, MIRType),
     /// store location, input value, provided type
@@ -20,9 +22,16 @@ pub(crate) enum MIRInstruction {
     IntMath(MIRValue, MIRValue, MIRValue, IntMathKind),
     Compare(MIRValue, MIRValue, MIRValue, IntCmpKind),
     Call(MIRValue, String, Vec<MIRValue>, MIRSignature),
-    Return(MIRValue),
+    /// no value in a function returning void
+    Return(Option<MIRValue>),
     /// ouput,input,target_type
     IntConvert(MIRValue, MIRValue, MIRType),
+    /// address of a new stack temporary, big enough for the type
+    StackAlloc(MIRValue, MIRType),
+    /// destination address, source address, copied type
+    CopyMemory(MIRValue, MIRValue, MIRType),
+    /// destination address, zeroed type
+    ZeroMemory(MIRValue, MIRType),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -32,6 +41,14 @@ pub(crate) enum IntMathKind {
     Mul,
     Div,
     Mod,
+    And,
+    Or,
+    Xor,
+    Shl,
+    /// arithmetic for signed, logical for unsigned results
+    Shr,
+    /// bitwise complement of the left operand, the right one is not used
+    Not,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -42,4 +59,24 @@ pub(crate) enum IntCmpKind {
     LT,
     GET,
     LET,
+    // unsigned integers and pointers
+    UnsignedGT,
+    UnsignedLT,
+    UnsignedGET,
+    UnsignedLET,
+}
+
+impl IntCmpKind {
+    pub(crate) fn with_unsigned(self, unsigned: bool) -> IntCmpKind {
+        if !unsigned {
+            return self;
+        }
+        match self {
+            IntCmpKind::GT => IntCmpKind::UnsignedGT,
+            IntCmpKind::LT => IntCmpKind::UnsignedLT,
+            IntCmpKind::GET => IntCmpKind::UnsignedGET,
+            IntCmpKind::LET => IntCmpKind::UnsignedLET,
+            other => other,
+        }
+    }
 }
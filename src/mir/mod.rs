@@ -60,7 +60,8 @@ impl MIRConstant {
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) struct MIRSignature {
-    pub(crate) return_type: MIRType,
+    /// None for a function returning void
+    pub(crate) return_type: Option<MIRType>,
     pub(crate) args: Vec<MIRType>,
     pub(crate) overloadable: bool,
 }
@@ -74,8 +75,10 @@ impl MIRSignature {
         } = &p_type.inner_type
         {
             Self {
-                return_type: MIRType::extract_from_pretty_type(&PrettyType {
-                    inner_type: *returns.clone(),
+                return_type: (**returns != ExtType::Void).then(|| {
+                    MIRType::extract_from_pretty_type(&PrettyType {
+                        inner_type: *returns.clone(),
+                    })
                 }),
                 args: parameters
                     .iter()
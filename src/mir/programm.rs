@@ -17,8 +17,33 @@ impl MIRProgramm {
     }
 }
 
+/// an object with static storage duration
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) struct GlobalEntity {
     pub(crate) name: String,
+    /// visible to other translation units, `static` objects are not
     pub(crate) extern_linkage: bool,
+    /// None if the object is only declared here and defined elsewhere
+    pub(crate) definition: Option<GlobalDefinition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct GlobalDefinition {
+    pub(crate) size: u64,
+    pub(crate) alignment: u64,
+    /// const objects go to .rodata
+    pub(crate) writable: bool,
+    /// the initial bytes, None zero initializes the object in .bss
+    pub(crate) bytes: Option<Vec<u8>>,
+    pub(crate) relocations: Vec<GlobalRelocation>,
+}
+
+/// the address of another symbol stored in the initial bytes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct GlobalRelocation {
+    pub(crate) offset: u64,
+    pub(crate) symbol: String,
+    pub(crate) addend: i64,
+    /// functions have to be declared with their signature
+    pub(crate) function: Option<MIRSignature>,
 }
@@ -12,6 +12,43 @@ pub(crate) enum MIRType {
     I32,
     U64,
     I64,
+    /// structs and unions, only ever in memory: locals and behind addresses,
+    /// a value of this type is the address of a private copy
+    Aggregate {
+        size: u32,
+        alignment: u32,
+        eightbytes: [EightbyteClass; 2],
+    },
+}
+
+/*
+System V x86-64 ABI 3.2.3 Parameter Passing:
+an aggregate of up to 16 bytes is split into eightbytes, each is classified by the members in it:
+    INTEGER: any integer or pointer in it
+    SSE: only float and double
+    NO_CLASS: only padding
+bigger aggregates are MEMORY, copied onto the stack for arguments, returned through a hidden pointer
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
+pub(crate) enum EightbyteClass {
+    NoClass,
+    Integer,
+    Sse,
+    Memory,
+}
+
+impl EightbyteClass {
+    /// classes of two members sharing an eightbyte
+    pub(crate) fn merge(self, other: EightbyteClass) -> EightbyteClass {
+        use EightbyteClass::*;
+        match (self, other) {
+            (left, right) if left == right => left,
+            (NoClass, other) | (other, NoClass) => other,
+            (Memory, _) | (_, Memory) => Memory,
+            (Integer, _) | (_, Integer) => Integer,
+            _ => Sse,
+        }
+    }
 }
 
 impl MIRType {
@@ -49,12 +86,12 @@ impl MIRType {
                 is_volatile: _,
                 size: _,
             } => todo!(),
-            ExtType::Array {
-                is_const: _,
-                is_volatile: _,
-                arr_size: _,
-                to: _,
-            } => todo!(),
+            // arrays are only ever in memory as well, in expressions they decay to pointers
+            ExtType::Array { .. } => MIRType::Aggregate {
+                size: p_type.size_of() as u32,
+                alignment: p_type.align_of() as u32,
+                eightbytes: [EightbyteClass::Memory; 2],
+            },
             ExtType::Pointer {
                 is_const: _,
                 is_volatile: _,
@@ -65,24 +102,25 @@ impl MIRType {
                 returns: _,
                 parameters: _,
             } => todo!(),
-            ExtType::Struct {
-                is_const: _,
-                is_volatile: _,
-                tag: _,
-                alignment: _,
-                members: _,
-            } => todo!(),
-            ExtType::Union {
-                is_const: _,
-                is_volatile: _,
-                tag: _,
-                members: _,
-            } => todo!(),
+            ExtType::Struct { .. } | ExtType::Union { .. } => MIRType::Aggregate {
+                size: p_type.size_of() as u32,
+                alignment: p_type.align_of() as u32,
+                eightbytes: p_type.inner_type.classify_eightbytes(),
+            },
         }
     }
 }
 
 impl MIRType {
+    pub(crate) fn is_aggregate(&self) -> bool {
+        matches!(self, MIRType::Aggregate { .. })
+    }
+    pub(crate) fn is_signed(&self) -> bool {
+        matches!(
+            self,
+            MIRType::I8 | MIRType::I16 | MIRType::I32 | MIRType::I64
+        )
+    }
     pub(crate) fn get_size_in_bytes(&self) -> u32 {
         match self {
             MIRType::U8 => 1,
@@ -93,6 +131,7 @@ impl MIRType {
             MIRType::I32 => 4,
             MIRType::U64 => 8,
             MIRType::I64 => 8,
+            MIRType::Aggregate { size, .. } => *size,
         }
     }
 }
@@ -0,0 +1,62 @@
+/*
+JSON output of the AST including the spans of every node.
+
+Schema (version 1):
+{
+    "format": "jankcc-ast",
+    "version": 1,
+    "translation_unit": [ <Spanned<ExternalDeclaration>>, ... ]
+}
+every Spanned<T> is written as:
+{
+    "span": {
+        "start": { "file": "...", "line": 0, "column": 0 },
+        "end": { "file": "...", "line": 0, "column": 0 }
+    },
+    "node": <T>
+}
+enums use the externally tagged serde representation, same as the yaml dump.
+*/
+
+use serde::{Deserialize, Serialize};
+
+use super::{parse_nodes::TranslationUnit, span_serde::WithSpans};
+
+pub(crate) const AST_JSON_FORMAT: &str = "jankcc-ast";
+pub(crate) const AST_JSON_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct AstJsonDocument {
+    format: String,
+    version: u32,
+    translation_unit: TranslationUnit,
+}
+
+pub(crate) fn translation_unit_to_json(
+    translation_unit: &TranslationUnit,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let document = AstJsonDocument {
+        format: AST_JSON_FORMAT.to_string(),
+        version: AST_JSON_VERSION,
+        translation_unit: translation_unit.clone(),
+    };
+    Ok(serde_json::to_string_pretty(&WithSpans(&document))?)
+}
+
+#[allow(dead_code)]
+pub(crate) fn translation_unit_from_json(
+    json: &str,
+) -> Result<TranslationUnit, Box<dyn std::error::Error>> {
+    let WithSpans(document): WithSpans<AstJsonDocument> = serde_json::from_str(json)?;
+    if document.format != AST_JSON_FORMAT {
+        return Err(format!("unknown ast format: '{}'", document.format).into());
+    }
+    if document.version != AST_JSON_VERSION {
+        return Err(format!(
+            "unsupported ast json version: {} (expected {})",
+            document.version, AST_JSON_VERSION
+        )
+        .into());
+    }
+    Ok(document.translation_unit)
+}
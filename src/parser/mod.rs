@@ -1,6 +1,14 @@
+pub(crate) mod ast_json;
 pub(crate) mod parse_nodes;
+pub(crate) mod partial;
+pub(crate) mod printer;
 pub(crate) mod span;
+pub(crate) mod span_serde;
 pub(crate) mod types;
+#[allow(dead_code)]
+pub(crate) mod visitor;
+#[allow(dead_code)]
+pub(crate) mod visitor_mut;
 
 #[cfg(test)]
 mod tests;
@@ -10,6 +18,9 @@ use std::collections::HashSet;
 use crate::lexer::token_types::CKeyword;
 use crate::lexer::OriginalLocation;
 use crate::lexer::{token_types::CTokenType, CToken};
+use crate::standard::CStandard;
+
+use self::partial::raise_parse_error;
 
 // use self::parse_nodes::declarations::{Declaration, InitDeclaratorList, StorageClassSpecifier};
 use self::parse_nodes::{Identifier, TranslationUnit};
@@ -19,6 +30,7 @@ pub struct CParser {
     tokens: Vec<CToken>,
     idx: usize,
     typedef_table: Vec<HashSet<String>>,
+    standard: CStandard,
 }
 
 impl CParser {
@@ -27,8 +39,14 @@ impl CParser {
             tokens: program_tokens,
             idx: 0,
             typedef_table: vec![HashSet::new()],
+            standard: CStandard::default(),
         }
     }
+    pub fn with_standard(mut self, standard: CStandard) -> Self {
+        self.standard = standard;
+        self
+    }
+    #[allow(dead_code)]
     pub(crate) fn parse(&mut self) -> TranslationUnit {
         let mut translation_unit = vec![];
 
@@ -166,6 +184,9 @@ impl CParser {
             .clone()
     }
     pub(crate) fn advance_idx(&mut self) -> CToken {
+        if self.idx >= self.tokens.len() {
+            self.error_unexpected(self.current_token(), "more input");
+        }
         let temp = self.tokens[self.idx].clone();
         self.idx += 1;
         temp
@@ -176,9 +197,9 @@ impl CParser {
 */
 impl CParser {
     pub(crate) fn error_unexpected(&mut self, found: CToken, expected: &str) {
-        panic!(
+        raise_parse_error(format!(
             "Line {}-{}: Expected: {}, Instead found Token: {:?}",
             found.loc.line, found.loc.collumn, expected, found
-        );
+        ));
     }
 }
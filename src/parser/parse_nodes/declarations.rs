@@ -4,7 +4,7 @@ use log::{error, warn};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    lexer::token_types::CKeyword,
+    lexer::{token_types::CKeyword, CToken},
     parser::{
         span::Spanned,
         types::{
@@ -15,18 +15,21 @@ use crate::{
     },
 };
 
-use super::{expressions::*, Identifier, StringLiteral};
+use super::{expressions::*, gnu_extensions::GnuAttribute, Identifier, StringLiteral};
 
 use crate::lexer::token_types::CTokenType;
 
 /*
 (6.7.10) static_assert-declaration:
     _Static_assert ( constant-expression , string-literal ) ;
+    (C23) static_assert ( constant-expression ) ;
 */
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) struct StaticAssertDeclaration {
     pub(crate) expression: ConstantExpression,
-    pub(crate) string_literal: StringLiteral,
+    /// only optional in C23
+    #[serde(default)]
+    pub(crate) string_literal: Option<StringLiteral>,
 }
 
 impl CParser {
@@ -36,14 +39,21 @@ impl CParser {
         self.expect_type(CTokenType::Keyword(CKeyword::STATIC_ASSERT));
         self.expect_type_and_string(CTokenType::Punctuator, "(");
 
+        let expression = self.parse_constant_expr();
+        let string_literal = if self.standard.is_c23()
+            && self.current_token().t_type == CTokenType::Punctuator
+            && self.current_token().original == ")"
+        {
+            None
+        } else {
+            self.expect_type_and_string(CTokenType::Punctuator, ",");
+            Some(StringLiteral {
+                value: self.expect_type(CTokenType::StringLiteral).original,
+            })
+        };
         let assert_decl = StaticAssertDeclaration {
-            expression: self.parse_constant_expr(),
-            string_literal: StringLiteral {
-                value: {
-                    self.expect_type_and_string(CTokenType::Punctuator, ",");
-                    self.expect_type(CTokenType::StringLiteral).original
-                },
-            },
+            expression,
+            string_literal,
         };
 
         self.expect_type_and_string(CTokenType::Punctuator, ")");
@@ -84,6 +94,8 @@ pub(crate) struct DeclarationSpecifiers {
     pub(crate) specifiers: CTypeSpecifier,
     pub(crate) function: CFunctionSpecifier,
     pub(crate) alignment: Option<Spanned<CAlignmentSpecifier>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) attributes: Vec<Spanned<GnuAttribute>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -94,6 +106,9 @@ pub(crate) struct CStorageClass {
     pub(crate) thread_local_c: bool,
     pub(crate) auto_c: bool,
     pub(crate) register_c: bool,
+    /// C23, the object is const and its initializer a constant expression
+    #[serde(default)]
+    pub(crate) constexpr_c: bool,
 }
 
 impl Add for CStorageClass {
@@ -107,6 +122,7 @@ impl Add for CStorageClass {
             thread_local_c: self.thread_local_c || rhs.thread_local_c,
             auto_c: self.auto_c || rhs.auto_c,
             register_c: self.register_c || rhs.register_c,
+            constexpr_c: self.constexpr_c || rhs.constexpr_c,
         }
     }
 }
@@ -208,6 +224,7 @@ impl CParser {
             CKeyword::THREAD_LOCAL,
             CKeyword::AUTO,
             CKeyword::REGISTER,
+            CKeyword::CONSTEXPR,
         ];
         let matcher = |key: &CKeyword, quals: &mut CStorageClass| match key {
             CKeyword::TYPEDEF => {
@@ -228,6 +245,9 @@ impl CParser {
             CKeyword::REGISTER => {
                 quals.register_c = true;
             }
+            CKeyword::CONSTEXPR => {
+                quals.constexpr_c = true;
+            }
             _ => unreachable!(),
         };
 
@@ -240,6 +260,7 @@ impl CParser {
             thread_local_c: false,
             auto_c: false,
             register_c: false,
+            constexpr_c: false,
         };
 
         // get beginning storage_class
@@ -274,6 +295,7 @@ impl CParser {
                 thread_local_c: false,
                 auto_c: false,
                 register_c: false,
+                constexpr_c: false,
             },
             qualifiers: CTypeQualifiers {
                 const_q: false,
@@ -287,23 +309,79 @@ impl CParser {
                 no_return: false,
             },
             alignment: None,
+            attributes: vec![],
         };
-        decl_spec.storage = decl_spec.storage + *self.parse_storage_class().inner;
-        decl_spec.qualifiers = decl_spec.qualifiers + *self.parse_type_qualifiers().inner;
-        decl_spec.function = decl_spec.function + *self.parse_c_function_specifier().inner;
-        decl_spec.alignment = self.parse_maybe_alignment_specifier();
+        self.skip_gnu_extension_keyword();
+        let without_specifiers = decl_spec.clone();
+        decl_spec = self.parse_non_type_declaration_specifiers(decl_spec);
+
+        // C89 implicit int: `register n;` or `f(a, b) { ... }`, anything else needs a type
+        if !self.check_is_start_of_type_name(&self.current_token()) {
+            if decl_spec == without_specifiers && !self.is_start_of_kr_function_definition() {
+                self.error_unexpected(self.current_token(), "a type specifier");
+            }
+            warn!(
+                "{}: type specifier missing, defaults to int",
+                self.current_token().loc
+            );
+            return decl_spec;
+        }
 
         let temp = *self.parse_specifier_qualifier_list().inner;
         decl_spec.qualifiers = decl_spec.qualifiers + temp.qualifiers;
         decl_spec.specifiers = temp.specifier;
 
+        self.parse_non_type_declaration_specifiers(decl_spec)
+    }
+
+    /// `f(a, b)` followed by the body or the declarations of the parameters
+    fn is_start_of_kr_function_definition(&mut self) -> bool {
+        let token_at = |offset: usize| {
+            self.tokens
+                .get(self.idx + offset)
+                .cloned()
+                .unwrap_or_else(|| self.current_token())
+        };
+        let is_punctuator = |token: &CToken, original: &str| {
+            token.t_type == CTokenType::Punctuator && token.original == original
+        };
+
+        if token_at(0).t_type != CTokenType::Identifier || !is_punctuator(&token_at(1), "(") {
+            return false;
+        }
+        let mut offset = 2;
+        if token_at(offset).t_type == CTokenType::Identifier {
+            offset += 1;
+            while is_punctuator(&token_at(offset), ",")
+                && token_at(offset + 1).t_type == CTokenType::Identifier
+            {
+                offset += 2;
+            }
+        }
+        if !is_punctuator(&token_at(offset), ")") {
+            return false;
+        }
+        let after = token_at(offset + 1);
+        is_punctuator(&after, "{")
+            || matches!(after.t_type, CTokenType::Keyword(CKeyword::REGISTER))
+            || self.check_is_start_of_type_name(&after)
+    }
+
+    /// everything but the type specifier, in any order
+    fn parse_non_type_declaration_specifiers(
+        &mut self,
+        mut decl_spec: DeclarationSpecifiers,
+    ) -> DeclarationSpecifiers {
         loop {
             let old_decl_spec = decl_spec.clone();
 
             decl_spec.storage = decl_spec.storage + *self.parse_storage_class().inner;
             decl_spec.qualifiers = decl_spec.qualifiers + *self.parse_type_qualifiers().inner;
             decl_spec.function = decl_spec.function + *self.parse_c_function_specifier().inner;
-            decl_spec.alignment = self.parse_maybe_alignment_specifier();
+            if let Some(alignment) = self.parse_maybe_alignment_specifier() {
+                decl_spec.alignment = Some(alignment);
+            }
+            decl_spec.attributes.extend(self.parse_gnu_attributes());
             if old_decl_spec == decl_spec {
                 break;
             }
@@ -463,7 +541,7 @@ impl CParser {
                     };
                 }
                 self.expect_type_and_string(CTokenType::Punctuator, ")");
-            } else if self.current_token().original == "[" {
+            } else if self.current_token().original == "[" && !self.is_start_of_attribute() {
                 // array
                 self.advance_idx();
 
@@ -555,21 +633,46 @@ impl CParser {
                 }
                 result.parameter_list.push(self.parse_parameter_decl());
             } else {
-                warn!(
-                    "unknown Punctuator in parse_parameter_type_list: {:?}",
-                    self.current_token()
-                );
-                panic!()
+                self.error_unexpected(self.current_token(), "',' or ')' after a parameter");
             }
         }
-        warn!("{:#?}", self.typedef_table);
-        error!("current: {:?}", self.current_token());
-        error!("result.parameter_list: {:?}", result.parameter_list);
-        println!("{}", serde_yaml::to_string(&result.parameter_list).unwrap());
+        self.error_unexpected(self.current_token(), "',' or ')' after a parameter");
         unreachable!()
     }
 }
 
+/*
+(6.7.6) identifier-list:
+    identifier
+    identifier-list , identifier
+*/
+impl CParser {
+    pub(crate) fn parse_identifier_list(&mut self) -> Vec<Spanned<Identifier>> {
+        let mut identifier_list = vec![];
+
+        loop {
+            let token = self.expect_type(CTokenType::Identifier);
+            identifier_list.push(Spanned::new(
+                Identifier {
+                    identifier: token.original,
+                },
+                token.loc.clone(),
+                token.loc,
+            ));
+
+            if self.current_token().t_type == CTokenType::Punctuator
+                && self.current_token().original == ","
+            {
+                self.advance_idx();
+            } else {
+                break;
+            }
+        }
+
+        identifier_list
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) enum ParameterDeclaration {
     // declaration-specifiers declarator
@@ -677,6 +780,8 @@ impl CParser {
                 CStructOrUnionTypeType::Union
             };
 
+        let mut attributes = self.parse_gnu_attributes();
+
         // opt ident
         let ident = if self.current_token().t_type == CTokenType::Identifier {
             Some(Identifier {
@@ -704,17 +809,40 @@ impl CParser {
                     ));
                 } else {
                     // initial
+                    self.skip_gnu_extension_keyword();
+                    let mut leading_attributes = self.parse_gnu_attributes();
+                    let alignment = self.parse_maybe_alignment_specifier();
                     let spec_qual_list = self.parse_specifier_qualifier_list();
+                    leading_attributes.extend(self.parse_gnu_attributes());
                     if !(self.current_token().t_type == CTokenType::Punctuator
                         && self.current_token().original == ";")
                     {
                         // struct dedclarator
+                        let mut delcarator_list = self.parse_struct_declarator_list();
+                        // attributes in front of the member belong to every declarator
+                        for struct_declarator in delcarator_list.iter_mut() {
+                            let declarator = match &mut *struct_declarator.inner {
+                                CStructDeclarator::Declarator(declarator) => declarator,
+                                CStructDeclarator::BitField {
+                                    declarator: Some(declarator),
+                                    ..
+                                } => declarator,
+                                CStructDeclarator::BitField {
+                                    declarator: None, ..
+                                } => continue,
+                            };
+                            let mut attributes = leading_attributes.clone();
+                            attributes.append(&mut declarator.attributes);
+                            declarator.attributes = attributes;
+                        }
                         struct_declaration_list.push(CSructDeclaration::StructDeclaration {
+                            alignment,
                             specifier_qualifier: spec_qual_list,
-                            delcarator_list: self.parse_struct_declarator_list(),
+                            delcarator_list,
                         });
                     } else {
                         struct_declaration_list.push(CSructDeclaration::StructDeclaration {
+                            alignment,
                             specifier_qualifier: spec_qual_list,
                             delcarator_list: vec![],
                         });
@@ -724,12 +852,14 @@ impl CParser {
             }
 
             self.expect_type_and_string(CTokenType::Punctuator, "}");
+            attributes.extend(self.parse_gnu_attributes());
 
             Spanned::new(
                 CStructOrUnionType {
                     struct_type: struct_or_union_type,
                     ident,
                     declarations: struct_declaration_list,
+                    attributes,
                 },
                 start,
                 self.prev_token().loc,
@@ -746,6 +876,7 @@ impl CParser {
                     struct_type: struct_or_union_type,
                     ident,
                     declarations: vec![],
+                    attributes,
                 },
                 start,
                 self.prev_token().loc,
@@ -779,10 +910,13 @@ impl CParser {
                     && self.current_token().original == ":"
                 {
                     self.advance_idx();
+                    let expr = self.parse_constant_expr();
+                    let mut decl = decl;
+                    decl.attributes.extend(self.parse_gnu_attributes());
                     result.push(Spanned::new(
                         CStructDeclarator::BitField {
                             declarator: Some(decl),
-                            expr: self.parse_constant_expr(),
+                            expr,
                         },
                         start,
                         self.prev_token().loc,
@@ -893,6 +1027,11 @@ pub(crate) struct Declarator {
     // Derived Declararator + Identifier Base
     pub(crate) base: Identifier,
     pub(crate) derive: DerivedDeclarator,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) attributes: Vec<Spanned<GnuAttribute>>,
+    /// GNU `__asm__("name")`, the symbol name to link against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) asm_label: Option<StringLiteral>,
 }
 
 impl CParser {
@@ -903,6 +1042,8 @@ impl CParser {
         let mut ident = Identifier {
             identifier: String::new(),
         };
+        let mut attributes = vec![];
+        let mut asm_label = None;
 
         // discern between ( abstract_declarator ) and ( parameter_type_list )
         // following '(' is '(' or '[' or '*'
@@ -922,6 +1063,8 @@ impl CParser {
 
             new_head = temp.derive.clone();
             ident = temp.base.clone();
+            attributes = temp.attributes.clone();
+            asm_label = temp.asm_label.clone();
 
             self.expect_type_and_string(CTokenType::Punctuator, ")");
         }
@@ -935,11 +1078,16 @@ impl CParser {
 
         while self.current_token().t_type == CTokenType::Punctuator {
             if self.current_token().original == "(" {
-                // todo!: identifier_list
-
-                // parameter type list
                 self.advance_idx();
-                if !(self.current_token().t_type == CTokenType::Punctuator
+                if self.current_token().t_type == CTokenType::Identifier
+                    && !self.is_typedef(&self.current_token().original)
+                {
+                    // K&R identifier list, the types follow as declarations
+                    base = DerivedDeclarator::FunctionIdentified {
+                        identifier_list: self.parse_identifier_list(),
+                        to: Box::new(base),
+                    };
+                } else if !(self.current_token().t_type == CTokenType::Punctuator
                     && self.current_token().original == ")")
                 {
                     //there is a parameter type list
@@ -961,7 +1109,7 @@ impl CParser {
                     };
                 }
                 self.expect_type_and_string(CTokenType::Punctuator, ")");
-            } else if self.current_token().original == "[" {
+            } else if self.current_token().original == "[" && !self.is_start_of_attribute() {
                 // array
                 self.advance_idx();
 
@@ -1019,10 +1167,23 @@ impl CParser {
             }
         }
 
+        // GNU: asm label and attributes after the declarator, in any order
+        loop {
+            if let Some(label) = self.parse_maybe_asm_label() {
+                asm_label = Some(label);
+            } else if self.is_start_of_attribute() {
+                attributes.extend(self.parse_gnu_attributes());
+            } else {
+                break;
+            }
+        }
+
         Spanned::new(
             Declarator {
                 base: ident,
                 derive: traverse_derived_replace_base(new_head, base),
+                attributes,
+                asm_label,
             },
             start,
             self.prev_token().loc,
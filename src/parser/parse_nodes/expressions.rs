@@ -1,9 +1,12 @@
 use crate::{
     lexer::token_types::{CKeyword, CTokenType, CTokenType::*},
-    parser::{span::Spanned, types::CTypeName, CParser},
+    parser::{partial::raise_parse_error, span::Spanned, types::CTypeName, CParser},
 };
 
-use super::{declarations::Initializer, Constant, Identifier, NumberLike, StringLiteral};
+use super::{
+    declarations::Initializer, statements::CompoundItem, Constant, Identifier, NumberLike,
+    StringLiteral,
+};
 
 use log::info;
 use serde::{Deserialize, Serialize};
@@ -29,8 +32,8 @@ use serde::{Deserialize, Serialize};
 */
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) struct GenericSelection {
-    assignment_expression: Spanned<CExpression>,
-    generic_assoc_list: Box<Spanned<GenericAssociationList>>,
+    pub(crate) assignment_expression: Spanned<CExpression>,
+    pub(crate) generic_assoc_list: Box<Spanned<GenericAssociationList>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -379,6 +382,10 @@ pub(crate) enum CExpression {
     StringLiteral(StringLiteral),
     Paranthesised(Spanned<Self>),
     GenericSelection(Box<Spanned<GenericSelection>>),
+    /// GNU: ({ block-item-list }), the value is the one of the last expression statement
+    StatementExpression(Vec<CompoundItem>),
+    /// GNU: && identifier, the address of a label
+    LabelAddress(Identifier),
 }
 
 impl super::super::CParser {
@@ -535,7 +542,7 @@ impl super::super::CParser {
             }
 
             result = Spanned::new(
-                CExpression::LogicalAnd(result_vec),
+                CExpression::LogicalOr(result_vec),
                 start,
                 self.prev_token().loc,
             );
@@ -852,17 +859,27 @@ impl super::super::CParser {
         let start = self.current_token().loc;
         let current_token = self.current_token();
 
+        if current_token.t_type == CTokenType::Keyword(CKeyword::EXTENSION) {
+            // GNU: __extension__ cast-expression, the keyword has no meaning here
+            self.advance_idx();
+            return self.parse_expr_cast();
+        }
+
+        if current_token.t_type == CTokenType::Punctuator && current_token.original == "&&" {
+            return self.parse_label_address();
+        }
+
         if current_token.original == "++" || current_token.original == "--" {
             // ++ unary-expression
-            self.advance_idx();
+            let increment_type = if self.advance_idx().original == "++" {
+                IncrementType::Increment
+            } else {
+                IncrementType::Decrement
+            };
             return Spanned::new(
                 CExpression::PrefixIncrement {
                     value: self.parse_expr_unary(),
-                    increment_type: if self.prev_token().original == "++" {
-                        IncrementType::Increment
-                    } else {
-                        IncrementType::Decrement
-                    },
+                    increment_type,
                 },
                 start,
                 self.prev_token().loc,
@@ -880,7 +897,7 @@ impl super::super::CParser {
             };
             return Spanned::new(
                 CExpression::Unary {
-                    value: self.parse_expr_unary(),
+                    value: self.parse_expr_cast(),
                     unary_op: op,
                 },
                 start,
@@ -891,6 +908,7 @@ impl super::super::CParser {
             self.advance_idx();
             if self.current_token().t_type == CTokenType::Punctuator
                 && self.current_token().original == "("
+                && self.check_is_start_of_type_name(&self.next_token())
             {
                 // type name sizeof
                 self.expect_type_and_string(CTokenType::Punctuator, "(");
@@ -1069,7 +1087,20 @@ impl super::super::CParser {
                 // only GENERIC for generic Selection
                 if keyword == CKeyword::GENERIC {
                     // generic selection
-                    panic!("Generic Selection Expression Still unsuported!")
+                    raise_parse_error("Generic Selection Expression Still unsuported!".to_string())
+                } else if [CKeyword::TRUE, CKeyword::FALSE, CKeyword::NULLPTR].contains(&keyword) {
+                    // C23 predefined constants
+                    self.advance_idx();
+                    let constant = match keyword {
+                        CKeyword::TRUE => Constant::Bool(true),
+                        CKeyword::FALSE => Constant::Bool(false),
+                        _ => Constant::Nullptr,
+                    };
+                    Spanned::new(
+                        CExpression::Constant(constant),
+                        current_token.loc.clone(),
+                        current_token.loc,
+                    )
                 } else {
                     // panic with unexpected keyword
                     self.error_unexpected(
@@ -1112,7 +1143,12 @@ impl super::super::CParser {
             }
             crate::lexer::token_types::CTokenType::Punctuator => {
                 // only '(' allowed for paranthesised expr
-                if current_token.original == "(" {
+                if current_token.original == "("
+                    && self.next_token().t_type == CTokenType::Punctuator
+                    && self.next_token().original == "{"
+                {
+                    self.parse_statement_expression()
+                } else if current_token.original == "(" {
                     let start = self.advance_idx().loc;
                     let expr = self.parse_expression();
                     let end = self.expect_type_and_string(CTokenType::Punctuator, ")").loc;
@@ -1127,7 +1163,10 @@ impl super::super::CParser {
                     unreachable!()
                 }
             }
-            Eof => unreachable!(),
+            Eof => {
+                self.error_unexpected(current_token, "an expression");
+                unreachable!()
+            }
         }
     }
 }
@@ -0,0 +1,396 @@
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lexer::{
+        token_types::{CKeyword, CTokenType},
+        CToken, OriginalLocation,
+    },
+    parser::{
+        span::Spanned,
+        types::{CTypeOf, CTypeOfArgument},
+        CParser,
+    },
+};
+
+use super::{
+    expressions::{CExpression, ConstantExpression},
+    statements::Statement,
+    Identifier, StringLiteral,
+};
+
+/*
+GNU extensions accepted by the parser, as found in preprocessed glibc headers:
+    __attribute__ (( attribute-list ))
+    __asm__ ( string-literal ) after a declarator, renaming the symbol
+    __extension__ in front of declarations and expressions, ignored
+    __typeof__ ( expression ) / __typeof__ ( type-name )
+    __restrict, __inline, __const, ... as alternate keyword spellings (lexer)
+only with -std=gnu11 or -std=gnu23:
+    ( compound-statement ) as a statement expression
+    && identifier for the address of a label
+    goto * expression ;
+
+(GNU) attribute-list:
+    attribute opt
+    attribute-list , attribute opt
+(GNU) attribute:
+    attribute-name
+    attribute-name ( expression-list opt )
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum GnuAttribute {
+    NoReturn,
+    Aligned(Option<ConstantExpression>),
+    Packed,
+    Unused,
+    Visibility(StringLiteral),
+    Section(StringLiteral),
+    /// kept as written, but not interpreted
+    Other {
+        name: Identifier,
+        arguments: Vec<Spanned<CExpression>>,
+    },
+}
+
+/*
+C23 attributes are parsed into GnuAttribute as well:
+(6.7.13.2) attribute-specifier:
+    [ [ attribute-list ] ]
+(6.7.13.2) attribute:
+    attribute-token attribute-argument-clause opt
+(6.7.13.2) attribute-token:
+    standard-attribute
+    attribute-prefix :: identifier
+*/
+impl CParser {
+    /// `__attribute__` or, with -std=c23, `[[`
+    pub(crate) fn is_start_of_attribute(&self) -> bool {
+        self.current_token().t_type == CTokenType::Keyword(CKeyword::ATTRIBUTE)
+            || (self.standard.is_c23()
+                && self.current_token().t_type == CTokenType::Punctuator
+                && self.current_token().original == "["
+                && self.next_token().t_type == CTokenType::Punctuator
+                && self.next_token().original == "[")
+    }
+
+    /// parses any number of consecutive `__attribute__((...))` and `[[...]]`
+    pub(crate) fn parse_gnu_attributes(&mut self) -> Vec<Spanned<GnuAttribute>> {
+        let mut attributes = vec![];
+
+        while self.is_start_of_attribute() {
+            if self.current_token().t_type == CTokenType::Punctuator {
+                attributes.extend(self.parse_standard_attribute_specifier());
+                continue;
+            }
+            self.advance_idx();
+            self.expect_type_and_string(CTokenType::Punctuator, "(");
+            self.expect_type_and_string(CTokenType::Punctuator, "(");
+
+            loop {
+                if self.current_token().t_type == CTokenType::Punctuator {
+                    match self.current_token().original.as_str() {
+                        // empty attribute
+                        "," => {
+                            self.advance_idx();
+                            continue;
+                        }
+                        ")" => break,
+                        _ => {}
+                    }
+                }
+
+                attributes.push(self.parse_gnu_attribute());
+
+                if self.current_token().t_type == CTokenType::Punctuator
+                    && self.current_token().original == ","
+                {
+                    self.advance_idx();
+                } else {
+                    break;
+                }
+            }
+
+            self.expect_type_and_string(CTokenType::Punctuator, ")");
+            self.expect_type_and_string(CTokenType::Punctuator, ")");
+        }
+
+        attributes
+    }
+
+    fn parse_gnu_attribute(&mut self) -> Spanned<GnuAttribute> {
+        let start = self.current_token().loc;
+
+        // attribute names may be keywords as well, e.g. const
+        let name_token = self.current_token();
+        if !matches!(
+            name_token.t_type,
+            CTokenType::Identifier | CTokenType::Keyword(_)
+        ) {
+            self.error_unexpected(name_token, "expected attribute name in __attribute__");
+            unreachable!()
+        }
+        self.advance_idx();
+
+        // __aligned__ and aligned are the same attribute
+        let name = name_token.original.as_str();
+        let name = if name.len() > 4 && name.starts_with("__") && name.ends_with("__") {
+            &name[2..name.len() - 2]
+        } else {
+            name
+        };
+
+        let has_arguments = self.current_token().t_type == CTokenType::Punctuator
+            && self.current_token().original == "(";
+
+        let attribute = match (name, has_arguments) {
+            ("noreturn" | "_Noreturn", false) => GnuAttribute::NoReturn,
+            ("packed", false) => GnuAttribute::Packed,
+            ("unused", false) => GnuAttribute::Unused,
+            ("aligned", false) => GnuAttribute::Aligned(None),
+            ("aligned", true) => {
+                self.advance_idx();
+                let alignment = self.parse_constant_expr();
+                self.expect_type_and_string(CTokenType::Punctuator, ")");
+                GnuAttribute::Aligned(Some(alignment))
+            }
+            ("visibility", true) => {
+                self.advance_idx();
+                let visibility = self.parse_concatenated_string_literal();
+                self.expect_type_and_string(CTokenType::Punctuator, ")");
+                GnuAttribute::Visibility(visibility)
+            }
+            ("section", true) => {
+                self.advance_idx();
+                let section = self.parse_concatenated_string_literal();
+                self.expect_type_and_string(CTokenType::Punctuator, ")");
+                GnuAttribute::Section(section)
+            }
+            (_, _) => {
+                let mut arguments = vec![];
+                if has_arguments {
+                    self.advance_idx();
+                    while !(self.current_token().t_type == CTokenType::Punctuator
+                        && self.current_token().original == ")")
+                    {
+                        arguments.push(self.parse_expr_assignment());
+                        if self.current_token().t_type == CTokenType::Punctuator
+                            && self.current_token().original == ","
+                        {
+                            self.advance_idx();
+                        }
+                    }
+                    self.expect_type_and_string(CTokenType::Punctuator, ")");
+                }
+                debug!("uninterpreted attribute: {}", name);
+                GnuAttribute::Other {
+                    name: Identifier {
+                        identifier: name.to_string(),
+                    },
+                    arguments,
+                }
+            }
+        };
+
+        Spanned::new(attribute, start, self.prev_token().loc)
+    }
+
+    /// [[ attribute-list ]], attributes of other vendors than gnu are dropped
+    fn parse_standard_attribute_specifier(&mut self) -> Vec<Spanned<GnuAttribute>> {
+        let mut attributes = vec![];
+
+        self.expect_type_and_string(CTokenType::Punctuator, "[");
+        self.expect_type_and_string(CTokenType::Punctuator, "[");
+        while !(self.current_token().t_type == CTokenType::Punctuator
+            && self.current_token().original == "]")
+        {
+            if self.current_token().t_type == CTokenType::Punctuator
+                && self.current_token().original == ","
+            {
+                self.advance_idx();
+                continue;
+            }
+
+            let is_prefixed = self.next_token().original == ":"
+                && self
+                    .tokens
+                    .get(self.idx + 2)
+                    .is_some_and(|token| token.original == ":");
+            if is_prefixed {
+                let prefix = self.advance_idx().original;
+                self.advance_idx();
+                self.advance_idx();
+                if prefix == "gnu" || prefix == "__gnu__" {
+                    attributes.push(self.parse_gnu_attribute());
+                } else {
+                    let name = self.advance_idx().original;
+                    self.skip_balanced_parentheses();
+                    debug!("ignored attribute: {}::{}", prefix, name);
+                }
+            } else if ["maybe_unused", "__maybe_unused__"]
+                .contains(&self.current_token().original.as_str())
+            {
+                let token = self.advance_idx();
+                attributes.push(Spanned::new(
+                    GnuAttribute::Unused,
+                    token.loc.clone(),
+                    token.loc,
+                ));
+            } else {
+                attributes.push(self.parse_gnu_attribute());
+            }
+        }
+        self.expect_type_and_string(CTokenType::Punctuator, "]");
+        self.expect_type_and_string(CTokenType::Punctuator, "]");
+
+        attributes
+    }
+
+    /// skips ( balanced-token-sequence ) if there is one
+    fn skip_balanced_parentheses(&mut self) {
+        if !(self.current_token().t_type == CTokenType::Punctuator
+            && self.current_token().original == "(")
+        {
+            return;
+        }
+        let mut depth = 0;
+        loop {
+            let token = self.advance_idx();
+            match (&token.t_type, token.original.as_str()) {
+                (CTokenType::Punctuator, "(") => depth += 1,
+                (CTokenType::Punctuator, ")") => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                (CTokenType::Eof, _) => {
+                    self.error_unexpected(token, "closing ) of attribute arguments");
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// `__asm__ ("symbol")` after a declarator, the symbol name used for linking
+    pub(crate) fn parse_maybe_asm_label(&mut self) -> Option<StringLiteral> {
+        if self.current_token().t_type == CTokenType::Keyword(CKeyword::ASM) {
+            self.advance_idx();
+            self.expect_type_and_string(CTokenType::Punctuator, "(");
+            let label = self.parse_concatenated_string_literal();
+            self.expect_type_and_string(CTokenType::Punctuator, ")");
+            Some(label)
+        } else {
+            None
+        }
+    }
+
+    /// adjacent string literals are one literal: "" "__isoc99_fscanf"
+    fn parse_concatenated_string_literal(&mut self) -> StringLiteral {
+        let mut value = self.expect_type(CTokenType::StringLiteral).original;
+        while self.current_token().t_type == CTokenType::StringLiteral {
+            value.push_str(&self.advance_idx().original);
+        }
+        StringLiteral { value }
+    }
+
+    /// `__extension__` only silences pedantic warnings in gcc, so it is dropped
+    pub(crate) fn skip_gnu_extension_keyword(&mut self) {
+        while self.current_token().t_type == CTokenType::Keyword(CKeyword::EXTENSION) {
+            self.advance_idx();
+        }
+    }
+
+    /*
+    (GNU) typeof-specifier:
+        __typeof__ ( expression )
+        __typeof__ ( type-name )
+    C23 adds the typeof and typeof_unqual spellings
+    */
+    pub(crate) fn parse_typeof_specifier(&mut self) -> Spanned<CTypeOf> {
+        let start = self.current_token().loc;
+
+        let unqualified = self.advance_idx().t_type == CTokenType::Keyword(CKeyword::TYPEOF_UNQUAL);
+        self.expect_type_and_string(CTokenType::Punctuator, "(");
+
+        let argument = if self.check_is_start_of_type_name(&self.current_token()) {
+            CTypeOfArgument::TypeName(self.parse_type_name())
+        } else {
+            CTypeOfArgument::Expression(self.parse_expression())
+        };
+
+        self.expect_type_and_string(CTokenType::Punctuator, ")");
+
+        Spanned::new(
+            CTypeOf {
+                argument,
+                unqualified,
+            },
+            start,
+            self.prev_token().loc,
+        )
+    }
+
+    fn expect_gnu_standard(&mut self, token: CToken, extension: &str) {
+        if !self.standard.is_gnu() {
+            self.error_unexpected(
+                token,
+                &format!(
+                    "{} are a GNU extension, enable them with -std=gnu11 or -std=gnu23",
+                    extension
+                ),
+            );
+        }
+    }
+
+    /// ({ block-item-list })
+    pub(crate) fn parse_statement_expression(&mut self) -> Spanned<CExpression> {
+        let start = self.current_token().loc;
+        self.expect_gnu_standard(self.current_token(), "statement expressions");
+
+        self.expect_type_and_string(CTokenType::Punctuator, "(");
+        let items = match *self.parse_statement().inner {
+            Statement::Compound(items) => items,
+            _ => unreachable!(),
+        };
+        self.expect_type_and_string(CTokenType::Punctuator, ")");
+
+        Spanned::new(
+            CExpression::StatementExpression(items),
+            start,
+            self.prev_token().loc,
+        )
+    }
+
+    /// && identifier
+    pub(crate) fn parse_label_address(&mut self) -> Spanned<CExpression> {
+        let start = self.current_token().loc;
+        self.expect_gnu_standard(self.current_token(), "label addresses");
+
+        self.expect_type_and_string(CTokenType::Punctuator, "&&");
+        let label = Identifier {
+            identifier: self.expect_type(CTokenType::Identifier).original,
+        };
+
+        Spanned::new(
+            CExpression::LabelAddress(label),
+            start,
+            self.prev_token().loc,
+        )
+    }
+
+    /// goto * expression ; with `goto` already consumed
+    pub(crate) fn parse_goto_indirect(&mut self, start: OriginalLocation) -> Spanned<Statement> {
+        self.expect_gnu_standard(self.prev_token(), "computed gotos");
+
+        self.expect_type_and_string(CTokenType::Punctuator, "*");
+        let target = self.parse_expression();
+        self.expect_type_and_string(CTokenType::Punctuator, ";");
+
+        Spanned::new(
+            Statement::GotoIndirect(target),
+            start,
+            self.prev_token().loc,
+        )
+    }
+}
@@ -1,7 +1,7 @@
 use log::debug;
 use serde::{Deserialize, Serialize};
 
-use crate::lexer::token_types::CTokenType;
+use crate::lexer::token_types::{CKeyword, CTokenType};
 
 use self::{
     declarations::{Declaration, DeclarationSpecifiers, Declarator, DerivedDeclarator},
@@ -12,6 +12,7 @@ use super::{span::Spanned, CParser};
 
 pub mod declarations;
 pub mod expressions;
+pub mod gnu_extensions;
 pub mod statements;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -24,6 +25,23 @@ pub(crate) struct Identifier {
 pub(crate) struct NumberLike {
     pub(crate) from: String,
 }
+impl NumberLike {
+    /// value of an integer constant: decimal, 0x hex, 0b binary or 0 octal with any u/l suffix
+    pub(crate) fn to_integer(&self) -> Option<i128> {
+        let trimmed = self.from.trim_end_matches(['u', 'U', 'l', 'L']);
+        let lowercase = trimmed.to_ascii_lowercase();
+
+        if let Some(hex) = lowercase.strip_prefix("0x") {
+            i128::from_str_radix(hex, 16).ok()
+        } else if let Some(binary) = lowercase.strip_prefix("0b") {
+            i128::from_str_radix(binary, 2).ok()
+        } else if lowercase.len() > 1 && lowercase.starts_with('0') {
+            i128::from_str_radix(&lowercase[1..], 8).ok()
+        } else {
+            lowercase.parse::<i128>().ok()
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) struct StringLiteral {
@@ -33,6 +51,10 @@ pub(crate) struct StringLiteral {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) enum Constant {
     Number(NumberLike),
+    /// C23: true and false
+    Bool(bool),
+    /// C23: nullptr
+    Nullptr,
 }
 
 /*
@@ -67,6 +89,13 @@ impl CParser {
             self.current_token().loc
         );
         let before_differ_idx = self.idx;
+        if self.current_token().t_type == CTokenType::Keyword(CKeyword::STATIC_ASSERT) {
+            return Spanned::new(
+                ExternalDeclaration::Declaration(self.parse_declaration()),
+                start,
+                self.prev_token().loc,
+            );
+        }
         // common point decl_specifier
         self.parse_declaration_specifiers();
         // warn!("{:?}",);
@@ -1,3 +1,5 @@
+use log::debug;
+
 use crate::lexer::token_types::CKeyword;
 use crate::lexer::token_types::CTokenType;
 use crate::lexer::CToken;
@@ -60,6 +62,8 @@ pub(crate) enum Statement {
         body: Spanned<Statement>,
     },
     Goto(Identifier),
+    /// GNU: goto * expression ;
+    GotoIndirect(Spanned<CExpression>),
     Continue,
     Break,
     Return(Option<Spanned<CExpression>>),
@@ -81,6 +85,12 @@ impl CParser {
         // selection -> if ( -> switch (
         // iteration -> while ( -> do -> for
         // jump -> goto -> continue -> break -> return
+        if self.is_start_of_attribute() {
+            // attributes of statements have no meaning for code generation
+            let attributes = self.parse_gnu_attributes();
+            debug!("ignored statement attributes: {:?}", attributes);
+            return self.parse_statement();
+        }
         match self.current_token().t_type {
             CTokenType::Keyword(keyword) => {
                 // case,default -> labeled
@@ -252,6 +262,11 @@ impl CParser {
                     }
                 } else if CKeyword::GOTO == keyword {
                     self.advance_idx();
+                    if self.current_token().t_type == CTokenType::Punctuator
+                        && self.current_token().original == "*"
+                    {
+                        return self.parse_goto_indirect(start);
+                    }
                     let ident = Identifier {
                         identifier: self.expect_type(CTokenType::Identifier).original,
                     };
@@ -394,10 +409,27 @@ impl CParser {
 
 impl CParser {
     pub(crate) fn is_start_of_declaration(&mut self, token: CToken) -> bool {
+        // attributes prefix declarations as well as statements, e.g. [[fallthrough]];
+        if self.is_start_of_attribute() {
+            let before_idx = self.idx;
+            self.parse_gnu_attributes();
+            let result = self.is_start_of_declaration(self.current_token());
+            self.idx = before_idx;
+            return result;
+        }
         match token.t_type {
+            // __extension__ prefixes declarations as well as expressions
+            CTokenType::Keyword(CKeyword::EXTENSION) => {
+                let before_idx = self.idx;
+                self.skip_gnu_extension_keyword();
+                let result = self.is_start_of_declaration(self.current_token());
+                self.idx = before_idx;
+                result
+            }
             CTokenType::Keyword(keyword) => {
                 use CKeyword::*;
                 [TYPEDEF, EXTERN, STATIC, THREAD_LOCAL, AUTO, REGISTER].contains(&keyword)
+                    || [CONSTEXPR].contains(&keyword)
                     || [
                         VOID, CHAR, SHORT, INT, LONG, DOUBLE, SIGNED, UNSIGNED, BOOL, COMPLEX,
                     ]
@@ -407,6 +439,7 @@ impl CParser {
                     || [INLINE, NORETURN].contains(&keyword)
                     || [ALIGNAS].contains(&keyword)
                     || [STATIC_ASSERT].contains(&keyword)
+                    || [ATTRIBUTE, TYPEOF, TYPEOF_UNQUAL].contains(&keyword)
             }
             CTokenType::Identifier => self.is_typedef(&token.original),
             CTokenType::Constant => false,
@@ -0,0 +1,350 @@
+use std::{
+    any::Any,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+};
+
+use crate::{
+    lexer::{token_types::CTokenType, CToken, OriginalLocation},
+    session::Ast,
+};
+
+use super::{
+    parse_nodes::{declarations::Declaration, expressions::CExpression, statements::Statement},
+    printer::CPrinter,
+    span::{Span, SpanError, Spanned},
+    CParser,
+};
+
+/*
+Entry points for tooling, that has to cope with code that is still being typed.
+
+Parse errors unwind with a ParseError payload through resume_unwind, which skips the panic hook,
+here they are caught and turned into diagnostics, so nothing panics for the caller:
+- single declarations, statements and expressions give the node if it parsed,
+  after an error the longest start of the construct that parses once its brackets are closed:
+      `while (x) { x = x -`  gives  `while (x) { x = x; }`
+- translation units give every external declaration that parsed, after an error
+  the parser resyncs at the next `;` or `}` on the top level,
+  a declaration cut short by the end of the input is recovered like a single construct
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDiagnostic {
+    pub message: String,
+    pub location: OriginalLocation,
+    /// the input ended before the construct was complete
+    pub incomplete: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialParse<T> {
+    pub node: Option<T>,
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+/// A declaration, statement or expression parsed on its own
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedNode {
+    pub(crate) node: SingleConstruct,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SingleConstruct {
+    Declaration(Spanned<Declaration>),
+    Statement(Spanned<Statement>),
+    Expression(Spanned<CExpression>),
+}
+
+impl ParsedNode {
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        match &self.node {
+            SingleConstruct::Declaration(declaration) => serde_yaml::to_string(declaration),
+            SingleConstruct::Statement(statement) => serde_yaml::to_string(statement),
+            SingleConstruct::Expression(expression) => serde_yaml::to_string(expression),
+        }
+    }
+    /// C source regenerated from the node
+    pub fn to_c(&self) -> String {
+        let mut printer = CPrinter::new();
+        match &self.node {
+            SingleConstruct::Declaration(declaration) => {
+                printer.print_declaration(&declaration.inner)
+            }
+            SingleConstruct::Statement(statement) => printer.print_statement(&statement.inner),
+            SingleConstruct::Expression(expression) => {
+                return printer.expression_to_string(&expression.inner, 0)
+            }
+        }
+        printer.finish()
+    }
+    pub fn start(&self) -> &OriginalLocation {
+        &self.span().start
+    }
+    pub fn end(&self) -> &OriginalLocation {
+        &self.span().end
+    }
+    fn span(&self) -> &Span {
+        match &self.node {
+            SingleConstruct::Declaration(declaration) => &declaration.span,
+            SingleConstruct::Statement(statement) => &statement.span,
+            SingleConstruct::Expression(expression) => &expression.span,
+        }
+    }
+}
+
+/// what a parse error unwinds with, it is no panic, so no panic hook reports it
+pub(crate) struct ParseError {
+    pub(crate) message: String,
+}
+
+/// stops parsing with `message`, the caller of the parser gets it as diagnostic
+pub(crate) fn raise_parse_error(message: String) -> ! {
+    resume_unwind(Box::new(ParseError { message }))
+}
+
+/// the tokens removed at most from the end of a construct to recover it
+const RECOVERY_CUTS: usize = 32;
+/// the closing suffixes tried at most for every cut, deeply nested blocks give many
+const RECOVERY_SUFFIXES: usize = 32;
+
+impl CParser {
+    pub fn from_token_slice(tokens: &[CToken]) -> Self {
+        CParser::new(tokens.to_vec())
+    }
+
+    pub fn parse_partial_translation_unit(&mut self) -> PartialParse<Ast> {
+        let mut translation_unit = vec![];
+        let mut diagnostics = vec![];
+
+        while self.current_token().t_type != CTokenType::Eof {
+            let start_idx = self.idx;
+            match self.catch_parse_error(CParser::parse_external_declaration) {
+                Ok(external_declaration) => translation_unit.push(external_declaration),
+                Err(diagnostic) => {
+                    if diagnostic.incomplete {
+                        translation_unit.extend(
+                            self.recover_prefix(start_idx, CParser::parse_external_declaration),
+                        );
+                    }
+                    diagnostics.push(diagnostic);
+                    self.skip_to_next_external_declaration(start_idx);
+                }
+            }
+        }
+
+        PartialParse {
+            node: Some(Ast { translation_unit }),
+            diagnostics,
+        }
+    }
+
+    pub fn parse_partial_declaration(&mut self) -> PartialParse<ParsedNode> {
+        self.parse_partial_single(|parser| {
+            SingleConstruct::Declaration(CParser::parse_declaration(parser))
+        })
+    }
+
+    pub fn parse_partial_statement(&mut self) -> PartialParse<ParsedNode> {
+        self.parse_partial_single(|parser| {
+            SingleConstruct::Statement(CParser::parse_statement(parser))
+        })
+    }
+
+    pub fn parse_partial_expression(&mut self) -> PartialParse<ParsedNode> {
+        self.parse_partial_single(|parser| {
+            SingleConstruct::Expression(CParser::parse_expression(parser))
+        })
+    }
+
+    fn parse_partial_single(
+        &mut self,
+        parse: fn(&mut CParser) -> SingleConstruct,
+    ) -> PartialParse<ParsedNode> {
+        let start_idx = self.idx;
+        let (node, diagnostic) = match self.catch_parse_error(parse) {
+            Ok(node) if self.current_token().t_type == CTokenType::Eof => (Some(node), None),
+            Ok(node) => {
+                let diagnostic = ParseDiagnostic {
+                    message: format!(
+                        "unexpected tokens after the end: '{}'",
+                        self.current_token().original
+                    ),
+                    location: self.current_token().loc,
+                    incomplete: false,
+                };
+                (Some(node), Some(diagnostic))
+            }
+            Err(diagnostic) => (self.recover_prefix(start_idx, parse), Some(diagnostic)),
+        };
+        PartialParse {
+            node: node.map(|node| ParsedNode { node }),
+            diagnostics: diagnostic.into_iter().collect(),
+        }
+    }
+
+    /// runs `parse`, a parse error becomes a diagnostic at the token the parser stopped at
+    fn catch_parse_error<T>(&mut self, parse: fn(&mut CParser) -> T) -> Result<T, ParseDiagnostic> {
+        let typedef_scopes = self.typedef_table.len();
+
+        catch_quietly(|| parse(self)).map_err(|caught| {
+            // scopes opened by the failed construct are never closed
+            self.typedef_table.truncate(typedef_scopes);
+            self.idx = self.idx.min(self.tokens.len());
+
+            let stopped_at = self.current_token();
+            ParseDiagnostic {
+                message: caught.message,
+                incomplete: stopped_at.t_type == CTokenType::Eof,
+                location: stopped_at.loc,
+            }
+        })
+    }
+
+    /// the construct from `start_idx` up to the longest cut before the stop, that parses
+    /// once the brackets left open are closed, with a `;` in front of a closing `}` or at the end if needed
+    fn recover_prefix<T>(&self, start_idx: usize, parse: fn(&mut CParser) -> T) -> Option<T> {
+        let stop_idx = self.idx.min(self.tokens.len());
+        let first_cut = stop_idx.saturating_sub(RECOVERY_CUTS).max(start_idx + 1);
+
+        (first_cut..=stop_idx).rev().find_map(|cut| {
+            let prefix = &self.tokens[start_idx..cut];
+            closing_suffixes(prefix).into_iter().find_map(|suffix| {
+                let mut tokens = prefix.to_vec();
+                tokens.extend(suffix);
+                let mut parser = CParser {
+                    tokens,
+                    idx: 0,
+                    typedef_table: self.typedef_table.clone(),
+                    standard: self.standard,
+                };
+                parser
+                    .catch_parse_error(parse)
+                    .ok()
+                    .filter(|_| parser.current_token().t_type == CTokenType::Eof)
+            })
+        })
+    }
+
+    fn skip_to_next_external_declaration(&mut self, start_idx: usize) {
+        self.idx = start_idx;
+        let mut depth = 0;
+
+        loop {
+            let token = self.current_token();
+            if token.t_type == CTokenType::Eof {
+                return;
+            }
+            self.advance_idx();
+            if token.t_type != CTokenType::Punctuator {
+                continue;
+            }
+            match token.original.as_str() {
+                "{" => depth += 1,
+                "}" => {
+                    depth -= 1;
+                    if depth <= 0 {
+                        return;
+                    }
+                }
+                ";" if depth == 0 => return,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// the closing brackets `tokens` leaves open, a `}` with and without a `;` before it,
+/// every combination with and without a final `;`
+fn closing_suffixes(tokens: &[CToken]) -> Vec<Vec<CToken>> {
+    let mut open_brackets = vec![];
+    for token in tokens.iter().filter(|t| t.t_type == CTokenType::Punctuator) {
+        match token.original.as_str() {
+            "(" => open_brackets.push(")"),
+            "[" => open_brackets.push("]"),
+            "{" => open_brackets.push("}"),
+            ")" | "]" | "}" => {
+                open_brackets.pop();
+            }
+            _ => {}
+        }
+    }
+    let location = tokens
+        .last()
+        .map(|token| token.loc.clone())
+        .unwrap_or(OriginalLocation {
+            file: String::new(),
+            line: 0,
+            collumn: 0,
+        });
+    let punctuator = |original: &str| CToken {
+        t_type: CTokenType::Punctuator,
+        original: original.to_string(),
+        loc: location.clone(),
+    };
+
+    let mut suffixes: Vec<Vec<CToken>> = vec![vec![]];
+    for closing in open_brackets.iter().rev() {
+        let mut next_suffixes = vec![];
+        for suffix in &suffixes {
+            if *closing == "}" && suffixes.len() < RECOVERY_SUFFIXES {
+                let mut with_semicolon = suffix.clone();
+                with_semicolon.extend([punctuator(";"), punctuator("}")]);
+                next_suffixes.push(with_semicolon);
+            }
+            let mut without_semicolon = suffix.clone();
+            without_semicolon.push(punctuator(closing));
+            next_suffixes.push(without_semicolon);
+        }
+        suffixes = next_suffixes;
+    }
+    let with_final_semicolon: Vec<Vec<CToken>> = suffixes
+        .iter()
+        .map(|suffix| {
+            let mut suffix = suffix.clone();
+            suffix.push(punctuator(";"));
+            suffix
+        })
+        .collect();
+    suffixes.extend(with_final_semicolon);
+    suffixes
+}
+
+/// a parse error, compile error or panic that stopped a phase
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CaughtError {
+    pub(crate) message: String,
+    /// where a compile error was raised
+    pub(crate) span: Option<Span>,
+}
+
+impl From<String> for CaughtError {
+    fn from(message: String) -> Self {
+        CaughtError {
+            message,
+            span: None,
+        }
+    }
+}
+
+/// runs `f`, a parse error, compile error or panic is returned instead of unwinding further
+pub(crate) fn catch_quietly<T>(f: impl FnOnce() -> T) -> Result<T, CaughtError> {
+    catch_unwind(AssertUnwindSafe(f)).map_err(caught_error)
+}
+
+fn caught_error(payload: Box<dyn Any + Send>) -> CaughtError {
+    if let Some(error) = payload.downcast_ref::<SpanError>() {
+        return CaughtError {
+            message: error.message.clone(),
+            span: Some(error.span.clone()),
+        };
+    }
+    let message = if let Some(error) = payload.downcast_ref::<ParseError>() {
+        error.message.clone()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else {
+        "parser error".to_string()
+    };
+    CaughtError::from(message)
+}
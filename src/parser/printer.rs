@@ -0,0 +1,1271 @@
+use super::{
+    parse_nodes::{
+        declarations::{
+            CAlignmentSpecifier, Declaration, DeclarationSpecifiers, Declarator, DerivedDeclarator,
+            Designator, Initializer, ParameterDeclaration, ParameterTypeList,
+            StaticAssertDeclaration,
+        },
+        expressions::{
+            AdditiveOperator, AssignmentOperator, CExpression, EqualityOperator,
+            GenericAssociation, IncrementType, MultiplicativeOperator, RelationalOperator,
+            ShiftOperator, UnaryOperator,
+        },
+        gnu_extensions::GnuAttribute,
+        statements::{CompoundItem, Statement},
+        Constant, ExternalDeclaration, FunctionDefinition, TranslationUnit,
+    },
+    span::Spanned,
+    types::{
+        CBasicTypes, CSructDeclaration, CStructDeclarator, CStructOrUnionTypeType, CTypeBasic,
+        CTypeName, CTypeOfArgument, CTypeQualifiers, CTypeSpecifier,
+    },
+};
+
+/*
+Printer for regenerating C source out of the AST.
+
+Output is formatted with 4 space indentation and K&R braces.
+Parenthesis are taken from `CExpression::Paranthesised`, additional ones are
+only inserted where the tree would otherwise not survive a reparse.
+So for every parsed translation unit: parse -> print -> parse gives an equal AST.
+*/
+pub(crate) fn translation_unit_to_c(translation_unit: &TranslationUnit) -> String {
+    let mut printer = CPrinter::new();
+    printer.print_translation_unit(translation_unit);
+    printer.finish()
+}
+
+/*
+Precedence levels of expressions, from loosest to tightest binding
+(6.5.17) expression
+(6.5.16) assignment-expression
+(6.5.15) conditional-expression
+...
+(6.5.2) postfix-expression
+(6.5.1) primary-expression
+*/
+const LEVEL_EXPRESSION: u8 = 1;
+const LEVEL_ASSIGNMENT: u8 = 2;
+const LEVEL_CONDITIONAL: u8 = 3;
+const LEVEL_LOGICAL_OR: u8 = 4;
+const LEVEL_LOGICAL_AND: u8 = 5;
+const LEVEL_INCLUSIVE_OR: u8 = 6;
+const LEVEL_EXCLUSIVE_OR: u8 = 7;
+const LEVEL_AND: u8 = 8;
+const LEVEL_EQUALITY: u8 = 9;
+const LEVEL_RELATIONAL: u8 = 10;
+const LEVEL_SHIFT: u8 = 11;
+const LEVEL_ADDITIVE: u8 = 12;
+const LEVEL_MULTIPLICATIVE: u8 = 13;
+const LEVEL_CAST: u8 = 14;
+const LEVEL_UNARY: u8 = 15;
+const LEVEL_POSTFIX: u8 = 16;
+const LEVEL_PRIMARY: u8 = 17;
+
+pub(crate) struct CPrinter {
+    output: String,
+    indent_level: usize,
+}
+
+impl CPrinter {
+    pub(crate) fn new() -> Self {
+        CPrinter {
+            output: String::new(),
+            indent_level: 0,
+        }
+    }
+
+    pub(crate) fn finish(self) -> String {
+        self.output
+    }
+
+    fn indent(level: usize) -> String {
+        "    ".repeat(level)
+    }
+
+    fn line(&mut self, text: &str) {
+        self.output.push_str(&Self::indent(self.indent_level));
+        self.output.push_str(text);
+        self.output.push('\n');
+    }
+
+    /// removes the closing brace line just printed, so it can be continued with 'else' or 'while'
+    fn pop_closing_brace(&mut self) {
+        let closing = format!("{}}}\n", Self::indent(self.indent_level));
+        assert!(self.output.ends_with(&closing));
+        self.output.truncate(self.output.len() - closing.len());
+    }
+}
+
+impl CPrinter {
+    pub(crate) fn print_translation_unit(&mut self, translation_unit: &TranslationUnit) {
+        let mut previous_was_function = false;
+        for (idx, external_declaration) in translation_unit.iter().enumerate() {
+            let is_function = matches!(
+                &*external_declaration.inner,
+                ExternalDeclaration::FunctionDefinition(_)
+            );
+            // functions get surrounded by empty lines
+            if idx != 0 && (is_function || previous_was_function) {
+                self.output.push('\n');
+            }
+            match &*external_declaration.inner {
+                ExternalDeclaration::FunctionDefinition(function) => {
+                    self.print_function_definition(function)
+                }
+                ExternalDeclaration::Declaration(declaration) => {
+                    self.print_declaration(declaration)
+                }
+            }
+            previous_was_function = is_function;
+        }
+    }
+
+    pub(crate) fn print_function_definition(&mut self, function: &FunctionDefinition) {
+        let header = format!(
+            "{} {}",
+            self.specifiers_to_string(&function.function_specifiers),
+            self.declarator_to_string(&function.declarator)
+        );
+
+        if function.declarations.is_empty() {
+            self.print_body(header, &function.body);
+        } else {
+            // identifier list style, declarations come before the body
+            self.line(&header);
+            self.indent_level += 1;
+            for declaration in &function.declarations {
+                self.print_declaration(declaration);
+            }
+            self.indent_level -= 1;
+            self.print_body(String::new(), &function.body);
+        }
+    }
+
+    pub(crate) fn print_declaration(&mut self, declaration: &Declaration) {
+        let declaration = self.declaration_to_string(declaration);
+        self.line(&declaration);
+    }
+
+    pub(crate) fn print_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Labeled { label, body } => {
+                self.line(&format!("{}:", label.identifier));
+                self.print_statement(body);
+            }
+            Statement::SwitchCase {
+                const_expr,
+                statement,
+            } => {
+                let header = format!(
+                    "case {}:",
+                    self.expression_to_string(&const_expr.internal, LEVEL_CONDITIONAL)
+                );
+                self.print_case_body(header, statement);
+            }
+            Statement::SwitchDefault { statement } => {
+                self.print_case_body("default:".to_string(), statement);
+            }
+            Statement::Compound(items) => {
+                self.line("{");
+                self.print_compound_items(items);
+                self.line("}");
+            }
+            Statement::CExpression(expr) => {
+                let expr = self.expression_to_string(expr, LEVEL_EXPRESSION);
+                self.line(&format!("{};", expr));
+            }
+            Statement::NoneExpr => self.line(";"),
+            Statement::If {
+                controlling_expr,
+                true_body,
+                else_body,
+            } => self.print_if(
+                String::new(),
+                controlling_expr,
+                true_body,
+                else_body.as_ref(),
+            ),
+            Statement::Switch {
+                controlling_expr,
+                body,
+            } => {
+                let header = format!(
+                    "switch ({})",
+                    self.expression_to_string(controlling_expr, LEVEL_EXPRESSION)
+                );
+                self.print_body(header, body);
+            }
+            Statement::While {
+                while_type,
+                controlling_expr,
+                body,
+            } => {
+                let controlling_expr =
+                    self.expression_to_string(controlling_expr, LEVEL_EXPRESSION);
+                if *while_type {
+                    // do-while
+                    self.print_body("do".to_string(), body);
+                    if matches!(&*body.inner, Statement::Compound(_)) {
+                        self.pop_closing_brace();
+                        self.line(&format!("}} while ({});", controlling_expr));
+                    } else {
+                        self.line(&format!("while ({});", controlling_expr));
+                    }
+                } else {
+                    self.print_body(format!("while ({})", controlling_expr), body);
+                }
+            }
+            Statement::For {
+                decl_clause,
+                expr_clause,
+                controlling_expr,
+                after_expr,
+                body,
+            } => {
+                let init = if let Some(declaration) = decl_clause {
+                    self.declaration_to_string(declaration)
+                } else if let Some(expr) = expr_clause {
+                    format!("{};", self.expression_to_string(expr, LEVEL_EXPRESSION))
+                } else {
+                    ";".to_string()
+                };
+                let controlling_expr = match controlling_expr {
+                    Some(expr) => {
+                        format!(" {};", self.expression_to_string(expr, LEVEL_EXPRESSION))
+                    }
+                    None => ";".to_string(),
+                };
+                let after_expr = match after_expr {
+                    Some(expr) => format!(" {}", self.expression_to_string(expr, LEVEL_EXPRESSION)),
+                    None => String::new(),
+                };
+                self.print_body(
+                    format!("for ({}{}{})", init, controlling_expr, after_expr),
+                    body,
+                );
+            }
+            Statement::Goto(label) => self.line(&format!("goto {};", label.identifier)),
+            Statement::GotoIndirect(target) => {
+                let target = self.expression_to_string(target, LEVEL_CAST);
+                self.line(&format!("goto *{};", target));
+            }
+            Statement::Continue => self.line("continue;"),
+            Statement::Break => self.line("break;"),
+            Statement::Return(None) => self.line("return;"),
+            Statement::Return(Some(expr)) => {
+                let expr = self.expression_to_string(expr, LEVEL_EXPRESSION);
+                self.line(&format!("return {};", expr));
+            }
+        }
+    }
+
+    fn print_compound_items(&mut self, items: &[CompoundItem]) {
+        self.indent_level += 1;
+        // statements following a case label inside a switch body get an extra indent
+        let mut in_case = false;
+        for item in items {
+            match item {
+                CompoundItem::Statement(statement)
+                    if matches!(
+                        &*statement.inner,
+                        Statement::SwitchCase { .. } | Statement::SwitchDefault { .. }
+                    ) =>
+                {
+                    in_case = true;
+                    self.print_statement(statement);
+                }
+                _ => {
+                    if in_case {
+                        self.indent_level += 1;
+                    }
+                    match item {
+                        CompoundItem::Statement(statement) => self.print_statement(statement),
+                        CompoundItem::Declaration(declaration) => {
+                            self.print_declaration(declaration)
+                        }
+                    }
+                    if in_case {
+                        self.indent_level -= 1;
+                    }
+                }
+            }
+        }
+        self.indent_level -= 1;
+    }
+
+    /// prints `header {` for compound bodies, otherwise the body indented on its own line
+    fn print_body(&mut self, header: String, body: &Statement) {
+        match body {
+            Statement::Compound(items) => {
+                if header.is_empty() {
+                    self.line("{");
+                } else {
+                    self.line(&format!("{} {{", header));
+                }
+                self.print_compound_items(items);
+                self.line("}");
+            }
+            _ => {
+                self.line(&header);
+                self.indent_level += 1;
+                self.print_statement(body);
+                self.indent_level -= 1;
+            }
+        }
+    }
+
+    fn print_case_body(&mut self, header: String, body: &Statement) {
+        if matches!(
+            body,
+            Statement::SwitchCase { .. } | Statement::SwitchDefault { .. }
+        ) {
+            // case 1: case 2: ...
+            self.line(&header);
+            self.print_statement(body);
+        } else {
+            self.print_body(header, body);
+        }
+    }
+
+    fn print_if(
+        &mut self,
+        prefix: String,
+        controlling_expr: &CExpression,
+        true_body: &Statement,
+        else_body: Option<&super::span::Spanned<Statement>>,
+    ) {
+        let header = format!(
+            "{}if ({})",
+            prefix,
+            self.expression_to_string(controlling_expr, LEVEL_EXPRESSION)
+        );
+
+        let braced = if else_body.is_some() && ends_in_open_if(true_body) {
+            // the else would otherwise bind to the inner if
+            self.line(&format!("{} {{", header));
+            self.indent_level += 1;
+            self.print_statement(true_body);
+            self.indent_level -= 1;
+            self.line("}");
+            true
+        } else {
+            self.print_body(header, true_body);
+            matches!(true_body, Statement::Compound(_))
+        };
+
+        if let Some(else_body) = else_body {
+            let prefix = if braced {
+                self.pop_closing_brace();
+                "} else".to_string()
+            } else {
+                "else".to_string()
+            };
+            match &*else_body.inner {
+                Statement::If {
+                    controlling_expr,
+                    true_body,
+                    else_body,
+                } => self.print_if(
+                    format!("{} ", prefix),
+                    controlling_expr,
+                    true_body,
+                    else_body.as_ref(),
+                ),
+                _ => self.print_body(prefix, else_body),
+            }
+        }
+    }
+}
+
+/// checks if a following 'else' would be taken by an if inside of this statement
+fn ends_in_open_if(statement: &Statement) -> bool {
+    match statement {
+        Statement::If {
+            else_body: None, ..
+        } => true,
+        Statement::If {
+            else_body: Some(else_body),
+            ..
+        } => ends_in_open_if(else_body),
+        Statement::Labeled { body, .. }
+        | Statement::Switch { body, .. }
+        | Statement::While {
+            while_type: false,
+            body,
+            ..
+        }
+        | Statement::For { body, .. } => ends_in_open_if(body),
+        Statement::SwitchCase { statement, .. } | Statement::SwitchDefault { statement } => {
+            ends_in_open_if(statement)
+        }
+        _ => false,
+    }
+}
+
+impl CPrinter {
+    fn declaration_to_string(&self, declaration: &Declaration) -> String {
+        match declaration {
+            Declaration::Declaration { specifiers, init } => {
+                let specifiers = self.specifiers_to_string(specifiers);
+                if init.is_empty() {
+                    return format!("{};", specifiers);
+                }
+                let declarators = init
+                    .iter()
+                    .map(|(declarator, initializer)| {
+                        let declarator = self.declarator_to_string(declarator);
+                        match initializer {
+                            Some(initializer) => {
+                                format!(
+                                    "{} = {}",
+                                    declarator,
+                                    self.initializer_to_string(initializer)
+                                )
+                            }
+                            None => declarator,
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("{} {};", specifiers, declarators)
+            }
+            Declaration::StaticAssertDeclaration(static_assert) => {
+                self.static_assert_to_string(static_assert)
+            }
+        }
+    }
+
+    fn static_assert_to_string(&self, static_assert: &StaticAssertDeclaration) -> String {
+        let expression =
+            self.expression_to_string(&static_assert.expression.internal, LEVEL_CONDITIONAL);
+        match &static_assert.string_literal {
+            Some(string_literal) => format!(
+                "_Static_assert({}, {});",
+                expression,
+                string_literal_to_string(&string_literal.value)
+            ),
+            None => format!("static_assert({});", expression),
+        }
+    }
+
+    fn initializer_to_string(&self, initializer: &Initializer) -> String {
+        match initializer {
+            Initializer::Single(expr) => self.expression_to_string(expr, LEVEL_ASSIGNMENT),
+            Initializer::Compound(list) => {
+                let list = list
+                    .iter()
+                    .map(|(designators, initializer)| {
+                        let initializer = self.initializer_to_string(initializer);
+                        if designators.is_empty() {
+                            initializer
+                        } else {
+                            let designators: String = designators
+                                .iter()
+                                .map(|designator| match designator {
+                                    Designator::Array(index) => format!(
+                                        "[{}]",
+                                        self.expression_to_string(
+                                            &index.internal,
+                                            LEVEL_CONDITIONAL
+                                        )
+                                    ),
+                                    Designator::Member(member) => {
+                                        format!(".{}", member.identifier)
+                                    }
+                                })
+                                .collect();
+                            format!("{} = {}", designators, initializer)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("{{{}}}", list)
+            }
+        }
+    }
+
+    pub(crate) fn specifiers_to_string(&self, specifiers: &DeclarationSpecifiers) -> String {
+        let storage = &specifiers.storage;
+        let mut parts = vec![];
+        if !specifiers.attributes.is_empty() {
+            parts.push(self.gnu_attributes_to_string(&specifiers.attributes));
+        }
+        for (is_set, keyword) in [
+            (storage.typedef_c, "typedef"),
+            (storage.extern_c, "extern"),
+            (storage.static_c, "static"),
+            (storage.thread_local_c, "_Thread_local"),
+            (storage.auto_c, "auto"),
+            (storage.register_c, "register"),
+            (storage.constexpr_c, "constexpr"),
+            (specifiers.function.inline, "inline"),
+            (specifiers.function.no_return, "_Noreturn"),
+        ] {
+            if is_set {
+                parts.push(keyword.to_string());
+            }
+        }
+        if let Some(alignment) = &specifiers.alignment {
+            parts.push(self.alignment_specifier_to_string(alignment));
+        }
+        parts.push(self.type_basic_to_string(&CTypeBasic {
+            qualifiers: specifiers.qualifiers.clone(),
+            specifier: specifiers.specifiers.clone(),
+        }));
+        parts.join(" ")
+    }
+
+    fn type_basic_to_string(&self, basic: &CTypeBasic) -> String {
+        let qualifiers = qualifiers_to_string(&basic.qualifiers);
+        let specifier = self.type_specifier_to_string(&basic.specifier);
+        if qualifiers.is_empty() {
+            specifier
+        } else {
+            format!("{} {}", qualifiers, specifier)
+        }
+    }
+
+    fn type_specifier_to_string(&self, specifier: &CTypeSpecifier) -> String {
+        match specifier {
+            CTypeSpecifier::Basic(basic) => basic_type_to_string(basic).to_string(),
+            CTypeSpecifier::StructOrUnion(struct_or_union) => {
+                let mut result = match struct_or_union.struct_type {
+                    CStructOrUnionTypeType::Struct => "struct".to_string(),
+                    CStructOrUnionTypeType::Union => "union".to_string(),
+                };
+                if !struct_or_union.attributes.is_empty() {
+                    result.push(' ');
+                    result.push_str(&self.gnu_attributes_to_string(&struct_or_union.attributes));
+                }
+                if let Some(ident) = &struct_or_union.ident {
+                    result.push(' ');
+                    result.push_str(&ident.identifier);
+                }
+                // an empty member list can not be told apart from a plain reference
+                if !struct_or_union.declarations.is_empty() || struct_or_union.ident.is_none() {
+                    let member_printer = CPrinter {
+                        output: String::new(),
+                        indent_level: self.indent_level + 1,
+                    };
+                    result.push_str(" {\n");
+                    for declaration in &struct_or_union.declarations {
+                        result.push_str(&Self::indent(member_printer.indent_level));
+                        result.push_str(&member_printer.struct_declaration_to_string(declaration));
+                        result.push('\n');
+                    }
+                    result.push_str(&Self::indent(self.indent_level));
+                    result.push('}');
+                }
+                result
+            }
+            CTypeSpecifier::Enum(enum_type) => {
+                let mut result = "enum".to_string();
+                if let Some(ident) = &enum_type.ident {
+                    result.push(' ');
+                    result.push_str(&ident.identifier);
+                }
+                if !enum_type.enumerators.is_empty() || enum_type.ident.is_none() {
+                    result.push_str(" {\n");
+                    for enumerator in &enum_type.enumerators {
+                        result.push_str(&Self::indent(self.indent_level + 1));
+                        result.push_str(&enumerator.enumeration_constant.identifier);
+                        if let Some(value) = &enumerator.const_assignment {
+                            result.push_str(" = ");
+                            result.push_str(
+                                &self.expression_to_string(&value.internal, LEVEL_CONDITIONAL),
+                            );
+                        }
+                        result.push_str(",\n");
+                    }
+                    result.push_str(&Self::indent(self.indent_level));
+                    result.push('}');
+                }
+                result
+            }
+            CTypeSpecifier::Typedefed(ident) => ident.identifier.clone(),
+            CTypeSpecifier::Atomic(type_name) => {
+                format!("_Atomic({})", self.type_name_to_string(type_name))
+            }
+            CTypeSpecifier::TypeOf(type_of) => {
+                let keyword = if type_of.unqualified {
+                    "typeof_unqual"
+                } else {
+                    "__typeof__"
+                };
+                match &type_of.argument {
+                    CTypeOfArgument::TypeName(type_name) => {
+                        format!("{}({})", keyword, self.type_name_to_string(type_name))
+                    }
+                    CTypeOfArgument::Expression(expr) => format!(
+                        "{}({})",
+                        keyword,
+                        self.expression_to_string(expr, LEVEL_EXPRESSION)
+                    ),
+                }
+            }
+        }
+    }
+
+    /// all attributes of one node, printed as a single `__attribute__((...))`
+    fn gnu_attributes_to_string(&self, attributes: &[Spanned<GnuAttribute>]) -> String {
+        let attributes = attributes
+            .iter()
+            .map(|attribute| match &*attribute.inner {
+                GnuAttribute::NoReturn => "noreturn".to_string(),
+                GnuAttribute::Aligned(None) => "aligned".to_string(),
+                GnuAttribute::Aligned(Some(alignment)) => format!(
+                    "aligned({})",
+                    self.expression_to_string(&alignment.internal, LEVEL_CONDITIONAL)
+                ),
+                GnuAttribute::Packed => "packed".to_string(),
+                GnuAttribute::Unused => "unused".to_string(),
+                GnuAttribute::Visibility(visibility) => {
+                    format!(
+                        "visibility({})",
+                        string_literal_to_string(&visibility.value)
+                    )
+                }
+                GnuAttribute::Section(section) => {
+                    format!("section({})", string_literal_to_string(&section.value))
+                }
+                GnuAttribute::Other { name, arguments } if arguments.is_empty() => {
+                    name.identifier.clone()
+                }
+                GnuAttribute::Other { name, arguments } => format!(
+                    "{}({})",
+                    name.identifier,
+                    self.expression_list_to_string(arguments, ", ", LEVEL_ASSIGNMENT)
+                ),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("__attribute__(({}))", attributes)
+    }
+
+    fn alignment_specifier_to_string(&self, alignment: &Spanned<CAlignmentSpecifier>) -> String {
+        match &*alignment.inner {
+            CAlignmentSpecifier::ToType(type_name) => {
+                format!("_Alignas({})", self.type_name_to_string(type_name))
+            }
+            CAlignmentSpecifier::ToExpression(expr) => format!(
+                "_Alignas({})",
+                self.expression_to_string(&expr.internal, LEVEL_CONDITIONAL)
+            ),
+        }
+    }
+
+    fn struct_declaration_to_string(&self, declaration: &CSructDeclaration) -> String {
+        match declaration {
+            CSructDeclaration::StaticAssertDeclaration(static_assert) => {
+                self.static_assert_to_string(static_assert)
+            }
+            CSructDeclaration::StructDeclaration {
+                alignment,
+                specifier_qualifier,
+                delcarator_list,
+            } => {
+                let mut specifier_qualifier = self.type_basic_to_string(specifier_qualifier);
+                if let Some(alignment) = alignment {
+                    specifier_qualifier = format!(
+                        "{} {}",
+                        self.alignment_specifier_to_string(alignment),
+                        specifier_qualifier
+                    );
+                }
+                if delcarator_list.is_empty() {
+                    return format!("{};", specifier_qualifier);
+                }
+                let declarators = delcarator_list
+                    .iter()
+                    .map(|declarator| match &*declarator.inner {
+                        CStructDeclarator::Declarator(declarator) => {
+                            self.declarator_to_string(declarator)
+                        }
+                        CStructDeclarator::BitField { declarator, expr } => {
+                            let width =
+                                self.expression_to_string(&expr.internal, LEVEL_CONDITIONAL);
+                            match declarator {
+                                Some(declarator) => {
+                                    format!("{} : {}", self.declarator_to_string(declarator), width)
+                                }
+                                None => format!(": {}", width),
+                            }
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("{} {};", specifier_qualifier, declarators)
+            }
+        }
+    }
+
+    pub(crate) fn type_name_to_string(&self, type_name: &CTypeName) -> String {
+        let base = self.type_basic_to_string(&type_name.base);
+        let declarator = self.derived_declarator_to_string(&type_name.declarator, "");
+        if declarator.is_empty() {
+            base
+        } else {
+            format!("{} {}", base, declarator)
+        }
+    }
+
+    fn declarator_to_string(&self, declarator: &Declarator) -> String {
+        let mut result =
+            self.derived_declarator_to_string(&declarator.derive, &declarator.base.identifier);
+        if let Some(asm_label) = &declarator.asm_label {
+            result.push_str(&format!(
+                " __asm__({})",
+                string_literal_to_string(&asm_label.value)
+            ));
+        }
+        if !declarator.attributes.is_empty() {
+            result.push(' ');
+            result.push_str(&self.gnu_attributes_to_string(&declarator.attributes));
+        }
+        result
+    }
+
+    /*
+    The parser builds the chain of derivations per nesting level as:
+        suffixes (last one outermost) -> pointers (last one outermost) -> next level
+    with the innermost parenthesised level being outermost in the chain.
+    So split the chain into levels and wrap every level except the last in parenthesis.
+    */
+    pub(crate) fn derived_declarator_to_string(
+        &self,
+        derived: &DerivedDeclarator,
+        identifier: &str,
+    ) -> String {
+        let mut levels: Vec<(Vec<&DerivedDeclarator>, Vec<&DerivedDeclarator>)> = vec![];
+        let mut current = derived;
+        loop {
+            match current {
+                DerivedDeclarator::Base => break,
+                DerivedDeclarator::Pointer { to, .. } => {
+                    if levels.is_empty() {
+                        levels.push((vec![], vec![]));
+                    }
+                    levels.last_mut().unwrap().1.push(current);
+                    current = to;
+                }
+                DerivedDeclarator::Array { to, .. }
+                | DerivedDeclarator::FunctionType { to, .. }
+                | DerivedDeclarator::FunctionIdentified { to, .. } => {
+                    if levels.is_empty() || !levels.last().unwrap().1.is_empty() {
+                        levels.push((vec![], vec![]));
+                    }
+                    levels.last_mut().unwrap().0.push(current);
+                    current = to;
+                }
+            }
+        }
+
+        let mut result = identifier.to_string();
+        let level_count = levels.len();
+        for (idx, (suffixes, pointers)) in levels.into_iter().enumerate() {
+            for suffix in suffixes.into_iter().rev() {
+                result.push_str(&self.declarator_suffix_to_string(suffix));
+            }
+            for pointer in pointers {
+                if let DerivedDeclarator::Pointer { qualifiers, .. } = pointer {
+                    let qualifiers = qualifiers_to_string(qualifiers);
+                    result = if qualifiers.is_empty() {
+                        format!("*{}", result)
+                    } else if result.is_empty() {
+                        format!("*{}", qualifiers)
+                    } else {
+                        format!("*{} {}", qualifiers, result)
+                    };
+                }
+            }
+            if idx + 1 != level_count {
+                result = format!("({})", result);
+            }
+        }
+        result
+    }
+
+    fn declarator_suffix_to_string(&self, suffix: &DerivedDeclarator) -> String {
+        match suffix {
+            DerivedDeclarator::Array {
+                qualifiers,
+                is_static,
+                size_expr,
+                vla,
+                ..
+            } => {
+                let mut parts = vec![];
+                if *is_static {
+                    parts.push("static".to_string());
+                }
+                let qualifiers = qualifiers_to_string(qualifiers);
+                if !qualifiers.is_empty() {
+                    parts.push(qualifiers);
+                }
+                if *vla {
+                    parts.push("*".to_string());
+                } else if let Some(size_expr) = size_expr {
+                    parts.push(self.expression_to_string(size_expr, LEVEL_ASSIGNMENT));
+                }
+                format!("[{}]", parts.join(" "))
+            }
+            DerivedDeclarator::FunctionType {
+                parameter_type_list,
+                ..
+            } => format!(
+                "({})",
+                self.parameter_type_list_to_string(parameter_type_list)
+            ),
+            DerivedDeclarator::FunctionIdentified {
+                identifier_list, ..
+            } => format!(
+                "({})",
+                identifier_list
+                    .iter()
+                    .map(|ident| ident.identifier.clone())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            DerivedDeclarator::Base | DerivedDeclarator::Pointer { .. } => unreachable!(),
+        }
+    }
+
+    fn parameter_type_list_to_string(&self, parameter_type_list: &ParameterTypeList) -> String {
+        let mut parameters = parameter_type_list
+            .parameter_list
+            .iter()
+            .map(|parameter| match &*parameter.inner {
+                ParameterDeclaration::Declarator {
+                    specifiers,
+                    declarator,
+                } => format!(
+                    "{} {}",
+                    self.specifiers_to_string(specifiers),
+                    self.declarator_to_string(declarator)
+                ),
+                ParameterDeclaration::AbstractDeclarator {
+                    specifiers,
+                    abstract_declarator,
+                } => {
+                    let specifiers = self.specifiers_to_string(specifiers);
+                    let abstract_declarator = abstract_declarator
+                        .as_ref()
+                        .map(|derived| self.derived_declarator_to_string(derived, ""))
+                        .unwrap_or_default();
+                    if abstract_declarator.is_empty() {
+                        specifiers
+                    } else {
+                        format!("{} {}", specifiers, abstract_declarator)
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        if parameter_type_list.ellipsis {
+            parameters.push("...".to_string());
+        }
+        parameters.join(", ")
+    }
+}
+
+impl CPrinter {
+    /// prints the expression, in parenthesis if it binds looser than min_level
+    pub(crate) fn expression_to_string(&self, expr: &CExpression, min_level: u8) -> String {
+        let (level, result) = self.expression_to_string_inner(expr);
+        if level < min_level {
+            format!("({})", result)
+        } else {
+            result
+        }
+    }
+
+    fn expression_to_string_inner(&self, expr: &CExpression) -> (u8, String) {
+        match expr {
+            CExpression::Expression(list) => (
+                LEVEL_EXPRESSION,
+                self.expression_list_to_string(list, ", ", LEVEL_ASSIGNMENT),
+            ),
+            CExpression::Assignment {
+                to_assign,
+                operator,
+                value,
+            } => (
+                LEVEL_ASSIGNMENT,
+                format!(
+                    "{} {} {}",
+                    self.expression_to_string(to_assign, LEVEL_UNARY),
+                    assignment_operator_to_str(operator),
+                    self.expression_to_string(value, LEVEL_ASSIGNMENT)
+                ),
+            ),
+            CExpression::Ternary {
+                condition,
+                if_true,
+                tern_else,
+            } => (
+                LEVEL_CONDITIONAL,
+                format!(
+                    "{} ? {} : {}",
+                    self.expression_to_string(condition, LEVEL_LOGICAL_OR),
+                    self.expression_to_string(if_true, LEVEL_EXPRESSION),
+                    self.expression_to_string(tern_else, LEVEL_CONDITIONAL)
+                ),
+            ),
+            CExpression::LogicalOr(list) => (
+                LEVEL_LOGICAL_OR,
+                self.expression_list_to_string(list, " || ", LEVEL_LOGICAL_AND),
+            ),
+            CExpression::LogicalAnd(list) => (
+                LEVEL_LOGICAL_AND,
+                self.expression_list_to_string(list, " && ", LEVEL_INCLUSIVE_OR),
+            ),
+            CExpression::InclusiveOr(list) => (
+                LEVEL_INCLUSIVE_OR,
+                self.expression_list_to_string(list, " | ", LEVEL_EXCLUSIVE_OR),
+            ),
+            CExpression::ExlusiveOr(list) => (
+                LEVEL_EXCLUSIVE_OR,
+                self.expression_list_to_string(list, " ^ ", LEVEL_AND),
+            ),
+            CExpression::And(list) => (
+                LEVEL_AND,
+                self.expression_list_to_string(list, " & ", LEVEL_EQUALITY),
+            ),
+            CExpression::Equality {
+                left_piece,
+                equality_op,
+                right_piece,
+            } => self.binary_to_string(
+                LEVEL_EQUALITY,
+                left_piece,
+                match equality_op {
+                    EqualityOperator::Equal => "==",
+                    EqualityOperator::NotEqual => "!=",
+                },
+                right_piece,
+            ),
+            CExpression::Relational {
+                left_piece,
+                equality_op,
+                right_piece,
+            } => self.binary_to_string(
+                LEVEL_RELATIONAL,
+                left_piece,
+                match equality_op {
+                    RelationalOperator::Lesser => "<",
+                    RelationalOperator::Greater => ">",
+                    RelationalOperator::LesserEqual => "<=",
+                    RelationalOperator::GreaterEqual => ">=",
+                },
+                right_piece,
+            ),
+            CExpression::Shift {
+                value,
+                shift_type,
+                shift_amount,
+            } => self.binary_to_string(
+                LEVEL_SHIFT,
+                value,
+                match shift_type {
+                    ShiftOperator::Left => "<<",
+                    ShiftOperator::Right => ">>",
+                },
+                shift_amount,
+            ),
+            CExpression::Additive {
+                left_value,
+                op,
+                right_value,
+            } => self.binary_to_string(
+                LEVEL_ADDITIVE,
+                left_value,
+                match op {
+                    AdditiveOperator::Plus => "+",
+                    AdditiveOperator::Minus => "-",
+                },
+                right_value,
+            ),
+            CExpression::Multiplicative {
+                left_value,
+                op,
+                right_value,
+            } => self.binary_to_string(
+                LEVEL_MULTIPLICATIVE,
+                left_value,
+                match op {
+                    MultiplicativeOperator::Mult => "*",
+                    MultiplicativeOperator::Div => "/",
+                    MultiplicativeOperator::Mod => "%",
+                },
+                right_value,
+            ),
+            CExpression::Cast { type_name, value } => (
+                LEVEL_CAST,
+                format!(
+                    "({}){}",
+                    self.type_name_to_string(type_name),
+                    self.expression_to_string(value, LEVEL_CAST)
+                ),
+            ),
+            CExpression::PrefixIncrement {
+                increment_type,
+                value,
+            } => (
+                LEVEL_UNARY,
+                prefix_operator_to_string(
+                    increment_type_to_str(increment_type),
+                    self.expression_to_string(value, LEVEL_UNARY),
+                ),
+            ),
+            CExpression::Unary { unary_op, value } => (
+                LEVEL_UNARY,
+                prefix_operator_to_string(
+                    match unary_op {
+                        UnaryOperator::REF => "&",
+                        UnaryOperator::DEREF => "*",
+                        UnaryOperator::VALUE => "+",
+                        UnaryOperator::NEGATIVE => "-",
+                        UnaryOperator::BITWISEINVERT => "~",
+                        UnaryOperator::BOOLEANINVERT => "!",
+                    },
+                    self.expression_to_string(value, LEVEL_CAST),
+                ),
+            ),
+            CExpression::SizeOf { value } => (
+                LEVEL_UNARY,
+                format!("sizeof {}", self.expression_to_string(value, LEVEL_UNARY)),
+            ),
+            CExpression::SizeOfType { type_name } => (
+                LEVEL_UNARY,
+                format!("sizeof({})", self.type_name_to_string(type_name)),
+            ),
+            CExpression::AlignOfType { type_name } => (
+                LEVEL_UNARY,
+                format!("_Alignof({})", self.type_name_to_string(type_name)),
+            ),
+            CExpression::ArraySubscription { array, index } => (
+                LEVEL_POSTFIX,
+                format!(
+                    "{}[{}]",
+                    self.expression_to_string(array, LEVEL_POSTFIX),
+                    self.expression_to_string(index, LEVEL_EXPRESSION)
+                ),
+            ),
+            CExpression::FunctionCall {
+                function,
+                arguments,
+            } => (
+                LEVEL_POSTFIX,
+                format!(
+                    "{}({})",
+                    self.expression_to_string(function, LEVEL_POSTFIX),
+                    self.expression_list_to_string(arguments, ", ", LEVEL_ASSIGNMENT)
+                ),
+            ),
+            CExpression::DirectMemberAccess { to_access, member } => (
+                LEVEL_POSTFIX,
+                format!(
+                    "{}.{}",
+                    self.expression_to_string(to_access, LEVEL_POSTFIX),
+                    member.identifier
+                ),
+            ),
+            CExpression::IndirectMemberAccess { to_access, member } => (
+                LEVEL_POSTFIX,
+                format!(
+                    "{}->{}",
+                    self.expression_to_string(to_access, LEVEL_POSTFIX),
+                    member.identifier
+                ),
+            ),
+            CExpression::PostfixIncrement {
+                increment_type,
+                value,
+            } => (
+                LEVEL_POSTFIX,
+                format!(
+                    "{}{}",
+                    self.expression_to_string(value, LEVEL_POSTFIX),
+                    increment_type_to_str(increment_type)
+                ),
+            ),
+            CExpression::TypeInitializer {
+                type_name,
+                initializer_list,
+            } => (
+                LEVEL_POSTFIX,
+                format!(
+                    "({}){}",
+                    self.type_name_to_string(type_name),
+                    self.initializer_to_string(initializer_list)
+                ),
+            ),
+            CExpression::Identifier(ident) => (LEVEL_PRIMARY, ident.identifier.clone()),
+            CExpression::Constant(Constant::Number(number)) => (LEVEL_PRIMARY, number.from.clone()),
+            CExpression::Constant(Constant::Bool(value)) => (LEVEL_PRIMARY, value.to_string()),
+            CExpression::Constant(Constant::Nullptr) => (LEVEL_PRIMARY, "nullptr".to_string()),
+            CExpression::StringLiteral(literal) => {
+                (LEVEL_PRIMARY, string_literal_to_string(&literal.value))
+            }
+            CExpression::Paranthesised(inner) => (
+                LEVEL_PRIMARY,
+                format!("({})", self.expression_to_string(inner, LEVEL_EXPRESSION)),
+            ),
+            CExpression::StatementExpression(items) => {
+                // the block is printed as if it started on the current line
+                let mut block_printer = CPrinter {
+                    output: String::new(),
+                    indent_level: self.indent_level,
+                };
+                block_printer.print_compound_items(items);
+                (
+                    LEVEL_PRIMARY,
+                    format!(
+                        "({{\n{}{}}})",
+                        block_printer.finish(),
+                        Self::indent(self.indent_level)
+                    ),
+                )
+            }
+            CExpression::LabelAddress(label) => (LEVEL_UNARY, format!("&&{}", label.identifier)),
+            CExpression::GenericSelection(generic) => {
+                let mut parts = vec![
+                    self.expression_to_string(&generic.assignment_expression, LEVEL_ASSIGNMENT)
+                ];
+                for association in generic.generic_assoc_list.iter() {
+                    parts.push(match association {
+                        GenericAssociation::TypeName {
+                            type_name,
+                            assignment_expression,
+                        } => format!(
+                            "{}: {}",
+                            self.type_name_to_string(type_name),
+                            self.expression_to_string(assignment_expression, LEVEL_ASSIGNMENT)
+                        ),
+                        GenericAssociation::Default(assignment_expression) => format!(
+                            "default: {}",
+                            self.expression_to_string(assignment_expression, LEVEL_ASSIGNMENT)
+                        ),
+                    });
+                }
+                (LEVEL_PRIMARY, format!("_Generic({})", parts.join(", ")))
+            }
+        }
+    }
+
+    /// left operand may be of the same level, the right one has to bind tighter
+    fn binary_to_string(
+        &self,
+        level: u8,
+        left: &CExpression,
+        op: &str,
+        right: &CExpression,
+    ) -> (u8, String) {
+        (
+            level,
+            format!(
+                "{} {} {}",
+                self.expression_to_string(left, level),
+                op,
+                self.expression_to_string(right, level + 1)
+            ),
+        )
+    }
+
+    fn expression_list_to_string<T: std::ops::Deref<Target = CExpression>>(
+        &self,
+        list: &[T],
+        seperator: &str,
+        min_level: u8,
+    ) -> String {
+        list.iter()
+            .map(|expr| self.expression_to_string(expr, min_level))
+            .collect::<Vec<_>>()
+            .join(seperator)
+    }
+}
+
+/// avoids gluing operators into new tokens like '- -x' => '--x'
+fn prefix_operator_to_string(op: &str, operand: String) -> String {
+    if operand.starts_with(op.chars().next().unwrap()) {
+        format!("{} {}", op, operand)
+    } else {
+        format!("{}{}", op, operand)
+    }
+}
+
+fn increment_type_to_str(increment_type: &IncrementType) -> &'static str {
+    match increment_type {
+        IncrementType::Increment => "++",
+        IncrementType::Decrement => "--",
+    }
+}
+
+fn assignment_operator_to_str(operator: &AssignmentOperator) -> &'static str {
+    match operator {
+        AssignmentOperator::Assign => "=",
+        AssignmentOperator::AssignMult => "*=",
+        AssignmentOperator::AssignDiv => "/=",
+        AssignmentOperator::AssignMod => "%=",
+        AssignmentOperator::AssignPlus => "+=",
+        AssignmentOperator::AssignMinus => "-=",
+        AssignmentOperator::AssignShiftLeft => "<<=",
+        AssignmentOperator::AssignShiftRight => ">>=",
+        AssignmentOperator::AssignAnd => "&=",
+        AssignmentOperator::AssignXor => "^=",
+        AssignmentOperator::AssignOr => "|=",
+    }
+}
+
+fn qualifiers_to_string(qualifiers: &CTypeQualifiers) -> String {
+    let mut parts = vec![];
+    for (is_set, keyword) in [
+        (qualifiers.const_q, "const"),
+        (qualifiers.restrict_q, "restrict"),
+        (qualifiers.volatile_q, "volatile"),
+        (qualifiers.atomic_q, "_Atomic"),
+    ] {
+        if is_set {
+            parts.push(keyword);
+        }
+    }
+    parts.join(" ")
+}
+
+fn basic_type_to_string(basic: &CBasicTypes) -> &'static str {
+    match basic {
+        CBasicTypes::Void => "void",
+        CBasicTypes::Char => "char",
+        CBasicTypes::SignedChar => "signed char",
+        CBasicTypes::UnsignedChar => "unsigned char",
+        CBasicTypes::Short => "short",
+        CBasicTypes::UnShort => "unsigned short",
+        CBasicTypes::Int => "int",
+        CBasicTypes::UnInt => "unsigned int",
+        CBasicTypes::Long => "long",
+        CBasicTypes::UnLong => "unsigned long",
+        CBasicTypes::LongLong => "long long",
+        CBasicTypes::UnLongLong => "unsigned long long",
+        CBasicTypes::Float => "float",
+        CBasicTypes::Double => "double",
+        CBasicTypes::LongDouble => "long double",
+        CBasicTypes::Bool => "_Bool",
+        CBasicTypes::FloatComplex => "float _Complex",
+        CBasicTypes::DoubleComplex => "double _Complex",
+        CBasicTypes::LongDoubleComplex => "long double _Complex",
+    }
+}
+
+/// the lexer resolves only '\n' and '\"', every other escape is kept as written
+fn string_literal_to_string(value: &str) -> String {
+    let mut result = String::from("\"");
+    for character in value.chars() {
+        match character {
+            '\n' => result.push_str("\\n"),
+            '"' => result.push_str("\\\""),
+            _ => result.push(character),
+        }
+    }
+    result.push('"');
+    result
+}
@@ -1,25 +1,64 @@
 use std::{
     fmt::Debug,
     ops::{Deref, DerefMut},
+    panic::resume_unwind,
 };
 
 use log::error;
-use serde::{Deserialize, Serialize};
+use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::lexer::OriginalLocation;
 
-#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
-#[serde(transparent)]
+#[derive(Debug, Clone, Eq)]
 pub(crate) struct Spanned<T>
 where
     T: Clone + Debug,
 {
     pub(crate) inner: Box<T>,
-    #[allow(dead_code)]
-    #[serde(skip_serializing)]
-    #[serde(default)]
     pub(crate) span: Span,
 }
+
+/*
+A Spanned<T> (de)serializes transparently as its inner node, that keeps the yaml in the tests
+readable. Wrapped in WithSpans (see span_serde.rs) the (de)serializer is not human readable and
+every Spanned<T> becomes { span, node } instead, this is what the json ast output uses.
+*/
+impl<T: Clone + Debug + Serialize> Serialize for Spanned<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !serializer.is_human_readable() {
+            let mut state = serializer.serialize_struct("Spanned", 2)?;
+            state.serialize_field("span", &self.span)?;
+            state.serialize_field("node", &self.inner)?;
+            state.end()
+        } else {
+            self.inner.serialize(serializer)
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SpannedRepr<T> {
+    span: Span,
+    node: Box<T>,
+}
+
+impl<'de, T: Clone + Debug + Deserialize<'de>> Deserialize<'de> for Spanned<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if !deserializer.is_human_readable() {
+            let repr = SpannedRepr::<T>::deserialize(deserializer)?;
+            Ok(Spanned {
+                inner: repr.node,
+                span: repr.span,
+            })
+        } else {
+            Ok(Spanned {
+                inner: Box::<T>::deserialize(deserializer)?,
+                span: Span::default(),
+            })
+        }
+    }
+}
 impl<T: Clone + Debug> Deref for Spanned<T> {
     type Target = T;
 
@@ -45,8 +84,8 @@ impl<T: Clone + Debug> Spanned<T> {
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) struct Span {
-    start: OriginalLocation,
-    end: OriginalLocation,
+    pub(crate) start: OriginalLocation,
+    pub(crate) end: OriginalLocation,
 }
 impl Span {
     pub(crate) fn new(start: OriginalLocation, end: OriginalLocation) -> Self {
@@ -63,6 +102,19 @@ impl Span {
             err
         );
     }
+    /// stops the compilation with `err` at the span, the caller of the compiler gets it as diagnostic
+    pub(crate) fn fail_at_span(&self, err: &str) -> ! {
+        resume_unwind(Box::new(SpanError {
+            message: err.to_string(),
+            span: self.clone(),
+        }))
+    }
+}
+
+/// what a compile error unwinds with, it is no panic, so no panic hook reports it
+pub(crate) struct SpanError {
+    pub(crate) message: String,
+    pub(crate) span: Span,
 }
 
 impl<T: Clone + Debug> Spanned<T> {
@@ -0,0 +1,73 @@
+use crate::parser::{
+    ast_json::{translation_unit_from_json, translation_unit_to_json},
+    parse_nodes::ExternalDeclaration,
+};
+
+use super::run_lexer_with_return_that_init_parser;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+#[test]
+fn ast_json_round_trip_keeps_spans() {
+    let code = r#"
+int add(int a, int b) {
+    return a + b;
+}
+int main() {
+    int c = add(4, 5);
+    return c;
+}"#;
+
+    let parsed = run_lexer_with_return_that_init_parser(code).parse();
+
+    let json = translation_unit_to_json(&parsed).unwrap();
+    println!("{}", json);
+
+    let loaded = translation_unit_from_json(&json).unwrap();
+
+    // PartialEq on Spanned ignores the span, so check them by hand
+    assert_eq!(loaded, parsed);
+    for (loaded_decl, parsed_decl) in loaded.iter().zip(parsed.iter()) {
+        assert_eq!(loaded_decl.span, parsed_decl.span);
+        if let (
+            ExternalDeclaration::FunctionDefinition(loaded_func),
+            ExternalDeclaration::FunctionDefinition(parsed_func),
+        ) = (&*loaded_decl.inner, &*parsed_decl.inner)
+        {
+            assert_eq!(loaded_func.span, parsed_func.span);
+            assert_eq!(loaded_func.body.span, parsed_func.body.span);
+        } else {
+            unreachable!()
+        }
+    }
+    assert_eq!(loaded[1].span.start.line, 4);
+    assert_eq!(loaded[1].span.end.line, 7);
+}
+
+#[test]
+fn ast_json_schema_layout() {
+    let code = r#"int x;"#;
+
+    let parsed = run_lexer_with_return_that_init_parser(code).parse();
+    let json: serde_json::Value =
+        serde_json::from_str(&translation_unit_to_json(&parsed).unwrap()).unwrap();
+
+    assert_eq!(json["format"], "jankcc-ast");
+    assert_eq!(json["version"], 1);
+    let first = &json["translation_unit"][0];
+    assert_eq!(first["span"]["start"]["line"], 0);
+    assert_eq!(first["span"]["start"]["column"], 0);
+    assert_eq!(
+        first["node"]["Declaration"]["node"]["Declaration"]["init"][0][0]["node"]["base"]
+            ["identifier"],
+        "x"
+    );
+}
+
+#[test]
+fn ast_json_rejects_unknown_version() {
+    let json = r#"{ "format": "jankcc-ast", "version": 99, "translation_unit": [] }"#;
+
+    assert!(translation_unit_from_json(json).is_err());
+}
@@ -0,0 +1,71 @@
+use crate::parser::{
+    parse_nodes::{declarations::Declaration, gnu_extensions::GnuAttribute, ExternalDeclaration},
+    printer::translation_unit_to_c,
+};
+use crate::standard::CStandard;
+
+use super::run_lexer_with_return_that_init_parser_with_standard;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+#[test]
+fn c23_keywords_and_attributes() {
+    let code = r#"
+static_assert(sizeof(int) == 4);
+[[nodiscard, gnu::noreturn]] int fail(void);
+int main() {
+    [[maybe_unused]] constexpr int million = 1'000'000;
+    bool flag = true;
+    typeof_unqual(million) copy = 0b1010;
+    int *ptr = nullptr;
+    switch (copy) {
+    case 10:
+        [[fallthrough]];
+    default:
+        return false;
+    }
+}
+"#;
+
+    let parsed = run_lexer_with_return_that_init_parser_with_standard(code, CStandard::C23).parse();
+    assert_eq!(parsed.len(), 3);
+
+    let ExternalDeclaration::Declaration(declaration) = &*parsed[1].inner else {
+        panic!("expected declaration");
+    };
+    let Declaration::Declaration { specifiers, .. } = &*declaration.inner else {
+        panic!("expected declaration");
+    };
+    let attributes: Vec<GnuAttribute> = specifiers
+        .attributes
+        .iter()
+        .map(|attribute| *attribute.inner.clone())
+        .collect();
+    assert!(
+        matches!(&attributes[0], GnuAttribute::Other { name, .. } if name.identifier == "nodiscard")
+    );
+    assert_eq!(attributes[1], GnuAttribute::NoReturn);
+
+    let printed = translation_unit_to_c(&parsed);
+    println!("{}", printed);
+    assert!(printed.contains("static_assert(sizeof(int) == 4);"));
+    assert!(printed.contains("__attribute__((unused)) constexpr int million = 1000000;"));
+    assert!(printed.contains("_Bool flag = true;"));
+    assert!(printed.contains("typeof_unqual(million) copy = 0b1010;"));
+    assert!(printed.contains("int *ptr = nullptr;"));
+
+    let reparsed =
+        run_lexer_with_return_that_init_parser_with_standard(&printed, CStandard::C23).parse();
+    assert_eq!(reparsed, parsed);
+}
+
+#[test]
+#[should_panic]
+fn c23_keywords_are_identifiers_in_c11() {
+    run_lexer_with_return_that_init_parser_with_standard(
+        "int main() { constexpr int x = 1; return x; }",
+        CStandard::C11,
+    )
+    .parse();
+}
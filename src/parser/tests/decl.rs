@@ -1,3 +1,8 @@
+use crate::parser::{
+    parse_nodes::declarations::{Declaration, DerivedDeclarator},
+    types::{CBasicTypes, CTypeSpecifier},
+};
+
 use super::run_lexer_with_return_that_init_parser;
 
 #[test]
@@ -1251,3 +1256,62 @@ Declaration:
 
     assert_eq!(got_result, expected_result);
 }
+
+#[test]
+fn kr_function_definition() {
+    let code = r#"
+scale(n, factor)
+register n;
+short factor;
+{
+    return n * factor;
+}"#;
+
+    let mut simple_parser = run_lexer_with_return_that_init_parser(code);
+    let got_result = simple_parser.parse_function_definition();
+    println!("{}", serde_yaml::to_string(&got_result).unwrap());
+
+    // the missing return and parameter types are int
+    assert_eq!(
+        got_result.function_specifiers.specifiers,
+        CTypeSpecifier::Basic(CBasicTypes::Int)
+    );
+    let DerivedDeclarator::FunctionIdentified {
+        identifier_list, ..
+    } = &got_result.declarator.derive
+    else {
+        panic!("expected identifier list");
+    };
+    let identifiers: Vec<&str> = identifier_list
+        .iter()
+        .map(|ident| ident.identifier.as_str())
+        .collect();
+    assert_eq!(identifiers, vec!["n", "factor"]);
+
+    assert_eq!(got_result.declarations.len(), 2);
+    let Declaration::Declaration { specifiers, init } = &*got_result.declarations[0].inner else {
+        panic!("expected declaration");
+    };
+    assert!(specifiers.storage.register_c);
+    assert_eq!(
+        specifiers.specifiers,
+        CTypeSpecifier::Basic(CBasicTypes::Int)
+    );
+    assert_eq!(init[0].0.base.identifier, "n");
+}
+
+#[test]
+fn alignment_specifier_kept_after_later_specifiers() {
+    let code = r#"_Alignas(8) const int"#;
+
+    let mut simple_parser = run_lexer_with_return_that_init_parser(code);
+    let got_result = simple_parser.parse_declaration_specifiers();
+    println!("{}", serde_yaml::to_string(&got_result).unwrap());
+
+    assert!(got_result.alignment.is_some());
+    assert!(got_result.qualifiers.const_q);
+    assert_eq!(
+        got_result.specifiers,
+        CTypeSpecifier::Basic(CBasicTypes::Int)
+    );
+}
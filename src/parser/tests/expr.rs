@@ -142,6 +142,30 @@ Multiplicative:
     expresion_test_helper(expr, expected_result, &CParser::parse_expression);
 }
 
+#[test]
+fn prefix_increment_and_decrement() {
+    let expr = r#"++x - --y"#;
+
+    let expected_result = "
+Additive:
+    left_value:
+      PrefixIncrement:
+        increment_type: Increment
+        value:
+          Identifier:
+            identifier: x
+    op: Minus
+    right_value:
+      PrefixIncrement:
+        increment_type: Decrement
+        value:
+          Identifier:
+            identifier: y
+    ";
+
+    expresion_test_helper(expr, expected_result, &CParser::parse_expression);
+}
+
 #[test]
 fn simple_add() {
     let expr = r#"42 + func()++ * --!x - -var"#;
@@ -593,3 +617,90 @@ Multiplicative:
 
     expresion_test_helper(expr, expected_result, &CParser::parse_expression);
 }
+
+#[test]
+fn logical_or_of_logical_and() {
+    let expr = r#"a || b && c"#;
+
+    let expected_result = "
+LogicalOr:
+  - Identifier:
+      identifier: a
+  - LogicalAnd:
+      - Identifier:
+          identifier: b
+      - Identifier:
+          identifier: c
+    ";
+
+    expresion_test_helper(expr, expected_result, &CParser::parse_expression);
+}
+
+#[test]
+fn unary_operand_is_a_cast_expression() {
+    let expr = r#"-(int)x"#;
+
+    let expected_result = "
+Unary:
+  unary_op: NEGATIVE
+  value:
+    Cast:
+      type_name:
+        base:
+          qualifiers:
+            const_q: false
+            restrict_q: false
+            volatile_q: false
+            atomic_q: false
+          specifier:
+            Basic: Int
+        declarator: Base
+      value:
+        Identifier:
+          identifier: x
+    ";
+
+    expresion_test_helper(expr, expected_result, &CParser::parse_expression);
+}
+
+#[test]
+fn sizeof_parenthesised_expression() {
+    let expr = r#"sizeof (x) + 1"#;
+
+    let expected_result = "
+Additive:
+  left_value:
+    SizeOf:
+      value:
+        Paranthesised:
+          Identifier:
+            identifier: x
+  op: Plus
+  right_value:
+    Constant:
+      Number: 1
+    ";
+
+    expresion_test_helper(expr, expected_result, &CParser::parse_expression);
+}
+
+#[test]
+fn sizeof_type_name() {
+    let expr = r#"sizeof (int)"#;
+
+    let expected_result = "
+SizeOfType:
+  type_name:
+    base:
+      qualifiers:
+        const_q: false
+        restrict_q: false
+        volatile_q: false
+        atomic_q: false
+      specifier:
+        Basic: Int
+    declarator: Base
+    ";
+
+    expresion_test_helper(expr, expected_result, &CParser::parse_expression);
+}
@@ -0,0 +1,213 @@
+use crate::parser::{
+    parse_nodes::{
+        declarations::Declaration, gnu_extensions::GnuAttribute, ExternalDeclaration, StringLiteral,
+    },
+    printer::translation_unit_to_c,
+    types::{CTypeOfArgument, CTypeSpecifier},
+};
+use crate::standard::CStandard;
+
+use super::{
+    run_lexer_with_return_that_init_parser, run_lexer_with_return_that_init_parser_with_standard,
+};
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn parse_and_reparse(code: &str) -> String {
+    let parsed = run_lexer_with_return_that_init_parser(code).parse();
+    let printed = translation_unit_to_c(&parsed);
+    println!("{}", printed);
+
+    let reparsed = run_lexer_with_return_that_init_parser(&printed).parse();
+    assert_eq!(reparsed, parsed);
+
+    printed
+}
+
+#[test]
+fn gnu_glibc_prototypes() {
+    // as found in `gcc -E` output of stdio.h and stdlib.h
+    let code = r#"
+__extension__ typedef signed long long int __int64_t;
+extern int fprintf (void *__restrict __stream,
+      const char *__restrict __format, ...);
+extern int fscanf (void *__restrict __stream,
+         const char *__restrict __format, ...) __asm__ ("" "__isoc99_fscanf")
+                               ;
+extern void exit (int __status) __attribute__ ((__nothrow__ , __leaf__)) __attribute__ ((__noreturn__));
+extern __inline __attribute__ ((__gnu_inline__)) int
+atoi (const char *__nptr)
+{
+  return (int) strtol (__nptr, (char **) ((void *)0), 10);
+}
+static int counter __attribute__((unused, section(".counters"), visibility("hidden"))) = 0;
+"#;
+
+    let parsed = run_lexer_with_return_that_init_parser(code).parse();
+    assert_eq!(parsed.len(), 6);
+
+    let declarations: Vec<&Declaration> = parsed
+        .iter()
+        .filter_map(|external| match &*external.inner {
+            ExternalDeclaration::Declaration(declaration) => Some(&*declaration.inner),
+            ExternalDeclaration::FunctionDefinition(_) => None,
+        })
+        .collect();
+
+    let Declaration::Declaration { specifiers, .. } = declarations[0] else {
+        panic!("expected typedef declaration");
+    };
+    assert!(specifiers.storage.typedef_c);
+
+    let Declaration::Declaration { init, .. } = declarations[2] else {
+        panic!("expected fscanf declaration");
+    };
+    assert_eq!(
+        init[0].0.asm_label,
+        Some(StringLiteral {
+            value: "__isoc99_fscanf".to_string()
+        })
+    );
+
+    let Declaration::Declaration { init, .. } = declarations[3] else {
+        panic!("expected exit declaration");
+    };
+    let attributes: Vec<GnuAttribute> = init[0]
+        .0
+        .attributes
+        .iter()
+        .map(|attribute| *attribute.inner.clone())
+        .collect();
+    assert!(
+        matches!(&attributes[0], GnuAttribute::Other { name, .. } if name.identifier == "nothrow")
+    );
+    assert!(
+        matches!(&attributes[1], GnuAttribute::Other { name, .. } if name.identifier == "leaf")
+    );
+    assert_eq!(attributes[2], GnuAttribute::NoReturn);
+
+    let Declaration::Declaration { init, .. } = declarations[4] else {
+        panic!("expected counter declaration");
+    };
+    let attributes: Vec<GnuAttribute> = init[0]
+        .0
+        .attributes
+        .iter()
+        .map(|attribute| *attribute.inner.clone())
+        .collect();
+    assert_eq!(
+        attributes,
+        vec![
+            GnuAttribute::Unused,
+            GnuAttribute::Section(StringLiteral {
+                value: ".counters".to_string()
+            }),
+            GnuAttribute::Visibility(StringLiteral {
+                value: "hidden".to_string()
+            }),
+        ]
+    );
+
+    let printed = parse_and_reparse(code);
+    assert!(printed.contains(r#"__asm__("__isoc99_fscanf")"#));
+    assert!(printed.contains("__attribute__((nothrow, leaf, noreturn))"));
+}
+
+#[test]
+fn gnu_struct_attributes() {
+    let code = r#"
+struct __attribute__((packed)) header {
+    char tag;
+    int length __attribute__((aligned(8)));
+    __extension__ unsigned long long big;
+} __attribute__((aligned));
+"#;
+
+    let parsed = run_lexer_with_return_that_init_parser(code).parse();
+    let ExternalDeclaration::Declaration(declaration) = &*parsed[0].inner else {
+        panic!("expected declaration");
+    };
+    let Declaration::Declaration { specifiers, .. } = &*declaration.inner else {
+        panic!("expected declaration");
+    };
+    let CTypeSpecifier::StructOrUnion(struct_type) = &specifiers.specifiers else {
+        panic!("expected struct");
+    };
+    let attributes: Vec<GnuAttribute> = struct_type
+        .attributes
+        .iter()
+        .map(|attribute| *attribute.inner.clone())
+        .collect();
+    assert_eq!(
+        attributes,
+        vec![GnuAttribute::Packed, GnuAttribute::Aligned(None)]
+    );
+
+    let printed = parse_and_reparse(code);
+    assert!(printed.starts_with("struct __attribute__((packed, aligned)) header {"));
+    assert!(printed.contains("int length __attribute__((aligned(8)));"));
+}
+
+#[test]
+fn gnu_typeof() {
+    let code = r#"
+int main() {
+    int a = 3;
+    __typeof__(a) b = a;
+    __typeof__(int *) c = &b;
+    const __typeof__(a + 1) d = __extension__ 4;
+    return sizeof(__typeof__(b));
+}
+"#;
+
+    let printed = parse_and_reparse(code);
+    assert!(printed.contains("__typeof__(a) b = a;"));
+    assert!(printed.contains("__typeof__(int *) c = &b;"));
+    assert!(printed.contains("const __typeof__(a + 1) d = 4;"));
+    assert!(printed.contains("return sizeof(__typeof__(b));"));
+
+    // only typeof of a type name or an expression
+    let mut parser = run_lexer_with_return_that_init_parser("__typeof__(unsigned char)");
+    assert!(matches!(
+        parser.parse_typeof_specifier().argument,
+        CTypeOfArgument::TypeName(_)
+    ));
+}
+
+#[test]
+fn gnu_statement_expression_and_computed_goto() {
+    let code = r#"
+int main() {
+    int a = 3;
+    int b = ({
+        int t = a;
+        t + 1;
+    });
+    void *target = &&done;
+    goto *target;
+done:
+    return b;
+}
+"#;
+
+    let parsed =
+        run_lexer_with_return_that_init_parser_with_standard(code, CStandard::Gnu11).parse();
+    let printed = translation_unit_to_c(&parsed);
+    println!("{}", printed);
+
+    let reparsed =
+        run_lexer_with_return_that_init_parser_with_standard(&printed, CStandard::Gnu11).parse();
+    assert_eq!(reparsed, parsed);
+
+    assert!(printed.contains("int b = ({"));
+    assert!(printed.contains("t + 1;"));
+    assert!(printed.contains("void *target = &&done;"));
+    assert!(printed.contains("goto *target;"));
+}
+
+#[test]
+#[should_panic]
+fn gnu_statement_expression_rejected_in_c11() {
+    run_lexer_with_return_that_init_parser("int main() { return ({ 1; }); }").parse();
+}
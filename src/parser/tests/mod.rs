@@ -1,10 +1,16 @@
 use super::CParser;
-use crate::lexer::Lexer;
+use crate::{lexer::Lexer, standard::CStandard};
 
+mod ast_json;
+mod c23;
 mod decl;
 mod expr;
+mod gnu_extensions;
+mod partial;
+mod printer;
 mod statement_tests;
 mod type_names;
+mod visitor;
 mod whole_tests;
 
 fn init() {
@@ -18,3 +24,16 @@ pub(crate) fn run_lexer_with_return_that_init_parser(code: &str) -> CParser {
 
     CParser::new(lexed)
 }
+
+pub(crate) fn run_lexer_with_return_that_init_parser_with_standard(
+    code: &str,
+    standard: CStandard,
+) -> CParser {
+    init();
+
+    let lexed = Lexer::new()
+        .with_standard(standard)
+        .string_to_token_arr(code.to_string());
+
+    CParser::new(lexed).with_standard(standard)
+}
@@ -0,0 +1,111 @@
+use crate::lexer::Lexer;
+use crate::parser::{parse_nodes::ExternalDeclaration, CParser};
+
+use super::init;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn parser_for(code: &str) -> CParser {
+    init();
+
+    let lexed = Lexer::new().string_to_token_arr(code.to_string());
+    CParser::from_token_slice(&lexed)
+}
+
+#[test]
+fn partial_single_constructs() {
+    let declaration = parser_for("static const int x = 5;").parse_partial_declaration();
+    assert!(declaration.node.is_some());
+    assert_eq!(declaration.diagnostics, vec![]);
+
+    let statement = parser_for("if (a) { b = 1; }").parse_partial_statement();
+    assert!(statement.node.is_some());
+    assert_eq!(statement.diagnostics, vec![]);
+
+    let expression = parser_for("a + b * 2").parse_partial_expression();
+    assert!(expression.node.is_some());
+    assert_eq!(expression.diagnostics, vec![]);
+
+    let trailing = parser_for("a + b c").parse_partial_expression();
+    assert!(trailing.node.is_some());
+    assert_eq!(trailing.diagnostics.len(), 1);
+    assert!(!trailing.diagnostics[0].incomplete);
+}
+
+#[test]
+fn partial_incomplete_input() {
+    let expression = parser_for("a + (b *").parse_partial_expression();
+    assert_eq!(expression.node.unwrap().to_c(), "a + (b)");
+    assert_eq!(expression.diagnostics.len(), 1);
+    assert!(expression.diagnostics[0].incomplete);
+
+    let statement = parser_for("while (x) { x = x -").parse_partial_statement();
+    assert_eq!(
+        statement.node.unwrap().to_c(),
+        "while (x) {\n    x = x;\n}\n"
+    );
+    assert!(statement.diagnostics[0].incomplete);
+
+    let declaration = parser_for("int table[] = {1, 2").parse_partial_declaration();
+    assert_eq!(declaration.node.unwrap().to_c(), "int table[] = {1, 2};\n");
+    assert!(declaration.diagnostics[0].incomplete);
+}
+
+#[test]
+fn partial_node_after_an_error() {
+    let expression = parser_for("a * (b + ]) - c").parse_partial_expression();
+    let node = expression.node.unwrap();
+    assert_eq!(node.to_c(), "a * (b)");
+    assert_eq!((node.start().line, node.start().collumn), (0, 0));
+    assert_eq!(expression.diagnostics.len(), 1);
+    assert!(!expression.diagnostics[0].incomplete);
+}
+
+#[test]
+fn partial_translation_unit_recovers() {
+    let code = r#"
+typedef int number;
+int broken(void) { return 1 + ; }
+int also_broken = ) ;
+number working(number a) { return a; }
+int unfinished(void) { if (
+"#;
+    let result = parser_for(code).parse_partial_translation_unit();
+
+    let translation_unit = result.node.unwrap().translation_unit;
+    assert_eq!(translation_unit.len(), 3);
+    assert!(matches!(
+        *translation_unit[1].inner,
+        ExternalDeclaration::FunctionDefinition(_)
+    ));
+
+    assert_eq!(result.diagnostics.len(), 3);
+    assert_eq!(result.diagnostics[0].location.line, 2);
+    assert_eq!(result.diagnostics[1].location.line, 3);
+    assert!(!result.diagnostics[1].incomplete);
+    assert!(result.diagnostics[2].incomplete);
+    // the unfinished function is kept up to where it was cut off
+    assert!(matches!(
+        *translation_unit[2].inner,
+        ExternalDeclaration::FunctionDefinition(_)
+    ));
+}
+
+#[test]
+fn partial_recovery_invents_no_declarations() {
+    let parameter = parser_for("int f(int a, ").parse_partial_declaration();
+    assert_eq!(parameter.node.unwrap().to_c(), "int f(int a);\n");
+    assert!(parameter.diagnostics[0].incomplete);
+
+    for code in ["( ( (", "a + * / b", "x ? y"] {
+        let declaration = parser_for(code).parse_partial_declaration();
+        assert!(declaration.node.is_none(), "{} gave a declaration", code);
+        assert_eq!(declaration.diagnostics.len(), 1);
+    }
+
+    // implicit int is left to C89 style declarations and K&R definitions
+    let register = parser_for("register n;").parse_partial_declaration();
+    assert_eq!(register.node.unwrap().to_c(), "register int n;\n");
+    assert_eq!(register.diagnostics, vec![]);
+}
@@ -0,0 +1,195 @@
+use crate::parser::printer::translation_unit_to_c;
+
+use super::run_lexer_with_return_that_init_parser;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+/// parse -> print -> parse has to give the same AST, printing again the same source
+fn round_trip_test_helper(code: &str) -> String {
+    let parsed = run_lexer_with_return_that_init_parser(code).parse();
+
+    let printed = translation_unit_to_c(&parsed);
+    println!("{}", printed);
+
+    let reparsed = run_lexer_with_return_that_init_parser(&printed).parse();
+    assert_eq!(reparsed, parsed);
+    assert_eq!(translation_unit_to_c(&reparsed), printed);
+
+    printed
+}
+
+#[test]
+fn printer_whole_program() {
+    let code = r#"
+int add(int a, int b) {
+    return a + b;
+}
+int main() {
+    printf("hello \"world\"\n");
+    int c = add(4, 5), d;
+    if (8 == 9){
+        return 1;
+    } else if (c) d = 3; else {
+        return 0;
+    }
+    return c;
+}"#;
+
+    let expected = r#"int add(int a, int b) {
+    return a + b;
+}
+
+int main() {
+    printf("hello \"world\"\n");
+    int c = add(4, 5), d;
+    if (8 == 9) {
+        return 1;
+    } else if (c)
+        d = 3;
+    else {
+        return 0;
+    }
+    return c;
+}
+"#;
+
+    assert_eq!(round_trip_test_helper(code), expected);
+}
+
+#[test]
+fn printer_declarators() {
+    let code = r#"
+extern int *a[3];
+static int (*b)[3];
+int (*signal(int sig, void (*func)(int)))(int);
+char *const *volatile c;
+int d[2][3], e = 5;
+typedef unsigned long long size;
+size f(const char *restrict str, ...);
+int g(int [static 4], void *(*)(long double));
+_Alignas(16) long h;
+"#;
+
+    let printed = round_trip_test_helper(code);
+    assert!(printed.contains("int (*signal(int sig, void (*func)(int)))(int);"));
+    assert!(printed.contains("int d[2][3], e = 5;"));
+}
+
+#[test]
+fn printer_struct_and_enum() {
+    let code = r#"
+struct point {
+    int x, y;
+    unsigned flag : 1;
+    struct { char c; } inner;
+};
+union value { int i; float f; };
+enum color { RED, GREEN = 4, BLUE, };
+struct point p = { .x = 1, .y = 2 };
+int arr[4] = { [0] = 1, 2, [3] = 4 };
+"#;
+
+    let printed = round_trip_test_helper(code);
+    let expected_struct = r#"struct point {
+    int x, y;
+    unsigned int flag : 1;
+    struct {
+        char c;
+    } inner;
+};
+"#;
+    assert!(printed.starts_with(expected_struct));
+}
+
+#[test]
+fn printer_struct_member_alignment() {
+    let code = r#"
+struct aligned {
+    _Alignas(16) char c;
+    _Alignas(long) int i;
+};
+"#;
+
+    let printed = round_trip_test_helper(code);
+    let expected = r#"struct aligned {
+    _Alignas(16) char c;
+    _Alignas(long) int i;
+};
+"#;
+    assert_eq!(printed, expected);
+}
+
+#[test]
+fn printer_statements() {
+    let code = r#"
+int main() {
+    int i;
+    for (i = 0; i < 10; i++) {
+        if (i == 5)
+            continue;
+    }
+    for (int j = 0;;) break;
+    while (i) i--;
+    do { i++; } while (i < 3);
+    switch (i) {
+        case 1:
+        case 2:
+            i = 3;
+            break;
+        default:
+            ;
+    }
+    goto end;
+end:
+    return i;
+}"#;
+
+    let expected = r#"int main() {
+    int i;
+    for (i = 0; i < 10; i++) {
+        if (i == 5)
+            continue;
+    }
+    for (int j = 0;;)
+        break;
+    while (i)
+        i--;
+    do {
+        i++;
+    } while (i < 3);
+    switch (i) {
+        case 1:
+        case 2:
+            i = 3;
+            break;
+        default:
+            ;
+    }
+    goto end;
+    end:
+    return i;
+}
+"#;
+
+    assert_eq!(round_trip_test_helper(code), expected);
+}
+
+#[test]
+fn printer_expressions() {
+    let code = r#"
+int main() {
+    a = b = c ? d, e : f ? g : h;
+    x = (a + b) * c - d / (e % f) << 2;
+    y = a || b && c | d ^ e & f;
+    z = - -a + !~b - *&c;
+    w = (int)-x + sizeof(int *) + sizeof (x) + sizeof x + _Alignof(long);
+    v = p->q.r[3](1, 2)++ + --u;
+    t = (struct point){ 1, 2 }.x;
+    s += a <= b != c > d;
+}"#;
+
+    let printed = round_trip_test_helper(code);
+    assert!(printed.contains("y = a || b && c | d ^ e & f;"));
+    assert!(printed.contains("z = - -a + !~b - *&c;"));
+}
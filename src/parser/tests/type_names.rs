@@ -326,3 +326,35 @@ declarator:
 
     type_name_test_helper(expr, expected_result);
 }
+
+#[test]
+fn long_long_in_any_order() {
+    let expr = r#"long int long"#;
+
+    let expected_result = "
+base:
+    qualifiers:
+      const_q: false
+      restrict_q: false
+      volatile_q: false
+      atomic_q: false
+    specifier:
+      Basic: LongLong
+declarator: Base";
+
+    type_name_test_helper(expr, expected_result);
+}
+
+#[test]
+#[should_panic]
+fn repeated_int_is_no_long_long() {
+    let mut simple_parser = run_lexer_with_return_that_init_parser("long int int");
+    simple_parser.parse_type_name();
+}
+
+#[test]
+#[should_panic]
+fn repeated_unsigned_is_invalid() {
+    let mut simple_parser = run_lexer_with_return_that_init_parser("unsigned unsigned int");
+    simple_parser.parse_type_name();
+}
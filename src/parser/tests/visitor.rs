@@ -0,0 +1,105 @@
+use crate::parser::{
+    parse_nodes::{declarations::Declarator, expressions::CExpression, statements::Statement},
+    printer::translation_unit_to_c,
+    span::Spanned,
+    visitor::{walk_declarator, walk_expression, walk_statement, Visitor},
+    visitor_mut::{walk_expression_mut, VisitorMut},
+};
+
+use super::run_lexer_with_return_that_init_parser;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+/// collects called function names and declared identifiers, counts return statements
+#[derive(Default)]
+struct Collector {
+    calls: Vec<String>,
+    declared: Vec<String>,
+    returns: usize,
+}
+
+impl Visitor for Collector {
+    fn visit_expression(&mut self, expr: &Spanned<CExpression>) {
+        if let CExpression::FunctionCall { function, .. } = &*expr.inner {
+            if let CExpression::Identifier(ident) = &*function.inner {
+                self.calls.push(ident.identifier.clone());
+            }
+        }
+        walk_expression(self, expr);
+    }
+    fn visit_declarator(&mut self, declarator: &Spanned<Declarator>) {
+        self.declared.push(declarator.base.identifier.clone());
+        walk_declarator(self, declarator);
+    }
+    fn visit_statement(&mut self, statement: &Spanned<Statement>) {
+        if let Statement::Return(_) = &*statement.inner {
+            self.returns += 1;
+        }
+        walk_statement(self, statement);
+    }
+}
+
+#[test]
+fn visitor_collects_nested_nodes() {
+    let code = r#"
+struct s { int (*cb)(int arg); };
+int add(int a, int b) {
+    return a + b;
+}
+int main() {
+    int arr[4] = { [0] = add(1, 2) };
+    for (int i = 0; i < 3; i++) {
+        if (i) return add(add(i, 1), sizeof(int));
+    }
+    return (int){ add(3, 4) };
+}"#;
+
+    let parsed = run_lexer_with_return_that_init_parser(code).parse();
+
+    let mut collector = Collector::default();
+    collector.visit_translation_unit(&parsed);
+
+    assert_eq!(collector.calls, vec!["add", "add", "add", "add"]);
+    assert_eq!(
+        collector.declared,
+        vec!["cb", "arg", "add", "a", "b", "main", "arr", "i"]
+    );
+    assert_eq!(collector.returns, 3);
+}
+
+/// drops redundant parenthesis around identifiers and constants
+struct ParenthesisRemover;
+
+impl VisitorMut for ParenthesisRemover {
+    fn visit_expression_mut(&mut self, expr: &mut Spanned<CExpression>) {
+        walk_expression_mut(self, expr);
+        if let CExpression::Paranthesised(inner) = &*expr.inner {
+            if matches!(
+                &*inner.inner,
+                CExpression::Identifier(_) | CExpression::Constant(_)
+            ) {
+                *expr = inner.clone();
+            }
+        }
+    }
+}
+
+#[test]
+fn visitor_mut_rewrites_expressions() {
+    let code = r#"
+int main() {
+    int x = ((1)) + (y);
+    return (x) * (x + (2));
+}"#;
+
+    let mut parsed = run_lexer_with_return_that_init_parser(code).parse();
+    ParenthesisRemover.visit_translation_unit_mut(&mut parsed);
+
+    let expected = r#"int main() {
+    int x = 1 + y;
+    return x * (x + 2);
+}
+"#;
+    assert_eq!(translation_unit_to_c(&parsed), expected);
+}
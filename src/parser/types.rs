@@ -7,8 +7,11 @@ use crate::lexer::{token_types::CKeyword, CToken};
 
 use super::{
     parse_nodes::{
-        declarations::{Declarator, DerivedDeclarator, StaticAssertDeclaration},
-        expressions::ConstantExpression,
+        declarations::{
+            CAlignmentSpecifier, Declarator, DerivedDeclarator, StaticAssertDeclaration,
+        },
+        expressions::{CExpression, ConstantExpression},
+        gnu_extensions::GnuAttribute,
         Identifier,
     },
     span::Spanned,
@@ -95,9 +98,9 @@ impl CParser {
             crate::lexer::token_types::CTokenType::Keyword(keyword) => {
                 let possible_type_def_start = [
                     VOID, CHAR, SHORT, INT, LONG, FLOAT, DOUBLE, SIGNED, UNSIGNED, BOOL, COMPLEX,
-                    ATOMIC, STRUCT, UNION, ENUM, CONST, RESTRICT, VOLATILE,
+                    ATOMIC, STRUCT, UNION, ENUM, CONST, RESTRICT, VOLATILE, TYPEOF,
                 ];
-                possible_type_def_start.contains(keyword)
+                possible_type_def_start.contains(keyword) || *keyword == TYPEOF_UNQUAL
             }
             crate::lexer::token_types::CTokenType::Identifier => self.is_typedef(&token.original),
             crate::lexer::token_types::CTokenType::Constant => false,
@@ -216,10 +219,6 @@ representation or alignment requirements.
 fn basic_ctype_alias_checker(cmp: &[CKeyword]) -> Option<CBasicTypes> {
     use CKeyword::*;
 
-    let mut cmp = cmp.to_vec();
-    cmp.dedup_by(|a, b| a == b);
-    let cmp = &cmp;
-
     if is_semi_equal_keywords(&[VOID], cmp) {
         Some(CBasicTypes::Void)
     } else if is_semi_equal_keywords(&[CHAR], cmp) {
@@ -286,18 +285,14 @@ fn basic_ctype_alias_checker(cmp: &[CKeyword]) -> Option<CBasicTypes> {
     }
 }
 
+/// every keyword appears as often in `cmp` as in `base`, the order does not matter
 fn is_semi_equal_keywords(base: &[CKeyword], cmp: &[CKeyword]) -> bool {
     if base.len() != cmp.len() {
         return false;
     }
 
-    for key in base {
-        if !cmp.contains(key) {
-            return false;
-        }
-    }
-
-    true
+    let count = |list: &[CKeyword], key: &CKeyword| list.iter().filter(|k| *k == key).count();
+    base.iter().all(|key| count(base, key) == count(cmp, key))
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -446,6 +441,9 @@ impl CParser {
                     } else if basic_specifiers_possible.contains(&keyword) {
                         self.advance_idx();
                         type_keyword_list.push(keyword.clone());
+                    } else if keyword == CKeyword::ATTRIBUTE {
+                        // attributes end the list, left for the caller
+                        break;
                     } else {
                         self.error_unexpected(
                             self.current_token(),
@@ -480,6 +478,9 @@ impl CParser {
                 self.idx -= 1; // for detection in self.parse_struct_or_union_specifier()
                 specifier = CTypeSpecifier::StructOrUnion(self.parse_struct_or_union_specifier());
                 // done!("still need to impl struct or union specifier in type name")
+            } else if keyword == CKeyword::TYPEOF || keyword == CKeyword::TYPEOF_UNQUAL {
+                self.idx -= 1;
+                specifier = CTypeSpecifier::TypeOf(self.parse_typeof_specifier());
             } else {
                 // unexpected keyword in specifier qualifier list
                 self.error_unexpected(
@@ -545,6 +546,30 @@ pub(crate) enum CTypeSpecifier {
     Enum(Spanned<CEnumType>),
     Typedefed(Identifier),
     Atomic(Spanned<CTypeName>),
+    TypeOf(Spanned<CTypeOf>),
+}
+
+/*
+(6.7.2.5) typeof-specifier:
+    typeof ( typeof-specifier-argument )
+    typeof_unqual ( typeof-specifier-argument )
+(6.7.2.5) typeof-specifier-argument:
+    expression
+    type-name
+GNU spells typeof as __typeof__ in every mode
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct CTypeOf {
+    pub(crate) argument: CTypeOfArgument,
+    /// C23 typeof_unqual drops the qualifiers of the argument
+    #[serde(default)]
+    pub(crate) unqualified: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum CTypeOfArgument {
+    TypeName(Spanned<CTypeName>),
+    Expression(Spanned<CExpression>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -552,6 +577,8 @@ pub(crate) struct CStructOrUnionType {
     pub(crate) struct_type: CStructOrUnionTypeType,
     pub(crate) ident: Option<Identifier>,
     pub(crate) declarations: Vec<CSructDeclaration>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) attributes: Vec<Spanned<GnuAttribute>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -564,6 +591,9 @@ pub(crate) enum CStructOrUnionTypeType {
 pub(crate) enum CSructDeclaration {
     StaticAssertDeclaration(Spanned<StaticAssertDeclaration>),
     StructDeclaration {
+        /// `_Alignas` in front of the member, part of the specifier-qualifier-list since C11
+        #[serde(default)]
+        alignment: Option<Spanned<CAlignmentSpecifier>>,
         specifier_qualifier: Spanned<CTypeBasic>,
         delcarator_list: Vec<Spanned<CStructDeclarator>>,
     },
@@ -0,0 +1,593 @@
+use super::{
+    parse_nodes::{
+        declarations::{
+            CAlignmentSpecifier, Declaration, DeclarationSpecifiers, Declarator, DerivedDeclarator,
+            Designator, Initializer, ParameterDeclaration, StaticAssertDeclaration,
+        },
+        expressions::{CExpression, ConstantExpression, GenericAssociation},
+        gnu_extensions::GnuAttribute,
+        statements::{CompoundItem, Statement},
+        ExternalDeclaration, FunctionDefinition, TranslationUnit,
+    },
+    span::Spanned,
+    types::{
+        CEnumEnumerator, CEnumType, CSructDeclaration, CStructDeclarator, CStructOrUnionType,
+        CTypeBasic, CTypeName, CTypeOfArgument, CTypeSpecifier,
+    },
+};
+
+/*
+Read only traversal of the AST.
+
+Every visit_* method defaults to the matching walk_* function, which visits all children.
+Override only the nodes of interest and call the walk_* function from the override
+to keep descending into the children.
+Nodes are handed over as Spanned wherever the AST stores them with a span.
+*/
+pub(crate) trait Visitor {
+    fn visit_translation_unit(&mut self, translation_unit: &TranslationUnit) {
+        walk_translation_unit(self, translation_unit)
+    }
+    fn visit_external_declaration(&mut self, external: &Spanned<ExternalDeclaration>) {
+        walk_external_declaration(self, external)
+    }
+    fn visit_function_definition(&mut self, function: &Spanned<FunctionDefinition>) {
+        walk_function_definition(self, function)
+    }
+    fn visit_declaration(&mut self, declaration: &Spanned<Declaration>) {
+        walk_declaration(self, declaration)
+    }
+    fn visit_static_assert(&mut self, static_assert: &Spanned<StaticAssertDeclaration>) {
+        walk_static_assert(self, static_assert)
+    }
+    fn visit_declaration_specifiers(&mut self, specifiers: &DeclarationSpecifiers) {
+        walk_declaration_specifiers(self, specifiers)
+    }
+    fn visit_alignment_specifier(&mut self, alignment: &Spanned<CAlignmentSpecifier>) {
+        walk_alignment_specifier(self, alignment)
+    }
+    fn visit_gnu_attribute(&mut self, attribute: &Spanned<GnuAttribute>) {
+        walk_gnu_attribute(self, attribute)
+    }
+    fn visit_type_specifier(&mut self, specifier: &CTypeSpecifier) {
+        walk_type_specifier(self, specifier)
+    }
+    fn visit_struct_or_union(&mut self, struct_or_union: &Spanned<CStructOrUnionType>) {
+        walk_struct_or_union(self, struct_or_union)
+    }
+    fn visit_struct_declaration(&mut self, declaration: &CSructDeclaration) {
+        walk_struct_declaration(self, declaration)
+    }
+    fn visit_struct_declarator(&mut self, declarator: &Spanned<CStructDeclarator>) {
+        walk_struct_declarator(self, declarator)
+    }
+    fn visit_enum(&mut self, enum_type: &Spanned<CEnumType>) {
+        walk_enum(self, enum_type)
+    }
+    fn visit_enumerator(&mut self, enumerator: &Spanned<CEnumEnumerator>) {
+        walk_enumerator(self, enumerator)
+    }
+    fn visit_type_name(&mut self, type_name: &CTypeName) {
+        walk_type_name(self, type_name)
+    }
+    fn visit_type_basic(&mut self, basic: &Spanned<CTypeBasic>) {
+        walk_type_basic(self, basic)
+    }
+    fn visit_declarator(&mut self, declarator: &Spanned<Declarator>) {
+        walk_declarator(self, declarator)
+    }
+    fn visit_derived_declarator(&mut self, derived: &DerivedDeclarator) {
+        walk_derived_declarator(self, derived)
+    }
+    fn visit_parameter_declaration(&mut self, parameter: &Spanned<ParameterDeclaration>) {
+        walk_parameter_declaration(self, parameter)
+    }
+    fn visit_initializer(&mut self, initializer: &Spanned<Initializer>) {
+        walk_initializer(self, initializer)
+    }
+    fn visit_designator(&mut self, designator: &Designator) {
+        walk_designator(self, designator)
+    }
+    fn visit_statement(&mut self, statement: &Spanned<Statement>) {
+        walk_statement(self, statement)
+    }
+    fn visit_compound_item(&mut self, item: &CompoundItem) {
+        walk_compound_item(self, item)
+    }
+    fn visit_expression(&mut self, expr: &Spanned<CExpression>) {
+        walk_expression(self, expr)
+    }
+    fn visit_constant_expression(&mut self, expr: &ConstantExpression) {
+        walk_constant_expression(self, expr)
+    }
+    fn visit_generic_association(&mut self, association: &GenericAssociation) {
+        walk_generic_association(self, association)
+    }
+}
+
+pub(crate) fn walk_translation_unit<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    translation_unit: &TranslationUnit,
+) {
+    for external in translation_unit {
+        visitor.visit_external_declaration(external);
+    }
+}
+
+pub(crate) fn walk_external_declaration<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    external: &Spanned<ExternalDeclaration>,
+) {
+    match &*external.inner {
+        ExternalDeclaration::FunctionDefinition(function) => {
+            visitor.visit_function_definition(function)
+        }
+        ExternalDeclaration::Declaration(declaration) => visitor.visit_declaration(declaration),
+    }
+}
+
+pub(crate) fn walk_function_definition<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    function: &Spanned<FunctionDefinition>,
+) {
+    visitor.visit_declaration_specifiers(&function.function_specifiers);
+    visitor.visit_declarator(&function.declarator);
+    for declaration in &function.declarations {
+        visitor.visit_declaration(declaration);
+    }
+    visitor.visit_statement(&function.body);
+}
+
+pub(crate) fn walk_declaration<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    declaration: &Spanned<Declaration>,
+) {
+    match &*declaration.inner {
+        Declaration::Declaration { specifiers, init } => {
+            visitor.visit_declaration_specifiers(specifiers);
+            for (declarator, initializer) in init {
+                visitor.visit_declarator(declarator);
+                if let Some(initializer) = initializer {
+                    visitor.visit_initializer(initializer);
+                }
+            }
+        }
+        Declaration::StaticAssertDeclaration(static_assert) => {
+            visitor.visit_static_assert(static_assert)
+        }
+    }
+}
+
+pub(crate) fn walk_static_assert<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    static_assert: &Spanned<StaticAssertDeclaration>,
+) {
+    visitor.visit_constant_expression(&static_assert.expression);
+}
+
+pub(crate) fn walk_declaration_specifiers<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    specifiers: &DeclarationSpecifiers,
+) {
+    for attribute in &specifiers.attributes {
+        visitor.visit_gnu_attribute(attribute);
+    }
+    if let Some(alignment) = &specifiers.alignment {
+        visitor.visit_alignment_specifier(alignment);
+    }
+    visitor.visit_type_specifier(&specifiers.specifiers);
+}
+
+pub(crate) fn walk_gnu_attribute<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    attribute: &Spanned<GnuAttribute>,
+) {
+    match &*attribute.inner {
+        GnuAttribute::Aligned(Some(alignment)) => visitor.visit_constant_expression(alignment),
+        GnuAttribute::Other { arguments, .. } => {
+            for argument in arguments {
+                visitor.visit_expression(argument);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub(crate) fn walk_alignment_specifier<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    alignment: &Spanned<CAlignmentSpecifier>,
+) {
+    match &*alignment.inner {
+        CAlignmentSpecifier::ToType(type_name) => visitor.visit_type_name(type_name),
+        CAlignmentSpecifier::ToExpression(expr) => visitor.visit_constant_expression(expr),
+    }
+}
+
+pub(crate) fn walk_type_specifier<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    specifier: &CTypeSpecifier,
+) {
+    match specifier {
+        CTypeSpecifier::Basic(_) | CTypeSpecifier::Typedefed(_) => {}
+        CTypeSpecifier::StructOrUnion(struct_or_union) => {
+            visitor.visit_struct_or_union(struct_or_union)
+        }
+        CTypeSpecifier::Enum(enum_type) => visitor.visit_enum(enum_type),
+        CTypeSpecifier::Atomic(type_name) => visitor.visit_type_name(type_name),
+        CTypeSpecifier::TypeOf(type_of) => match &type_of.argument {
+            CTypeOfArgument::TypeName(type_name) => visitor.visit_type_name(type_name),
+            CTypeOfArgument::Expression(expr) => visitor.visit_expression(expr),
+        },
+    }
+}
+
+pub(crate) fn walk_struct_or_union<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    struct_or_union: &Spanned<CStructOrUnionType>,
+) {
+    for attribute in &struct_or_union.attributes {
+        visitor.visit_gnu_attribute(attribute);
+    }
+    for declaration in &struct_or_union.declarations {
+        visitor.visit_struct_declaration(declaration);
+    }
+}
+
+pub(crate) fn walk_struct_declaration<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    declaration: &CSructDeclaration,
+) {
+    match declaration {
+        CSructDeclaration::StaticAssertDeclaration(static_assert) => {
+            visitor.visit_static_assert(static_assert)
+        }
+        CSructDeclaration::StructDeclaration {
+            alignment,
+            specifier_qualifier,
+            delcarator_list,
+        } => {
+            if let Some(alignment) = alignment {
+                visitor.visit_alignment_specifier(alignment);
+            }
+            visitor.visit_type_basic(specifier_qualifier);
+            for declarator in delcarator_list {
+                visitor.visit_struct_declarator(declarator);
+            }
+        }
+    }
+}
+
+pub(crate) fn walk_struct_declarator<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    declarator: &Spanned<CStructDeclarator>,
+) {
+    match &*declarator.inner {
+        CStructDeclarator::Declarator(declarator) => visitor.visit_declarator(declarator),
+        CStructDeclarator::BitField { declarator, expr } => {
+            if let Some(declarator) = declarator {
+                visitor.visit_declarator(declarator);
+            }
+            visitor.visit_constant_expression(expr);
+        }
+    }
+}
+
+pub(crate) fn walk_enum<V: Visitor + ?Sized>(visitor: &mut V, enum_type: &Spanned<CEnumType>) {
+    for enumerator in &enum_type.enumerators {
+        visitor.visit_enumerator(enumerator);
+    }
+}
+
+pub(crate) fn walk_enumerator<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    enumerator: &Spanned<CEnumEnumerator>,
+) {
+    if let Some(value) = &enumerator.const_assignment {
+        visitor.visit_constant_expression(value);
+    }
+}
+
+pub(crate) fn walk_type_name<V: Visitor + ?Sized>(visitor: &mut V, type_name: &CTypeName) {
+    visitor.visit_type_basic(&type_name.base);
+    visitor.visit_derived_declarator(&type_name.declarator);
+}
+
+pub(crate) fn walk_type_basic<V: Visitor + ?Sized>(visitor: &mut V, basic: &Spanned<CTypeBasic>) {
+    visitor.visit_type_specifier(&basic.specifier);
+}
+
+pub(crate) fn walk_declarator<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    declarator: &Spanned<Declarator>,
+) {
+    visitor.visit_derived_declarator(&declarator.derive);
+    for attribute in &declarator.attributes {
+        visitor.visit_gnu_attribute(attribute);
+    }
+}
+
+pub(crate) fn walk_derived_declarator<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    derived: &DerivedDeclarator,
+) {
+    match derived {
+        DerivedDeclarator::Base => {}
+        DerivedDeclarator::Pointer { to, .. } => visitor.visit_derived_declarator(to),
+        DerivedDeclarator::Array { size_expr, to, .. } => {
+            if let Some(size_expr) = size_expr {
+                visitor.visit_expression(size_expr);
+            }
+            visitor.visit_derived_declarator(to);
+        }
+        DerivedDeclarator::FunctionType {
+            parameter_type_list,
+            to,
+        } => {
+            for parameter in &parameter_type_list.parameter_list {
+                visitor.visit_parameter_declaration(parameter);
+            }
+            visitor.visit_derived_declarator(to);
+        }
+        DerivedDeclarator::FunctionIdentified { to, .. } => visitor.visit_derived_declarator(to),
+    }
+}
+
+pub(crate) fn walk_parameter_declaration<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    parameter: &Spanned<ParameterDeclaration>,
+) {
+    match &*parameter.inner {
+        ParameterDeclaration::Declarator {
+            specifiers,
+            declarator,
+        } => {
+            visitor.visit_declaration_specifiers(specifiers);
+            visitor.visit_declarator(declarator);
+        }
+        ParameterDeclaration::AbstractDeclarator {
+            specifiers,
+            abstract_declarator,
+        } => {
+            visitor.visit_declaration_specifiers(specifiers);
+            if let Some(derived) = abstract_declarator {
+                visitor.visit_derived_declarator(derived);
+            }
+        }
+    }
+}
+
+pub(crate) fn walk_initializer<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    initializer: &Spanned<Initializer>,
+) {
+    match &*initializer.inner {
+        Initializer::Single(expr) => visitor.visit_expression(expr),
+        Initializer::Compound(list) => {
+            for (designators, initializer) in list {
+                for designator in designators {
+                    visitor.visit_designator(designator);
+                }
+                visitor.visit_initializer(initializer);
+            }
+        }
+    }
+}
+
+pub(crate) fn walk_designator<V: Visitor + ?Sized>(visitor: &mut V, designator: &Designator) {
+    match designator {
+        Designator::Array(index) => visitor.visit_constant_expression(index),
+        Designator::Member(_) => {}
+    }
+}
+
+pub(crate) fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &Spanned<Statement>) {
+    match &*statement.inner {
+        Statement::Labeled { label: _, body } => visitor.visit_statement(body),
+        Statement::SwitchCase {
+            const_expr,
+            statement,
+        } => {
+            visitor.visit_constant_expression(const_expr);
+            visitor.visit_statement(statement);
+        }
+        Statement::SwitchDefault { statement } => visitor.visit_statement(statement),
+        Statement::Compound(items) => {
+            for item in items {
+                visitor.visit_compound_item(item);
+            }
+        }
+        Statement::CExpression(expr) => visitor.visit_expression(expr),
+        Statement::NoneExpr => {}
+        Statement::If {
+            controlling_expr,
+            true_body,
+            else_body,
+        } => {
+            visitor.visit_expression(controlling_expr);
+            visitor.visit_statement(true_body);
+            if let Some(else_body) = else_body {
+                visitor.visit_statement(else_body);
+            }
+        }
+        Statement::Switch {
+            controlling_expr,
+            body,
+        } => {
+            visitor.visit_expression(controlling_expr);
+            visitor.visit_statement(body);
+        }
+        Statement::While {
+            while_type: _,
+            controlling_expr,
+            body,
+        } => {
+            visitor.visit_expression(controlling_expr);
+            visitor.visit_statement(body);
+        }
+        Statement::For {
+            decl_clause,
+            expr_clause,
+            controlling_expr,
+            after_expr,
+            body,
+        } => {
+            if let Some(declaration) = decl_clause {
+                visitor.visit_declaration(declaration);
+            }
+            for expr in [expr_clause, controlling_expr, after_expr]
+                .into_iter()
+                .flatten()
+            {
+                visitor.visit_expression(expr);
+            }
+            visitor.visit_statement(body);
+        }
+        Statement::Goto(_) | Statement::Continue | Statement::Break => {}
+        Statement::GotoIndirect(target) => visitor.visit_expression(target),
+        Statement::Return(expr) => {
+            if let Some(expr) = expr {
+                visitor.visit_expression(expr);
+            }
+        }
+    }
+}
+
+pub(crate) fn walk_compound_item<V: Visitor + ?Sized>(visitor: &mut V, item: &CompoundItem) {
+    match item {
+        CompoundItem::Statement(statement) => visitor.visit_statement(statement),
+        CompoundItem::Declaration(declaration) => visitor.visit_declaration(declaration),
+    }
+}
+
+pub(crate) fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expr: &Spanned<CExpression>) {
+    match &*expr.inner {
+        CExpression::Expression(list)
+        | CExpression::LogicalOr(list)
+        | CExpression::LogicalAnd(list)
+        | CExpression::InclusiveOr(list)
+        | CExpression::ExlusiveOr(list)
+        | CExpression::And(list) => {
+            for expr in list {
+                visitor.visit_expression(expr);
+            }
+        }
+        CExpression::Assignment {
+            to_assign: left,
+            operator: _,
+            value: right,
+        }
+        | CExpression::Equality {
+            left_piece: left,
+            equality_op: _,
+            right_piece: right,
+        }
+        | CExpression::Relational {
+            left_piece: left,
+            equality_op: _,
+            right_piece: right,
+        }
+        | CExpression::Shift {
+            value: left,
+            shift_type: _,
+            shift_amount: right,
+        }
+        | CExpression::Additive {
+            left_value: left,
+            op: _,
+            right_value: right,
+        }
+        | CExpression::Multiplicative {
+            left_value: left,
+            op: _,
+            right_value: right,
+        }
+        | CExpression::ArraySubscription {
+            array: left,
+            index: right,
+        } => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+        CExpression::Ternary {
+            condition,
+            if_true,
+            tern_else,
+        } => {
+            visitor.visit_expression(condition);
+            visitor.visit_expression(if_true);
+            visitor.visit_expression(tern_else);
+        }
+        CExpression::Cast { type_name, value } => {
+            visitor.visit_type_name(type_name);
+            visitor.visit_expression(value);
+        }
+        CExpression::PrefixIncrement { value, .. }
+        | CExpression::PostfixIncrement { value, .. }
+        | CExpression::Unary { value, .. }
+        | CExpression::SizeOf { value }
+        | CExpression::DirectMemberAccess {
+            to_access: value, ..
+        }
+        | CExpression::IndirectMemberAccess {
+            to_access: value, ..
+        }
+        | CExpression::Paranthesised(value) => visitor.visit_expression(value),
+        CExpression::SizeOfType { type_name } | CExpression::AlignOfType { type_name } => {
+            visitor.visit_type_name(type_name)
+        }
+        CExpression::FunctionCall {
+            function,
+            arguments,
+        } => {
+            visitor.visit_expression(function);
+            for argument in arguments {
+                visitor.visit_expression(argument);
+            }
+        }
+        CExpression::TypeInitializer {
+            type_name,
+            initializer_list,
+        } => {
+            visitor.visit_type_name(type_name);
+            visitor.visit_initializer(initializer_list);
+        }
+        CExpression::Identifier(_) | CExpression::Constant(_) | CExpression::StringLiteral(_) => {}
+        CExpression::GenericSelection(generic) => {
+            visitor.visit_expression(&generic.assignment_expression);
+            for association in generic.generic_assoc_list.iter() {
+                visitor.visit_generic_association(association);
+            }
+        }
+        CExpression::StatementExpression(items) => {
+            for item in items.iter() {
+                visitor.visit_compound_item(item);
+            }
+        }
+        CExpression::LabelAddress(_) => {}
+    }
+}
+
+pub(crate) fn walk_constant_expression<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    expr: &ConstantExpression,
+) {
+    visitor.visit_expression(&expr.internal);
+}
+
+pub(crate) fn walk_generic_association<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    association: &GenericAssociation,
+) {
+    match association {
+        GenericAssociation::TypeName {
+            type_name,
+            assignment_expression,
+        } => {
+            visitor.visit_type_name(type_name);
+            visitor.visit_expression(assignment_expression);
+        }
+        GenericAssociation::Default(assignment_expression) => {
+            visitor.visit_expression(assignment_expression)
+        }
+    }
+}
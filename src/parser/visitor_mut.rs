@@ -0,0 +1,614 @@
+use super::{
+    parse_nodes::{
+        declarations::{
+            CAlignmentSpecifier, Declaration, DeclarationSpecifiers, Declarator, DerivedDeclarator,
+            Designator, Initializer, ParameterDeclaration, StaticAssertDeclaration,
+        },
+        expressions::{CExpression, ConstantExpression, GenericAssociation},
+        gnu_extensions::GnuAttribute,
+        statements::{CompoundItem, Statement},
+        ExternalDeclaration, FunctionDefinition, TranslationUnit,
+    },
+    span::Spanned,
+    types::{
+        CEnumEnumerator, CEnumType, CSructDeclaration, CStructDeclarator, CStructOrUnionType,
+        CTypeBasic, CTypeName, CTypeOfArgument, CTypeSpecifier,
+    },
+};
+
+/*
+Mutable traversal of the AST for rewriting passes, mirrors Visitor in visitor.rs.
+
+Every visit_*_mut method defaults to the matching walk_*_mut function.
+A rewrite can replace a whole Spanned node, so the new node can carry its own span.
+*/
+pub(crate) trait VisitorMut {
+    fn visit_translation_unit_mut(&mut self, translation_unit: &mut TranslationUnit) {
+        walk_translation_unit_mut(self, translation_unit)
+    }
+    fn visit_external_declaration_mut(&mut self, external: &mut Spanned<ExternalDeclaration>) {
+        walk_external_declaration_mut(self, external)
+    }
+    fn visit_function_definition_mut(&mut self, function: &mut Spanned<FunctionDefinition>) {
+        walk_function_definition_mut(self, function)
+    }
+    fn visit_declaration_mut(&mut self, declaration: &mut Spanned<Declaration>) {
+        walk_declaration_mut(self, declaration)
+    }
+    fn visit_static_assert_mut(&mut self, static_assert: &mut Spanned<StaticAssertDeclaration>) {
+        walk_static_assert_mut(self, static_assert)
+    }
+    fn visit_declaration_specifiers_mut(&mut self, specifiers: &mut DeclarationSpecifiers) {
+        walk_declaration_specifiers_mut(self, specifiers)
+    }
+    fn visit_alignment_specifier_mut(&mut self, alignment: &mut Spanned<CAlignmentSpecifier>) {
+        walk_alignment_specifier_mut(self, alignment)
+    }
+    fn visit_gnu_attribute_mut(&mut self, attribute: &mut Spanned<GnuAttribute>) {
+        walk_gnu_attribute_mut(self, attribute)
+    }
+    fn visit_type_specifier_mut(&mut self, specifier: &mut CTypeSpecifier) {
+        walk_type_specifier_mut(self, specifier)
+    }
+    fn visit_struct_or_union_mut(&mut self, struct_or_union: &mut Spanned<CStructOrUnionType>) {
+        walk_struct_or_union_mut(self, struct_or_union)
+    }
+    fn visit_struct_declaration_mut(&mut self, declaration: &mut CSructDeclaration) {
+        walk_struct_declaration_mut(self, declaration)
+    }
+    fn visit_struct_declarator_mut(&mut self, declarator: &mut Spanned<CStructDeclarator>) {
+        walk_struct_declarator_mut(self, declarator)
+    }
+    fn visit_enum_mut(&mut self, enum_type: &mut Spanned<CEnumType>) {
+        walk_enum_mut(self, enum_type)
+    }
+    fn visit_enumerator_mut(&mut self, enumerator: &mut Spanned<CEnumEnumerator>) {
+        walk_enumerator_mut(self, enumerator)
+    }
+    fn visit_type_name_mut(&mut self, type_name: &mut CTypeName) {
+        walk_type_name_mut(self, type_name)
+    }
+    fn visit_type_basic_mut(&mut self, basic: &mut Spanned<CTypeBasic>) {
+        walk_type_basic_mut(self, basic)
+    }
+    fn visit_declarator_mut(&mut self, declarator: &mut Spanned<Declarator>) {
+        walk_declarator_mut(self, declarator)
+    }
+    fn visit_derived_declarator_mut(&mut self, derived: &mut DerivedDeclarator) {
+        walk_derived_declarator_mut(self, derived)
+    }
+    fn visit_parameter_declaration_mut(&mut self, parameter: &mut Spanned<ParameterDeclaration>) {
+        walk_parameter_declaration_mut(self, parameter)
+    }
+    fn visit_initializer_mut(&mut self, initializer: &mut Spanned<Initializer>) {
+        walk_initializer_mut(self, initializer)
+    }
+    fn visit_designator_mut(&mut self, designator: &mut Designator) {
+        walk_designator_mut(self, designator)
+    }
+    fn visit_statement_mut(&mut self, statement: &mut Spanned<Statement>) {
+        walk_statement_mut(self, statement)
+    }
+    fn visit_compound_item_mut(&mut self, item: &mut CompoundItem) {
+        walk_compound_item_mut(self, item)
+    }
+    fn visit_expression_mut(&mut self, expr: &mut Spanned<CExpression>) {
+        walk_expression_mut(self, expr)
+    }
+    fn visit_constant_expression_mut(&mut self, expr: &mut ConstantExpression) {
+        walk_constant_expression_mut(self, expr)
+    }
+    fn visit_generic_association_mut(&mut self, association: &mut GenericAssociation) {
+        walk_generic_association_mut(self, association)
+    }
+}
+
+pub(crate) fn walk_translation_unit_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    translation_unit: &mut TranslationUnit,
+) {
+    for external in translation_unit.iter_mut() {
+        visitor.visit_external_declaration_mut(external);
+    }
+}
+
+pub(crate) fn walk_external_declaration_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    external: &mut Spanned<ExternalDeclaration>,
+) {
+    match &mut *external.inner {
+        ExternalDeclaration::FunctionDefinition(function) => {
+            visitor.visit_function_definition_mut(function)
+        }
+        ExternalDeclaration::Declaration(declaration) => visitor.visit_declaration_mut(declaration),
+    }
+}
+
+pub(crate) fn walk_function_definition_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    function: &mut Spanned<FunctionDefinition>,
+) {
+    visitor.visit_declaration_specifiers_mut(&mut function.function_specifiers);
+    visitor.visit_declarator_mut(&mut function.declarator);
+    for declaration in &mut function.declarations {
+        visitor.visit_declaration_mut(declaration);
+    }
+    visitor.visit_statement_mut(&mut function.body);
+}
+
+pub(crate) fn walk_declaration_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    declaration: &mut Spanned<Declaration>,
+) {
+    match &mut *declaration.inner {
+        Declaration::Declaration { specifiers, init } => {
+            visitor.visit_declaration_specifiers_mut(specifiers);
+            for (declarator, initializer) in init {
+                visitor.visit_declarator_mut(declarator);
+                if let Some(initializer) = initializer {
+                    visitor.visit_initializer_mut(initializer);
+                }
+            }
+        }
+        Declaration::StaticAssertDeclaration(static_assert) => {
+            visitor.visit_static_assert_mut(static_assert)
+        }
+    }
+}
+
+pub(crate) fn walk_static_assert_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    static_assert: &mut Spanned<StaticAssertDeclaration>,
+) {
+    visitor.visit_constant_expression_mut(&mut static_assert.expression);
+}
+
+pub(crate) fn walk_declaration_specifiers_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    specifiers: &mut DeclarationSpecifiers,
+) {
+    for attribute in &mut specifiers.attributes {
+        visitor.visit_gnu_attribute_mut(attribute);
+    }
+    if let Some(alignment) = &mut specifiers.alignment {
+        visitor.visit_alignment_specifier_mut(alignment);
+    }
+    visitor.visit_type_specifier_mut(&mut specifiers.specifiers);
+}
+
+pub(crate) fn walk_gnu_attribute_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    attribute: &mut Spanned<GnuAttribute>,
+) {
+    match &mut *attribute.inner {
+        GnuAttribute::Aligned(Some(alignment)) => visitor.visit_constant_expression_mut(alignment),
+        GnuAttribute::Other { arguments, .. } => {
+            for argument in arguments {
+                visitor.visit_expression_mut(argument);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub(crate) fn walk_alignment_specifier_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    alignment: &mut Spanned<CAlignmentSpecifier>,
+) {
+    match &mut *alignment.inner {
+        CAlignmentSpecifier::ToType(type_name) => visitor.visit_type_name_mut(type_name),
+        CAlignmentSpecifier::ToExpression(expr) => visitor.visit_constant_expression_mut(expr),
+    }
+}
+
+pub(crate) fn walk_type_specifier_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    specifier: &mut CTypeSpecifier,
+) {
+    match specifier {
+        CTypeSpecifier::Basic(_) | CTypeSpecifier::Typedefed(_) => {}
+        CTypeSpecifier::StructOrUnion(struct_or_union) => {
+            visitor.visit_struct_or_union_mut(struct_or_union)
+        }
+        CTypeSpecifier::Enum(enum_type) => visitor.visit_enum_mut(enum_type),
+        CTypeSpecifier::Atomic(type_name) => visitor.visit_type_name_mut(type_name),
+        CTypeSpecifier::TypeOf(type_of) => match &mut type_of.argument {
+            CTypeOfArgument::TypeName(type_name) => visitor.visit_type_name_mut(type_name),
+            CTypeOfArgument::Expression(expr) => visitor.visit_expression_mut(expr),
+        },
+    }
+}
+
+pub(crate) fn walk_struct_or_union_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    struct_or_union: &mut Spanned<CStructOrUnionType>,
+) {
+    for attribute in &mut struct_or_union.attributes {
+        visitor.visit_gnu_attribute_mut(attribute);
+    }
+    for declaration in &mut struct_or_union.declarations {
+        visitor.visit_struct_declaration_mut(declaration);
+    }
+}
+
+pub(crate) fn walk_struct_declaration_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    declaration: &mut CSructDeclaration,
+) {
+    match declaration {
+        CSructDeclaration::StaticAssertDeclaration(static_assert) => {
+            visitor.visit_static_assert_mut(static_assert)
+        }
+        CSructDeclaration::StructDeclaration {
+            alignment,
+            specifier_qualifier,
+            delcarator_list,
+        } => {
+            if let Some(alignment) = alignment {
+                visitor.visit_alignment_specifier_mut(alignment);
+            }
+            visitor.visit_type_basic_mut(specifier_qualifier);
+            for declarator in delcarator_list {
+                visitor.visit_struct_declarator_mut(declarator);
+            }
+        }
+    }
+}
+
+pub(crate) fn walk_struct_declarator_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    declarator: &mut Spanned<CStructDeclarator>,
+) {
+    match &mut *declarator.inner {
+        CStructDeclarator::Declarator(declarator) => visitor.visit_declarator_mut(declarator),
+        CStructDeclarator::BitField { declarator, expr } => {
+            if let Some(declarator) = declarator {
+                visitor.visit_declarator_mut(declarator);
+            }
+            visitor.visit_constant_expression_mut(expr);
+        }
+    }
+}
+
+pub(crate) fn walk_enum_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    enum_type: &mut Spanned<CEnumType>,
+) {
+    for enumerator in &mut enum_type.enumerators {
+        visitor.visit_enumerator_mut(enumerator);
+    }
+}
+
+pub(crate) fn walk_enumerator_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    enumerator: &mut Spanned<CEnumEnumerator>,
+) {
+    if let Some(value) = &mut enumerator.const_assignment {
+        visitor.visit_constant_expression_mut(value);
+    }
+}
+
+pub(crate) fn walk_type_name_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    type_name: &mut CTypeName,
+) {
+    visitor.visit_type_basic_mut(&mut type_name.base);
+    visitor.visit_derived_declarator_mut(&mut type_name.declarator);
+}
+
+pub(crate) fn walk_type_basic_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    basic: &mut Spanned<CTypeBasic>,
+) {
+    visitor.visit_type_specifier_mut(&mut basic.specifier);
+}
+
+pub(crate) fn walk_declarator_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    declarator: &mut Spanned<Declarator>,
+) {
+    visitor.visit_derived_declarator_mut(&mut declarator.derive);
+    for attribute in &mut declarator.attributes {
+        visitor.visit_gnu_attribute_mut(attribute);
+    }
+}
+
+pub(crate) fn walk_derived_declarator_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    derived: &mut DerivedDeclarator,
+) {
+    match derived {
+        DerivedDeclarator::Base => {}
+        DerivedDeclarator::Pointer { to, .. } => visitor.visit_derived_declarator_mut(to),
+        DerivedDeclarator::Array { size_expr, to, .. } => {
+            if let Some(size_expr) = size_expr {
+                visitor.visit_expression_mut(size_expr);
+            }
+            visitor.visit_derived_declarator_mut(to);
+        }
+        DerivedDeclarator::FunctionType {
+            parameter_type_list,
+            to,
+        } => {
+            for parameter in &mut parameter_type_list.parameter_list {
+                visitor.visit_parameter_declaration_mut(parameter);
+            }
+            visitor.visit_derived_declarator_mut(to);
+        }
+        DerivedDeclarator::FunctionIdentified { to, .. } => {
+            visitor.visit_derived_declarator_mut(to)
+        }
+    }
+}
+
+pub(crate) fn walk_parameter_declaration_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    parameter: &mut Spanned<ParameterDeclaration>,
+) {
+    match &mut *parameter.inner {
+        ParameterDeclaration::Declarator {
+            specifiers,
+            declarator,
+        } => {
+            visitor.visit_declaration_specifiers_mut(specifiers);
+            visitor.visit_declarator_mut(declarator);
+        }
+        ParameterDeclaration::AbstractDeclarator {
+            specifiers,
+            abstract_declarator,
+        } => {
+            visitor.visit_declaration_specifiers_mut(specifiers);
+            if let Some(derived) = abstract_declarator {
+                visitor.visit_derived_declarator_mut(derived);
+            }
+        }
+    }
+}
+
+pub(crate) fn walk_initializer_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    initializer: &mut Spanned<Initializer>,
+) {
+    match &mut *initializer.inner {
+        Initializer::Single(expr) => visitor.visit_expression_mut(expr),
+        Initializer::Compound(list) => {
+            for (designators, initializer) in list {
+                for designator in designators {
+                    visitor.visit_designator_mut(designator);
+                }
+                visitor.visit_initializer_mut(initializer);
+            }
+        }
+    }
+}
+
+pub(crate) fn walk_designator_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    designator: &mut Designator,
+) {
+    match designator {
+        Designator::Array(index) => visitor.visit_constant_expression_mut(index),
+        Designator::Member(_) => {}
+    }
+}
+
+pub(crate) fn walk_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    statement: &mut Spanned<Statement>,
+) {
+    match &mut *statement.inner {
+        Statement::Labeled { label: _, body } => visitor.visit_statement_mut(body),
+        Statement::SwitchCase {
+            const_expr,
+            statement,
+        } => {
+            visitor.visit_constant_expression_mut(const_expr);
+            visitor.visit_statement_mut(statement);
+        }
+        Statement::SwitchDefault { statement } => visitor.visit_statement_mut(statement),
+        Statement::Compound(items) => {
+            for item in items {
+                visitor.visit_compound_item_mut(item);
+            }
+        }
+        Statement::CExpression(expr) => visitor.visit_expression_mut(expr),
+        Statement::NoneExpr => {}
+        Statement::If {
+            controlling_expr,
+            true_body,
+            else_body,
+        } => {
+            visitor.visit_expression_mut(controlling_expr);
+            visitor.visit_statement_mut(true_body);
+            if let Some(else_body) = else_body {
+                visitor.visit_statement_mut(else_body);
+            }
+        }
+        Statement::Switch {
+            controlling_expr,
+            body,
+        } => {
+            visitor.visit_expression_mut(controlling_expr);
+            visitor.visit_statement_mut(body);
+        }
+        Statement::While {
+            while_type: _,
+            controlling_expr,
+            body,
+        } => {
+            visitor.visit_expression_mut(controlling_expr);
+            visitor.visit_statement_mut(body);
+        }
+        Statement::For {
+            decl_clause,
+            expr_clause,
+            controlling_expr,
+            after_expr,
+            body,
+        } => {
+            if let Some(declaration) = decl_clause {
+                visitor.visit_declaration_mut(declaration);
+            }
+            for expr in [expr_clause, controlling_expr, after_expr]
+                .into_iter()
+                .flatten()
+            {
+                visitor.visit_expression_mut(expr);
+            }
+            visitor.visit_statement_mut(body);
+        }
+        Statement::Goto(_) | Statement::Continue | Statement::Break => {}
+        Statement::GotoIndirect(target) => visitor.visit_expression_mut(target),
+        Statement::Return(expr) => {
+            if let Some(expr) = expr {
+                visitor.visit_expression_mut(expr);
+            }
+        }
+    }
+}
+
+pub(crate) fn walk_compound_item_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    item: &mut CompoundItem,
+) {
+    match item {
+        CompoundItem::Statement(statement) => visitor.visit_statement_mut(statement),
+        CompoundItem::Declaration(declaration) => visitor.visit_declaration_mut(declaration),
+    }
+}
+
+pub(crate) fn walk_expression_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    expr: &mut Spanned<CExpression>,
+) {
+    match &mut *expr.inner {
+        CExpression::Expression(list)
+        | CExpression::LogicalOr(list)
+        | CExpression::LogicalAnd(list)
+        | CExpression::InclusiveOr(list)
+        | CExpression::ExlusiveOr(list)
+        | CExpression::And(list) => {
+            for expr in list {
+                visitor.visit_expression_mut(expr);
+            }
+        }
+        CExpression::Assignment {
+            to_assign: left,
+            operator: _,
+            value: right,
+        }
+        | CExpression::Equality {
+            left_piece: left,
+            equality_op: _,
+            right_piece: right,
+        }
+        | CExpression::Relational {
+            left_piece: left,
+            equality_op: _,
+            right_piece: right,
+        }
+        | CExpression::Shift {
+            value: left,
+            shift_type: _,
+            shift_amount: right,
+        }
+        | CExpression::Additive {
+            left_value: left,
+            op: _,
+            right_value: right,
+        }
+        | CExpression::Multiplicative {
+            left_value: left,
+            op: _,
+            right_value: right,
+        }
+        | CExpression::ArraySubscription {
+            array: left,
+            index: right,
+        } => {
+            visitor.visit_expression_mut(left);
+            visitor.visit_expression_mut(right);
+        }
+        CExpression::Ternary {
+            condition,
+            if_true,
+            tern_else,
+        } => {
+            visitor.visit_expression_mut(condition);
+            visitor.visit_expression_mut(if_true);
+            visitor.visit_expression_mut(tern_else);
+        }
+        CExpression::Cast { type_name, value } => {
+            visitor.visit_type_name_mut(type_name);
+            visitor.visit_expression_mut(value);
+        }
+        CExpression::PrefixIncrement { value, .. }
+        | CExpression::PostfixIncrement { value, .. }
+        | CExpression::Unary { value, .. }
+        | CExpression::SizeOf { value }
+        | CExpression::DirectMemberAccess {
+            to_access: value, ..
+        }
+        | CExpression::IndirectMemberAccess {
+            to_access: value, ..
+        }
+        | CExpression::Paranthesised(value) => visitor.visit_expression_mut(value),
+        CExpression::SizeOfType { type_name } | CExpression::AlignOfType { type_name } => {
+            visitor.visit_type_name_mut(type_name)
+        }
+        CExpression::FunctionCall {
+            function,
+            arguments,
+        } => {
+            visitor.visit_expression_mut(function);
+            for argument in arguments {
+                visitor.visit_expression_mut(argument);
+            }
+        }
+        CExpression::TypeInitializer {
+            type_name,
+            initializer_list,
+        } => {
+            visitor.visit_type_name_mut(type_name);
+            visitor.visit_initializer_mut(initializer_list);
+        }
+        CExpression::Identifier(_) | CExpression::Constant(_) | CExpression::StringLiteral(_) => {}
+        CExpression::GenericSelection(generic) => {
+            visitor.visit_expression_mut(&mut generic.assignment_expression);
+            for association in generic.generic_assoc_list.iter_mut() {
+                visitor.visit_generic_association_mut(association);
+            }
+        }
+        CExpression::StatementExpression(items) => {
+            for item in items.iter_mut() {
+                visitor.visit_compound_item_mut(item);
+            }
+        }
+        CExpression::LabelAddress(_) => {}
+    }
+}
+
+pub(crate) fn walk_constant_expression_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    expr: &mut ConstantExpression,
+) {
+    visitor.visit_expression_mut(&mut expr.internal);
+}
+
+pub(crate) fn walk_generic_association_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    association: &mut GenericAssociation,
+) {
+    match association {
+        GenericAssociation::TypeName {
+            type_name,
+            assignment_expression,
+        } => {
+            visitor.visit_type_name_mut(type_name);
+            visitor.visit_expression_mut(assignment_expression);
+        }
+        GenericAssociation::Default(assignment_expression) => {
+            visitor.visit_expression_mut(assignment_expression)
+        }
+    }
+}
@@ -7,6 +7,7 @@ Notes:
 #ifndef [X]
 #else
 #endif
+#embed "file" | <file>: (C23) the bytes of that file as a comma separated list of integers
 
 Statements:
 - defined
@@ -25,15 +26,23 @@ use std::{
 
 use log::{debug, error, info, trace, warn};
 
+use crate::standard::CStandard;
+
 pub struct Preprocessor {
     define_map: HashMap<String, String>,
+    standard: CStandard,
 }
 impl Preprocessor {
     pub fn new() -> Self {
         Preprocessor {
             define_map: HashMap::new(),
+            standard: CStandard::default(),
         }
     }
+    pub fn with_standard(mut self, standard: CStandard) -> Self {
+        self.standard = standard;
+        self
+    }
     pub fn replace_final(&self, string_to_finalize: String) -> String {
         let mut output = string_to_finalize;
         for defined in self.define_map.iter() {
@@ -160,6 +169,39 @@ impl Preprocessor {
                             );
                         }
                     }
+                    "embed" => {
+                        if !self.standard.is_c23() {
+                            panic!("#embed requires -std=c23 or -std=gnu23: {:?}", line);
+                        }
+                        let resource_path = &line.word_list[3];
+                        let file_to_embed = if let Some(temp) = resource_path
+                            .strip_prefix('<')
+                            .and_then(|path| path.strip_suffix('>'))
+                        {
+                            Path::new("/usr/include").join(temp)
+                        } else if let Some(temp) = resource_path
+                            .strip_prefix('"')
+                            .and_then(|path| path.strip_suffix('"'))
+                        {
+                            Path::new(&file_path).parent().unwrap().join(temp)
+                        } else {
+                            panic!("unknown embed string {:?}", line);
+                        };
+
+                        let bytes = match std::fs::read(&file_to_embed) {
+                            Ok(bytes) => bytes,
+                            Err(err) => {
+                                panic!("failed to read embedded file {:?}: {}", file_to_embed, err)
+                            }
+                        };
+                        output_buf.push(
+                            bytes
+                                .iter()
+                                .map(|byte| byte.to_string())
+                                .collect::<Vec<String>>()
+                                .join(", "),
+                        );
+                    }
                     "if" => {
                         let mut nesting_level = 0;
                         let mut if_else_chain_idx = 0; // if true we are in if_else
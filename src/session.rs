@@ -0,0 +1,387 @@
+use std::{fmt::Display, fs::read_to_string, path::Path, process::Command};
+
+use log::{debug, info, warn};
+
+use crate::{
+    cranelift_backend::CraneliftBackend,
+    environment_builder::EnvironmentController,
+    lexer::{CToken, Lexer, OriginalLocation},
+    mir::MIRProgramm,
+    parser::{
+        ast_json::{translation_unit_from_json, translation_unit_to_json},
+        parse_nodes::TranslationUnit,
+        partial::{catch_quietly, CaughtError},
+        printer::translation_unit_to_c,
+        CParser,
+    },
+    preprocessor::Preprocessor,
+    standard::CStandard,
+};
+
+/*
+The compilation pipeline as a library:
+    Compiler: the options, shared by every Session it creates
+    Session: one source file, the phases run lazily and only once,
+        every phase result is kept so it can be inspected afterwards
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Preprocessing,
+    Lexing,
+    Parsing,
+    Lowering,
+    Codegen,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PreprocessorKind {
+    #[default]
+    Internal,
+    /// `gcc -E` with the crate's header_fixes/fix.h included
+    Gcc,
+    /// the source is already preprocessed
+    None,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub phase: Phase,
+    pub message: String,
+    pub location: Option<OriginalLocation>,
+    /// the last token of the construct the error is about, if it has a span
+    pub end: Option<OriginalLocation>,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.location, &self.end) {
+            (Some(location), Some(end)) if end != location => write!(
+                f,
+                "{:?} error at {} to {}-{}: {}",
+                self.phase, location, end.line, end.collumn, self.message
+            ),
+            (Some(location), _) => write!(
+                f,
+                "{:?} error at {}: {}",
+                self.phase, location, self.message
+            ),
+            (None, _) => write!(f, "{:?} error: {}", self.phase, self.message),
+        }
+    }
+}
+
+/// The parsed translation unit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ast {
+    pub(crate) translation_unit: TranslationUnit,
+}
+
+impl Ast {
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Ast {
+            translation_unit: translation_unit_from_json(json)?,
+        })
+    }
+    pub fn to_json(&self) -> Result<String, Box<dyn std::error::Error>> {
+        translation_unit_to_json(&self.translation_unit)
+    }
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(&self.translation_unit)
+    }
+    /// C source regenerated from the AST
+    pub fn to_c(&self) -> String {
+        translation_unit_to_c(&self.translation_unit)
+    }
+    pub fn len(&self) -> usize {
+        self.translation_unit.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.translation_unit.is_empty()
+    }
+}
+
+/// The program lowered to MIR, ready for codegen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mir {
+    programm: MIRProgramm,
+}
+
+impl Mir {
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(&self.programm)
+    }
+    pub fn function_names(&self) -> Vec<&str> {
+        self.programm
+            .functions
+            .iter()
+            .map(|function| function.name.as_str())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Compiler {
+    standard: CStandard,
+    preprocessor: PreprocessorKind,
+}
+
+impl Compiler {
+    pub fn new() -> Self {
+        Compiler::default()
+    }
+    pub fn with_standard(mut self, standard: CStandard) -> Self {
+        self.standard = standard;
+        self
+    }
+    pub fn with_preprocessor(mut self, preprocessor: PreprocessorKind) -> Self {
+        self.preprocessor = preprocessor;
+        self
+    }
+
+    pub fn session(&self, file_path: impl Into<String>, source: impl Into<String>) -> Session {
+        Session {
+            compiler: self.clone(),
+            file_path: file_path.into(),
+            source: source.into(),
+            preprocessed: None,
+            tokens: None,
+            ast: None,
+            mir: None,
+            object: None,
+            assembly: None,
+            diagnostics: vec![],
+            failed: None,
+        }
+    }
+    pub fn session_from_file(&self, file_path: impl Into<String>) -> std::io::Result<Session> {
+        let file_path = file_path.into();
+        let source = read_to_string(&file_path)?;
+        Ok(self.session(file_path, source))
+    }
+
+    /// runs every phase, the object file or all the diagnostics
+    pub fn compile(
+        &self,
+        file_path: impl Into<String>,
+        source: impl Into<String>,
+    ) -> Result<Vec<u8>, Vec<Diagnostic>> {
+        let mut session = self.session(file_path, source);
+        match session.object() {
+            Some(object) => Ok(object.to_vec()),
+            None => Err(session.diagnostics),
+        }
+    }
+}
+
+pub struct Session {
+    compiler: Compiler,
+    file_path: String,
+    source: String,
+    preprocessed: Option<String>,
+    tokens: Option<Vec<CToken>>,
+    ast: Option<Ast>,
+    mir: Option<Mir>,
+    object: Option<Vec<u8>>,
+    assembly: Option<String>,
+    diagnostics: Vec<Diagnostic>,
+    failed: Option<Phase>,
+}
+
+impl Session {
+    pub fn file_path(&self) -> &str {
+        &self.file_path
+    }
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+    pub fn has_errors(&self) -> bool {
+        self.failed.is_some()
+    }
+
+    /// runs every phase up to and including `phase`, false if one of them failed
+    pub fn run_until(&mut self, phase: Phase) -> bool {
+        match phase {
+            Phase::Preprocessing => self.preprocessed().is_some(),
+            Phase::Lexing => self.tokens().is_some(),
+            Phase::Parsing => self.ast().is_some(),
+            Phase::Lowering => self.mir().is_some(),
+            Phase::Codegen => self.object().is_some(),
+        }
+    }
+
+    pub fn preprocessed(&mut self) -> Option<&str> {
+        if self.preprocessed.is_none() && self.failed.is_none() {
+            info!("Starting Preprocessing of file: {:?}", self.file_path);
+            let standard = self.compiler.standard;
+            let (file_path, source) = (self.file_path.clone(), self.source.clone());
+
+            let result = match self.compiler.preprocessor {
+                PreprocessorKind::Internal => catch_quietly(|| {
+                    let mut preprocessor = Preprocessor::new().with_standard(standard);
+                    let preprocessed = preprocessor.preprocess_code_string(source, file_path);
+                    preprocessor.replace_final(preprocessed)
+                }),
+                PreprocessorKind::Gcc => {
+                    run_gcc_preprocessor(&file_path, standard).map_err(CaughtError::from)
+                }
+                PreprocessorKind::None => Ok(source),
+            };
+            self.preprocessed = self.record(Phase::Preprocessing, result);
+        }
+        self.preprocessed.as_deref()
+    }
+
+    pub fn tokens(&mut self) -> Option<&[CToken]> {
+        if self.tokens.is_none() && self.preprocessed().is_some() && self.failed.is_none() {
+            info!("Starting Lexing of file: {:?}", self.file_path);
+            let standard = self.compiler.standard;
+            let preprocessed = self.preprocessed.clone().unwrap_or_default();
+            let file_path = self.file_path.clone();
+
+            let result = catch_quietly(|| {
+                Lexer::new()
+                    .with_standard(standard)
+                    .with_file(&file_path)
+                    .string_to_token_arr(preprocessed)
+            });
+            self.tokens = self.record(Phase::Lexing, result);
+
+            for token in self.tokens.iter().flatten() {
+                debug!("{}", token);
+            }
+        }
+        self.tokens.as_deref()
+    }
+
+    pub fn ast(&mut self) -> Option<&Ast> {
+        if self.ast.is_none() && self.tokens().is_some() && self.failed.is_none() {
+            info!("Starting Parsing of file: {:?}", self.file_path);
+            let tokens = self.tokens.clone().unwrap_or_default();
+
+            let partial = CParser::new(tokens)
+                .with_standard(self.compiler.standard)
+                .parse_partial_translation_unit();
+            for diagnostic in &partial.diagnostics {
+                self.diagnostics.push(Diagnostic {
+                    phase: Phase::Parsing,
+                    message: diagnostic.message.clone(),
+                    location: Some(diagnostic.location.clone()),
+                    end: None,
+                });
+            }
+
+            if partial.diagnostics.is_empty() {
+                self.ast = partial.node;
+            } else {
+                self.failed = Some(Phase::Parsing);
+            }
+        }
+        self.ast.as_ref()
+    }
+
+    pub fn mir(&mut self) -> Option<&Mir> {
+        if self.mir.is_none() && self.ast().is_some() && self.failed.is_none() {
+            info!("Starting Environment of file: {:?}", self.file_path);
+            let translation_unit = self.ast.clone().unwrap().translation_unit;
+
+            let result = catch_quietly(|| {
+                let mut controller = EnvironmentController::new();
+                controller.build(translation_unit);
+                Mir {
+                    programm: controller.get_mir(),
+                }
+            });
+            self.mir = self.record(Phase::Lowering, result);
+        }
+        self.mir.as_ref()
+    }
+
+    /// the relocatable x86_64 ELF object
+    pub fn object(&mut self) -> Option<&[u8]> {
+        if self.object.is_none() && self.mir().is_some() && self.failed.is_none() {
+            info!("Starting Codegen of file: {:?}", self.file_path);
+            let programm = self.mir.clone().unwrap().programm;
+
+            let result = catch_quietly(|| {
+                let mut cranelift_backend = CraneliftBackend::default();
+                cranelift_backend
+                    .compile(programm)
+                    .map(|()| cranelift_backend.finish())
+            })
+            .and_then(|compiled| compiled.map_err(CaughtError::from));
+            self.object = self.record(Phase::Codegen, result);
+        }
+        self.object.as_deref()
+    }
+
+    /// a listing of the generated machine code, informational and not meant for `as`
+    pub fn assembly(&mut self) -> Option<&str> {
+        if self.assembly.is_none() && self.mir().is_some() && self.failed.is_none() {
+            info!(
+                "Starting Codegen with disassembly of file: {:?}",
+                self.file_path
+            );
+            let programm = self.mir.clone().unwrap().programm;
+
+            let result = catch_quietly(|| {
+                let mut cranelift_backend = CraneliftBackend::default().with_disassembly();
+                cranelift_backend.compile(programm).map(|()| {
+                    format!(
+                        "\t.file\t{:?}\n\t.text\n{}",
+                        self.file_path,
+                        cranelift_backend.disassembly().unwrap_or_default()
+                    )
+                })
+            })
+            .and_then(|compiled| compiled.map_err(CaughtError::from));
+            self.assembly = self.record(Phase::Codegen, result);
+        }
+        self.assembly.as_deref()
+    }
+
+    fn record<T>(&mut self, phase: Phase, result: Result<T, CaughtError>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(caught) => {
+                self.diagnostics.push(Diagnostic {
+                    phase,
+                    message: caught.message,
+                    location: caught.span.as_ref().map(|span| span.start.clone()),
+                    end: caught.span.map(|span| span.end),
+                });
+                self.failed = Some(phase);
+                None
+            }
+        }
+    }
+}
+
+/// declarations gcc's headers rely on, that jankcc does not provide as builtins
+const HEADER_FIXES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/header_fixes/fix.h");
+
+fn run_gcc_preprocessor(file_path: &str, standard: CStandard) -> Result<String, String> {
+    if !Path::new(file_path).exists() {
+        return Err(format!("gcc needs the source on disk: {:?}", file_path));
+    }
+    let output_from_gcc = Command::new("gcc")
+        .args([
+            "-E",
+            &format!("-std={}", standard),
+            "-undef",
+            "-fno-builtin",
+            "-include",
+            HEADER_FIXES,
+            file_path,
+        ])
+        .output()
+        .map_err(|err| err.to_string())?;
+    let stderr = String::from_utf8_lossy(&output_from_gcc.stderr);
+    if !output_from_gcc.status.success() {
+        return Err(format!("gcc -E failed: {}", stderr.trim_end()));
+    }
+    if !stderr.is_empty() {
+        warn!("{}", stderr)
+    }
+    String::from_utf8(output_from_gcc.stdout).map_err(|err| err.to_string())
+}
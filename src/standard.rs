@@ -0,0 +1,64 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// The language standard selected with `-std=`, extensions are only accepted in the gnu modes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CStandard {
+    #[default]
+    C11,
+    Gnu11,
+    C23,
+    Gnu23,
+}
+
+impl CStandard {
+    pub(crate) fn is_gnu(&self) -> bool {
+        match self {
+            CStandard::C11 | CStandard::C23 => false,
+            CStandard::Gnu11 | CStandard::Gnu23 => true,
+        }
+    }
+
+    /// C23 keywords, `[[attributes]]`, digit separators and #embed
+    pub(crate) fn is_c23(&self) -> bool {
+        match self {
+            CStandard::C11 | CStandard::Gnu11 => false,
+            CStandard::C23 | CStandard::Gnu23 => true,
+        }
+    }
+
+    /// standard in C23, but gcc accepts them as an extension in the gnu modes as well
+    pub(crate) fn has_binary_literals(&self) -> bool {
+        self.is_c23() || self.is_gnu()
+    }
+}
+
+impl FromStr for CStandard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "c11" | "c1x" | "iso9899:2011" => Ok(CStandard::C11),
+            "gnu11" | "gnu1x" => Ok(CStandard::Gnu11),
+            "c23" | "c2x" | "iso9899:2024" => Ok(CStandard::C23),
+            "gnu23" | "gnu2x" => Ok(CStandard::Gnu23),
+            _ => Err(format!(
+                "unsupported standard: '{}', expected c11, gnu11, c23 or gnu23",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for CStandard {
+    /// the name as understood by gcc, older versions only know c2x
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CStandard::C11 => write!(f, "c11"),
+            CStandard::Gnu11 => write!(f, "gnu11"),
+            CStandard::C23 => write!(f, "c2x"),
+            CStandard::Gnu23 => write!(f, "gnu2x"),
+        }
+    }
+}
@@ -24,6 +24,355 @@ fn simple_hello_world() {
     );
 }
 
+#[test]
+fn struct_pass_and_return_by_value() {
+    let path = "tests/source_files/struct_by_value.c";
+
+    let expected_output = "2 1 60 100\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn arrays_subscript_decay_and_sizeof() {
+    let path = "tests/source_files/arrays.c";
+
+    let expected_output = "9 4 30 12\n0 16 7 24\n20 48 24 32\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn pointer_arithmetic_and_comparison() {
+    let path = "tests/source_files/pointer_arithmetic.c";
+
+    let expected_output = "6 12 5 3\n1 0 1 1\n3 15\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn enum_constants_in_expressions_and_array_sizes() {
+    let path = "tests/source_files/enums.c";
+
+    let expected_output = "0 6 2 10\n24 4 20\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn constant_expressions_in_array_sizes_and_static_asserts() {
+    let path = "tests/source_files/constant_expressions.c";
+
+    let expected_output = "4 11 8\n2 64 32\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn globals_and_static_locals() {
+    let path = "tests/source_files/globals.c";
+
+    let expected_output = "5 42 -7 hello globals\n10 101 102 8\n6 12 0\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn brace_initializers_with_designators() {
+    let path = "tests/source_files/initializers.c";
+
+    let expected_output = "1 2 0 9 ab\n32 5 17 19\n3 4 0 jank 5\none three 1 257\n\
+                           0 5 7 0 xyz\n0 4 8 0\n64 0 2 3 6\nhi 0 0 0 42 1\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn compound_literals_as_objects() {
+    let path = "tests/source_files/compound_literals.c";
+
+    let expected_output = "1 2 15 6 8\n42 4 9\n7 0 4 6 7\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn short_circuit_and_conditional_operators() {
+    let path = "tests/source_files/logical_operators.c";
+
+    let expected_output = "0 1 0 5\n1 0 0\n7 -1\n3 8 fallback 7\nguarded\n3 6\n7\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn bitwise_and_shift_operators() {
+    let path = "tests/source_files/bitwise_operators.c";
+
+    let expected_output = "8 14 6 -13\n-16 1024 1 2147483647\n256 1099511627776 4\n79 1\n\
+                           240904447 878082066\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn compound_assignment_and_increments() {
+    let path = "tests/source_files/compound_assignment.c";
+
+    let expected_output = "2\n14\n15 4\n30 2\n30 50 4\n30\n5 7 7 5\n3 40 3 41\n151 30\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn loops_with_break_and_continue() {
+    let path = "tests/source_files/loops.c";
+
+    let expected_output = "70 6\n12 1\n52\n27 4 4 -1\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn switch_jump_tables_and_compare_trees() {
+    let path = "tests/source_files/switch.c";
+
+    let expected_output = "100 12 12 34 8 0 70 0\n1 3 0 7 0\n1 3 4 5 6 7 0\n7133\n14\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn switch_duplicate_case_is_reported_at_the_label() {
+    let output = std::process::Command::new("cargo")
+        .args([
+            "r",
+            "--",
+            "-c",
+            "tests/source_files/switch_duplicate_case.c",
+            "-o",
+        ])
+        .arg(std::env::temp_dir().join("jankcc-switch_duplicate_case.o"))
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(!output.status.success());
+    // from `case` to the end of its value, the lines are counted from 0
+    assert!(
+        stderr.contains(
+            "Lowering error at \"tests/source_files/switch_duplicate_case.c\": 5-0 to 5-1: \
+             duplicate case value '1'"
+        ),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn goto_forward_and_backward() {
+    let path = "tests/source_files/goto.c";
+
+    let expected_output = "206 909 -1\n10 0 -1\n1\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn computed_goto_dispatch() {
+    let path = "tests/source_files/computed_goto.c";
+
+    let expected_output = "42\n44\nleft 1, counted 5\n";
+
+    assert_eq!(
+        common::execute_with_flags_and_cleanup_capturing_stdout(path, &["-std=gnu11"]),
+        expected_output
+    );
+}
+
+#[test]
+fn kr_parameters_have_their_declared_type() {
+    let path = "tests/source_files/kr_parameters.c";
+
+    let expected_output = "12 44 32767\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn compile_only_without_preprocessor_flag_writes_the_object() {
+    let object_path = std::env::temp_dir().join("jankcc-compile-only-hello_world.o");
+    let _ = std::fs::remove_file(&object_path);
+
+    let output = std::process::Command::new("cargo")
+        .args([
+            "r",
+            "--",
+            "-q",
+            "-c",
+            "tests/source_files/hello_world.c",
+            "-o",
+        ])
+        .arg(&object_path)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{:?}", output);
+    assert!(object_path.exists());
+    std::fs::remove_file(&object_path).unwrap();
+}
+
+#[test]
+fn preprocessed_input_is_not_preprocessed_again() {
+    let preprocessed_path = std::env::temp_dir().join("jankcc-preprocessed-simple_add.i");
+    let executable_path = std::env::temp_dir().join("jankcc-preprocessed-simple_add");
+
+    let jankcc = |args: &[&std::ffi::OsStr]| {
+        let output = std::process::Command::new("cargo")
+            .args(["r", "--", "-q"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+    };
+    jankcc(&[
+        "-E".as_ref(),
+        "tests/source_files/simple_add.c".as_ref(),
+        "-o".as_ref(),
+        preprocessed_path.as_os_str(),
+    ]);
+    jankcc(&[
+        preprocessed_path.as_os_str(),
+        "-o".as_ref(),
+        executable_path.as_os_str(),
+    ]);
+
+    let output = std::process::Command::new(&executable_path)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "4000+777=4777");
+    std::fs::remove_file(&preprocessed_path).unwrap();
+    std::fs::remove_file(&executable_path).unwrap();
+}
+
+#[test]
+fn unsigned_compares_against_int_constants() {
+    let path = "tests/source_files/unsigned_comparison.c";
+
+    let expected_output = "if ok\n1 1 1 0 1\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn casts_to_pointers_and_null() {
+    let path = "tests/source_files/null_pointer_casts.c";
+
+    let expected_output = "1 1 1 1 44 0\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn conditional_arms_get_their_common_type_or_void() {
+    let path = "tests/source_files/conditional_types.c";
+
+    let expected_output = "1 1\n4294967295\n421\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn narrow_arguments_are_converted_and_promoted() {
+    let path = "tests/source_files/narrow_arguments.c";
+
+    let expected_output = "66 130 4464\n0 -3 65\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn sibling_and_nested_blocks_reuse_local_names() {
+    let path = "tests/source_files/scoped_locals.c";
+
+    let expected_output = "29 1\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
+#[test]
+fn members_of_returned_structs() {
+    let path = "tests/source_files/returned_struct_members.c";
+
+    let expected_output = "1 4 15 1\n";
+
+    assert_eq!(
+        common::execute_and_cleanup_capturing_stdout(path),
+        expected_output
+    );
+}
+
 #[test]
 fn simple_recursive_fibonacci_easy() {
     let path = "tests/source_files/fib_easy_blocks.c";
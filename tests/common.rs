@@ -1,31 +1,34 @@
 use std::{path::Path, process::Command};
 
 pub fn execute_and_cleanup_capturing_stdout(path_to_c_file: &str) -> String {
+    execute_with_flags_and_cleanup_capturing_stdout(path_to_c_file, &[])
+}
+
+/// like execute_and_cleanup_capturing_stdout, with extra compiler flags like -std=gnu11
+pub fn execute_with_flags_and_cleanup_capturing_stdout(
+    path_to_c_file: &str,
+    flags: &[&str],
+) -> String {
     println!("path: {:?}", Path::new(&path_to_c_file).with_extension("c"));
     println!("dir: {:?}", Path::new(".").canonicalize());
 
     let _output_from_compiler = Command::new("cargo")
-        .args(&["r", "--", "-g", path_to_c_file])
-        .output()
-        .unwrap();
-    println!("compiler done");
-    let _output_from_linker = Command::new("cc")
         .args(&[
-            Path::new(&path_to_c_file)
-                .with_extension("o")
-                .to_str()
-                .unwrap(),
+            "r",
+            "--",
+            "-g",
+            path_to_c_file,
             "-o",
             Path::new(&path_to_c_file)
                 .with_extension("out")
                 .to_str()
                 .unwrap(),
-            "-no-pie",
             "-static",
         ])
+        .args(flags)
         .output()
         .unwrap();
-    println!("linker done");
+    println!("compiler and linker done");
     let output_from_program = Command::new(
         Path::new(&path_to_c_file)
             .with_extension("out")
@@ -0,0 +1,119 @@
+use std::cell::Cell;
+
+use jankcc::{CParser, CStandard, Compiler, Phase, PreprocessorKind};
+
+#[test]
+fn session_phases_as_values() {
+    let compiler = Compiler::new().with_preprocessor(PreprocessorKind::None);
+    let mut session = compiler.session(
+        "in_memory.c",
+        "int add(int a, int b) { return a + b; }\nint main() { return add(1, 2); }\n",
+    );
+
+    assert!(session.tokens().unwrap().len() > 10);
+    assert_eq!(session.ast().unwrap().len(), 2);
+    assert_eq!(session.mir().unwrap().function_names(), vec!["add", "main"]);
+    // ELF magic
+    assert_eq!(&session.object().unwrap()[..4], b"\x7fELF");
+    assert!(session.assembly().unwrap().contains("main:"));
+    assert!(session.diagnostics().is_empty());
+}
+
+#[test]
+fn session_collects_diagnostics() {
+    let compiler = Compiler::new().with_preprocessor(PreprocessorKind::None);
+    let mut session = compiler.session("broken.c", "int main() { return 1 + ; }\n");
+
+    assert!(session.tokens().is_some());
+    assert!(!session.run_until(Phase::Codegen));
+    assert!(session.has_errors());
+    assert_eq!(session.diagnostics().len(), 1);
+    assert_eq!(session.diagnostics()[0].phase, Phase::Parsing);
+    assert!(session.mir().is_none());
+}
+
+#[test]
+fn compiler_standard_is_used() {
+    let source = "int main() { return ({ int x = 2; x * 3; }); }\n";
+
+    let gnu = Compiler::new()
+        .with_standard(CStandard::Gnu11)
+        .with_preprocessor(PreprocessorKind::None);
+    assert!(gnu.compile("gnu.c", source).is_ok());
+
+    let c11 = Compiler::new().with_preprocessor(PreprocessorKind::None);
+    let diagnostics = c11.compile("c11.c", source).unwrap_err();
+    assert_eq!(diagnostics[0].phase, Phase::Parsing);
+}
+
+thread_local! {
+    static HOOK_CALLS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[test]
+fn partial_parsing_leaves_the_panic_hook_alone() {
+    // the hook is global, the calls are counted per thread as tests run in parallel
+    std::panic::set_hook(Box::new(|_| {
+        HOOK_CALLS.with(|calls| calls.set(calls.get() + 1))
+    }));
+
+    let compiler = Compiler::new().with_preprocessor(PreprocessorKind::None);
+    let mut session = compiler.session("typing.c", "int total = add(1, 2) * (3 +");
+    let tokens = session.tokens().unwrap().to_vec();
+    let partial = CParser::from_token_slice(&tokens).parse_partial_declaration();
+
+    let _ = std::panic::take_hook();
+    assert_eq!(HOOK_CALLS.with(Cell::get), 0);
+    assert_eq!(
+        partial.node.unwrap().to_c(),
+        "int total = add(1, 2) * (3);\n"
+    );
+    assert!(partial.diagnostics[0].incomplete);
+}
+
+#[test]
+fn lowering_diagnostic_has_a_location() {
+    let compiler = Compiler::new().with_preprocessor(PreprocessorKind::None);
+    let source = "int main() {\n    int x;\n    x.y = 1;\n    return 0;\n}\n";
+    let diagnostics = compiler.compile("member.c", source).unwrap_err();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].phase, Phase::Lowering);
+    assert_eq!(
+        diagnostics[0].message,
+        "member access into something that is not a struct or union"
+    );
+    let location = diagnostics[0].location.as_ref().unwrap();
+    assert_eq!((location.file.as_str(), location.line), ("member.c", 2));
+}
+
+#[test]
+fn gcc_preprocessor_failure_stops_the_compile() {
+    let compiler = Compiler::new().with_preprocessor(PreprocessorKind::Gcc);
+    let mut session = compiler
+        .session_from_file("tests/source_files/missing_header.c")
+        .unwrap();
+
+    assert!(session.object().is_none());
+    assert_eq!(session.diagnostics().len(), 1);
+    assert_eq!(session.diagnostics()[0].phase, Phase::Preprocessing);
+    assert!(session.diagnostics()[0]
+        .message
+        .contains("nonexistent_header_for_jankcc.h"));
+}
+
+#[test]
+fn codegen_failure_is_a_diagnostic() {
+    let compiler = Compiler::new().with_preprocessor(PreprocessorKind::None);
+    let mut session = compiler.session(
+        "twice.c",
+        "int f() { return 1; }\nint f() { return 2; }\nint main() { return f(); }\n",
+    );
+
+    assert!(session.object().is_none());
+    assert_eq!(session.diagnostics().len(), 1);
+    assert_eq!(session.diagnostics()[0].phase, Phase::Codegen);
+    assert!(session.diagnostics()[0]
+        .message
+        .starts_with("cannot compile 'f': "));
+}
@@ -0,0 +1,34 @@
+int printf(const char *format, ...);
+
+struct Row {
+    int cells[3];
+    long tag;
+};
+
+long sum(int values[], int count) {
+    long total = 0;
+    for (int i = 0; i < count; i = i + 1) {
+        total = total + values[i];
+    }
+    return total;
+}
+
+int main() {
+    int a[5];
+    long grid[2][3];
+    struct Row row;
+    for (int i = 0; i < 5; i = i + 1) {
+        a[i] = i * i;
+    }
+    for (int r = 0; r < 2; r = r + 1) {
+        for (int c = 0; c < 3; c = c + 1) {
+            grid[r][c] = r * 10 + c;
+        }
+    }
+    row.cells[2] = 7;
+    int *p = a;
+    printf("%d %d %ld %ld\n", a[3], 2[a], sum(a, 5), grid[1][2]);
+    printf("%d %d %d %d\n", *p, p[4], row.cells[2], (int)sizeof(grid[1]));
+    printf("%ld %ld %ld %ld\n", sizeof(a), sizeof grid, sizeof(struct Row), sizeof(long[4]));
+    return 0;
+}
@@ -0,0 +1,33 @@
+int printf(const char *format, ...);
+
+unsigned int hash(const char *text) {
+    unsigned int h = 2166136261;
+    unsigned int prime = 16777619;
+    int i = 0;
+    for (; text[i] != 0; i = i + 1) {
+        h = h ^ (unsigned char)text[i];
+        h = h * prime;
+    }
+    return h;
+}
+
+unsigned int rotate_left(unsigned int value, int amount) {
+    return (value << amount) | (value >> (32 - amount));
+}
+
+int main() {
+    int flags = 12;
+    int mask = 10;
+    int negative = 0 - 64;
+    unsigned int high = 2147483648;
+    unsigned int pattern = 305419896;
+    char small = 1;
+    long wide = 1;
+
+    printf("%d %d %d %d\n", flags & mask, flags | mask, flags ^ mask, ~flags);
+    printf("%d %d %u %u\n", negative >> 2, 1 << 10, high >> 31, ~high);
+    printf("%d %ld %d\n", small << 8, wide << 40, (int)sizeof(small << 1));
+    printf("%d %d\n", 255 & 15 | 64 ^ 3, (flags & 4) != 0);
+    printf("%u %u\n", hash("jank"), rotate_left(pattern, 8));
+    return 0;
+}
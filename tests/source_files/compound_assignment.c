@@ -0,0 +1,47 @@
+int printf(const char *format, ...);
+
+struct counter { int hits; long total; };
+
+int calls;
+struct counter *pick(struct counter *counter) { calls++; return counter; }
+
+int main() {
+    int x = 100;
+    unsigned int u = 7;
+    unsigned char c = 250;
+    int values[5] = { 10, 20, 30, 40, 50 };
+    int *p = values;
+    struct counter counter = { 0, 0 };
+
+    x += 5; x -= 3; x *= 2; x /= 4; x %= 7;
+    printf("%d\n", x);
+    x = 6; x <<= 3; x >>= 1; x &= 12; x |= 3; x ^= 5;
+    printf("%d\n", x);
+    u -= 8; u >>= 28; c += 10;
+    printf("%u %d\n", u, (int)c);
+
+    p += 3; p -= 1;
+    printf("%d %d\n", *p, (int)(p - values));
+    int before = *p++;
+    int after = *++p;
+    printf("%d %d %d\n", before, after, (int)(p-- - values));
+    --p;
+    printf("%d\n", *p);
+
+    int i = 5;
+    int post = i++;
+    int pre = ++i;
+    int down = i--;
+    printf("%d %d %d %d\n", post, pre, down, --i);
+
+    pick(&counter)->hits += 2;
+    pick(&counter)->total += 40;
+    pick(&counter)->hits++;
+    values[counter.hits]++;
+    printf("%d %ld %d %d\n", counter.hits, counter.total, calls, values[3]);
+
+    int total = 0;
+    for (int k = 0; k < 5; k++) total += values[k];
+    printf("%d %d\n", total, (x += 1) * 2);
+    return 0;
+}
@@ -0,0 +1,27 @@
+int printf(const char *format, ...);
+
+struct point { int x; int y; };
+
+int *primes = (int[]){ 2, 3, 5, 7 };
+struct point *corner = &(struct point){ .y = 4 };
+struct point start = (struct point){ 6, 7 };
+
+int sum(struct point p) { return p.x + p.y; }
+int length(int *values, int count) {
+    int total = 0;
+    if (count > 0) total = values[0] + values[count - 1];
+    return total;
+}
+
+int main() {
+    struct point p = (struct point){ 1, 2 };
+    int *q = &(int){ 10 };
+    int *list = (int[]){ 4, 5, 6 };
+    *q = *q + 5;
+    (struct point){ 0 }.x = 3;
+    int n = sizeof((char[]){ "abcdef" });
+    printf("%d %d %d %d %d\n", p.x, p.y, *q, list[2], n);
+    printf("%d %d %d\n", sum((struct point){ 20, 22 }), length((int[]){ 1, 2, 3 }, 3), ((struct point){ .y = 9 }).y);
+    printf("%d %d %d %d %d\n", primes[3], corner->x, corner->y, start.x, start.y);
+    return 0;
+}
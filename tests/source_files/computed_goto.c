@@ -0,0 +1,51 @@
+int printf(const char *format, ...);
+
+/* a tiny stack machine, dispatched through a static table of label addresses */
+int run(int *program) {
+    static void *dispatch[] = {&&push, &&add, &&mul, &&print, &&halt};
+    int stack[8];
+    int top = 0;
+    int pc = 0;
+
+    goto *dispatch[program[pc]];
+push:
+    stack[top] = program[pc + 1];
+    top = top + 1;
+    pc = pc + 2;
+    goto *dispatch[program[pc]];
+add:
+    top = top - 1;
+    stack[top - 1] = stack[top - 1] + stack[top];
+    pc = pc + 1;
+    goto *dispatch[program[pc]];
+mul:
+    top = top - 1;
+    stack[top - 1] = stack[top - 1] * stack[top];
+    pc = pc + 1;
+    goto *dispatch[program[pc]];
+print:
+    printf("%d\n", stack[top - 1]);
+    pc = pc + 1;
+    goto *dispatch[program[pc]];
+halt:
+    return top;
+}
+
+/* the same dispatch with the table built at run time */
+int count_down(int n) {
+    void *steps[] = {&&done, &&again};
+    int count = 0;
+again:
+    count = count + 1;
+    n = n - 1;
+    goto *steps[n > 0];
+done:
+    return count;
+}
+
+int main() {
+    int program[] = {0, 6, 0, 7, 2, 3, 0, 2, 1, 3, 4};
+    int left = run(program);
+    printf("left %d, counted %d\n", left, count_down(5));
+    return 0;
+}
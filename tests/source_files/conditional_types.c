@@ -0,0 +1,26 @@
+int printf(const char *format, ...);
+int counter = 0;
+void g(int amount) {
+    counter = counter + amount;
+}
+void early(int amount) {
+    if (amount > 10)
+        return;
+    counter = counter + 100;
+}
+int main() {
+    int z = 1;
+    int neg = 0 - 1;
+    unsigned u = 5;
+    long wide = (z ? neg : u);
+    printf("%d %d\n", (z ? neg : u) > 0, (z ? neg : u) == 4294967295);
+    printf("%ld\n", wide);
+    z ? g(1) : g(2);
+    z = 0;
+    z ? g(1) : g(20);
+    z ? (void)0 : g(300);
+    early(50);
+    early(5);
+    printf("%d\n", counter);
+    return 0;
+}
@@ -0,0 +1,21 @@
+int printf(const char *format, ...);
+
+enum limits { FIRST = 1 << 2, LAST = FIRST * 3 - 1, COUNT = LAST - FIRST + 1 };
+
+_Static_assert(sizeof(long) == 8 && _Alignof(int) == 4, "LP64");
+_Static_assert(COUNT == 8, "enum arithmetic");
+
+struct header {
+    char kind;
+    long length;
+};
+
+int main() {
+    char small[(unsigned char)258];
+    int table[COUNT > 4 ? COUNT : 4][sizeof(struct header) / 8];
+    long wrapped[(unsigned)-1 / 1000000000];
+    _Static_assert(sizeof table == 64, "table size");
+    printf("%d %d %d\n", FIRST, LAST, COUNT);
+    printf("%d %d %d\n", (int)sizeof(small), (int)sizeof(table), (int)sizeof(wrapped));
+    return 0;
+}
@@ -0,0 +1,30 @@
+int printf(const char *format, ...);
+
+enum color { RED, GREEN = 5, BLUE, LAST = (BLUE) };
+
+typedef enum { SMALL = 2, LARGE = SMALL } size_kind;
+
+struct paint {
+    enum color color;
+    size_kind size;
+};
+
+
+int shade(enum color c) {
+    if (c == GREEN) {
+        return 10;
+    }
+    return 20;
+}
+
+int main() {
+    enum color c = BLUE;
+    struct paint p;
+    int table[BLUE];
+    p.color = GREEN;
+    p.size = LARGE;
+    table[GREEN] = shade(p.color);
+    printf("%d %d %d %d\n", RED, c, p.size, table[5]);
+    printf("%ld %ld %d\n", sizeof(table), sizeof(enum color), shade(RED));
+    return 0;
+}
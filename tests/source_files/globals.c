@@ -0,0 +1,31 @@
+int printf(const char *format, ...);
+
+int counter;
+long total = 40 + 2;
+static int hidden = -7;
+const char greeting[] = "hello";
+const char *message = "globals";
+int values[4];
+int *cursor = &values[2];
+char *end = (char *)&total + 8;
+
+struct point { int x; long y; };
+struct point origin;
+
+int next() {
+    static int calls = 100;
+    calls = calls + 1;
+    return calls;
+}
+
+int main() {
+    extern int counter;
+    counter = counter + 5;
+    values[2] = 9;
+    *cursor = *cursor + 1;
+    origin.y = 12;
+    printf("%d %ld %d %s %s\n", counter, total, hidden, greeting, message);
+    printf("%d %d %d %ld\n", values[2], next(), next(), (long)(end - (char *)&total));
+    printf("%d %ld %d\n", (int)sizeof(greeting), origin.y, origin.x);
+    return 0;
+}
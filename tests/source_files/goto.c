@@ -0,0 +1,46 @@
+int printf(const char *format, ...);
+
+int find_pair(int target) {
+    int i = 0;
+    int j = 0;
+    for (i = 0; i < 10; i++) {
+        for (j = 0; j < 10; j++) {
+            if (i * j == target)
+                goto found;
+        }
+    }
+    return 0 - 1;
+found:
+    return i * 100 + j;
+}
+
+int checked_sum(int count) {
+    int sum = 0;
+    int index = 0;
+    if (count < 0)
+        goto err;
+again:
+    if (index >= count)
+        goto done;
+    sum += index;
+    index++;
+    goto again;
+done:
+    return sum;
+err:
+    return 0 - 1;
+}
+
+int main() {
+    printf("%d %d %d\n", find_pair(12), find_pair(81), find_pair(97));
+    printf("%d %d %d\n", checked_sum(5), checked_sum(0), checked_sum(0 - 3));
+    int x = 0;
+    goto inside;
+    {
+        x = 100;
+    inside:
+        x += 1;
+    }
+    printf("%d\n", x);
+    return 0;
+}
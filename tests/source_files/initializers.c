@@ -0,0 +1,33 @@
+int printf(const char *format, ...);
+
+struct point { int x; int y; };
+struct line { struct point from; struct point to; char tag[4]; };
+union number { int whole; char bytes[4]; };
+
+struct line global_line = { {1, 2}, .to.y = 9, "ab" };
+int primes[] = { 2, 3, 5, [6] = 17, 19 };
+int grid[2][3] = { 1, 2, 3, {4} };
+char word[] = { "jank" };
+const char *names[] = { "zero", "one", [3] = "three" };
+union number global_number = { .bytes = { 1, 1 } };
+
+int main() {
+    struct line local_line = { .tag = "xyz", .from = { .y = 5 }, 7 };
+    int counts[5] = { [2] = 4, 8 };
+    long matrix[][2] = { { 1 }, 2, 3, [3][1] = 6 };
+    char text[8] = "hi";
+    struct point origin = {0};
+    struct point copy = origin;
+    int scalar = { 42 };
+    union number local_number = { 258 };
+
+    printf("%d %d %d %d %s\n", global_line.from.x, global_line.from.y, global_line.to.x, global_line.to.y, global_line.tag);
+    printf("%d %d %d %d\n", (int)sizeof(primes), primes[2], primes[6], primes[7]);
+    printf("%d %d %d %s %d\n", grid[0][2], grid[1][0], grid[1][1], word, (int)sizeof(word));
+    printf("%s %s %d %d\n", names[1], names[3], names[2] == 0, global_number.whole);
+    printf("%d %d %d %d %s\n", local_line.from.x, local_line.from.y, local_line.to.x, local_line.to.y, local_line.tag);
+    printf("%d %d %d %d\n", counts[1], counts[2], counts[3], counts[4]);
+    printf("%d %ld %ld %ld %ld\n", (int)sizeof(matrix), matrix[0][1], matrix[1][0], matrix[1][1], matrix[3][1]);
+    printf("%s %d %d %d %d %d\n", text, (int)text[5], copy.x, copy.y, scalar, (int)local_number.bytes[1]);
+    return 0;
+}
@@ -0,0 +1,27 @@
+int printf(const char *format, ...);
+
+/* K&R parameters are passed promoted, but have their declared type in the body */
+int sizes(c, d)
+char c;
+short d;
+{
+    return sizeof(c) * 10 + sizeof(d);
+}
+
+int narrow(c)
+char c;
+{
+    return c;
+}
+
+int wrap(s, n)
+short s;
+{
+    s = s + n;
+    return s;
+}
+
+int main() {
+    printf("%d %d %d\n", sizes(1, 2), narrow(300), wrap(65535, 32768));
+    return 0;
+}
@@ -0,0 +1,39 @@
+int printf(const char *format, ...);
+
+struct node { int value; struct node *next; };
+
+int calls;
+int touch(int value) { calls = calls + 1; return value; }
+
+int has_value(struct node *node) { return node && node->value; }
+int first_value(struct node *node) { return node ? node->value : 0 - 1; }
+
+int main() {
+    struct node tail = { 7, 0 };
+    struct node head = { 0, &tail };
+    struct node *none = 0;
+    char small = 3;
+    long big = 5;
+    const char *name = 0;
+
+    int a = touch(0) && touch(1);
+    int b = touch(2) || touch(3);
+    int c = touch(1) && touch(4) && touch(0) && touch(9);
+    printf("%d %d %d %d\n", a, b, c, calls);
+
+    printf("%d %d %d\n", has_value(&tail), has_value(&head), has_value(none));
+    printf("%d %d\n", first_value(head.next), first_value(none));
+
+    long mixed = small > 2 ? small : big;
+    int size = (int)sizeof(small ? small : big);
+    const char *text = name ? name : "fallback";
+    struct node picked = big > 9 ? head : tail;
+    printf("%ld %d %s %d\n", mixed, size, text, picked.value);
+
+    if (none && none->value) printf("unreachable\n");
+    if (!none || none->value) printf("guarded\n");
+    int nested = a ? 1 : b ? c ? 2 : 3 : 4;
+    printf("%d %d\n", nested, touch(5) ? touch(6) : touch(7));
+    printf("%d\n", calls);
+    return 0;
+}
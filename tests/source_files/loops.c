@@ -0,0 +1,56 @@
+int printf(const char *format, ...);
+
+int find(int *values, int count, int wanted) {
+    int index = 0;
+    while (index < count) {
+        if (values[index] == wanted)
+            return index;
+        index++;
+    }
+    return 0 - 1;
+}
+
+int main() {
+    int values[6] = { 4, 8, 15, 16, 23, 42 };
+    int sum = 0;
+    int i = 0;
+    while (i < 6) {
+        i++;
+        if (values[i - 1] % 2) continue;
+        sum += values[i - 1];
+    }
+    printf("%d %d\n", sum, i);
+
+    int n = 0;
+    do {
+        n += 3;
+    } while (n < 10);
+    int once = 0;
+    do once++; while (0);
+    printf("%d %d\n", n, once);
+
+    int pairs = 0;
+    for (int a = 0; a < 5; a++) {
+        if (a == 3) break;
+        for (int b = 0; ; b++) {
+            if (b > a) break;
+            if (b == 1) continue;
+            pairs += 10 * a + b;
+        }
+    }
+    printf("%d\n", pairs);
+
+    int skipped = 0;
+    int k = 0;
+    do {
+        k++;
+        if (k % 3 == 0) continue;
+        skipped += k;
+    } while (k < 9);
+    int spins = 0;
+    for (;;) {
+        if (++spins == 4) break;
+    }
+    printf("%d %d %d %d\n", skipped, spins, find(values, 6, 23), find(values, 6, 5));
+    return 0;
+}
@@ -0,0 +1,3 @@
+#include <nonexistent_header_for_jankcc.h>
+
+int main() { return 0; }
@@ -0,0 +1,23 @@
+int printf(const char *format, ...);
+
+/* arguments are converted to the parameter type, variadic ones are promoted */
+int next(c)
+char c;
+{
+    return c + 1;
+}
+
+long twice(long x) { return x * 2; }
+
+int as_short(short s) { return s; }
+
+int main() {
+    char c = 65;
+    unsigned char u = 255;
+    u += 1;
+    signed char m = 253;
+    int big = 70000;
+    printf("%d %ld %d\n", next(c), twice(c), as_short(big));
+    printf("%d %d %d\n", u, m, c);
+    return 0;
+}
@@ -0,0 +1,14 @@
+#include <stddef.h>
+int printf(const char *format, ...);
+void *malloc(unsigned long size);
+int main() {
+    int *p = NULL;
+    int *q = (int *)0;
+    void *v = (void *)0;
+    long *l = (long *) malloc(sizeof(long));
+    *l = 300;
+    char c = (char) *l;
+    long back = (long) p;
+    printf("%d %d %d %d %d %d\n", p == NULL, q == 0, v == NULL, l != NULL, c, back);
+    return 0;
+}
@@ -0,0 +1,23 @@
+int printf(const char *format, ...);
+
+long distance(int *from, int *to) {
+    return to - from;
+}
+
+int main() {
+    int values[6];
+    for (int i = 0; i < 6; i = i + 1) {
+        values[i] = i * 3;
+    }
+    int *first = values;
+    int *last = values + 5;
+    int *middle = 2 + first;
+    int *null = 0;
+    char text[4];
+    char *end = text + 3;
+    int count = last - first;
+    printf("%d %d %d %ld\n", *middle, *(last - 1), count, distance(middle, last));
+    printf("%d %d %d %d\n", first < last, last <= middle, null == 0, middle != 0);
+    printf("%ld %d\n", end - text, *(values + 4) + values[1]);
+    return 0;
+}
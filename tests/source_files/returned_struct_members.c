@@ -0,0 +1,22 @@
+int printf(const char *format, ...);
+
+/* members of a returned struct, in registers and in memory */
+typedef struct {
+    int quot;
+    int rem;
+} div_t;
+div_t div(int numerator, int denominator);
+
+struct big {
+    long a, b, c;
+};
+
+struct big make(long x) {
+    struct big result = {x, x * 2, x * 3};
+    return result;
+}
+
+int main() {
+    printf("%d %d %ld %ld\n", div(9, 2).rem, div(9, 2).quot, make(5).c, make(1).a);
+    return 0;
+}
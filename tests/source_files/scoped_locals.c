@@ -0,0 +1,19 @@
+int printf(const char *format, ...);
+
+/* locals of sibling and nested blocks may share a name */
+int main() {
+    int total = 0;
+    for (int i = 0; i < 3; i++) {
+        total += i;
+    }
+    for (int i = 10; i < 12; i++) {
+        total += i;
+    }
+    int x = 1;
+    {
+        int x = 5;
+        total += x;
+    }
+    printf("%d %d\n", total, x);
+    return 0;
+}
@@ -0,0 +1,46 @@
+extern int printf (const char *restrict __format, ...);
+
+struct pair {
+    long a;
+    long b;
+};
+
+struct big {
+    char tag;
+    long x;
+    long y;
+};
+
+struct pair swap(struct pair p) {
+    struct pair swapped;
+    swapped.a = p.b;
+    swapped.b = p.a;
+    return swapped;
+}
+
+struct big make_big(char tag, long x) {
+    struct big b;
+    b.tag = tag;
+    b.x = x;
+    b.y = x + x;
+    return b;
+}
+
+long sum_big(struct big *out, struct big b) {
+    out->x = b.x + b.y;
+    return b.y + out->x;
+}
+
+int main() {
+    struct pair p;
+    p.a = 1;
+    p.b = 2;
+    struct pair q = swap(p);
+    struct big b = make_big(3, 20);
+    struct big copy;
+    copy = b;
+    b.x = 0;
+    long total = sum_big(&b, copy);
+    printf("%ld %ld %ld %ld\n", q.a, q.b, b.x, total);
+    return 0;
+}
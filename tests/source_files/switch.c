@@ -0,0 +1,92 @@
+int printf(const char *format, ...);
+
+int classify(int value) {
+    switch (value) {
+    case 0 - 3:
+        return 100;
+    case 1:
+    case 2:
+        return 12;
+    case 3:
+        value = value * 10;
+    case 4:
+        return value + 4;
+    default:
+        return 0;
+    case 7:
+        return 70;
+    }
+}
+
+int dense(unsigned char c) {
+    int result = 0;
+    switch (c) {
+    case 97: result = 1; break;
+    case 98: result = 2; break;
+    case 99: result = 3; break;
+    case 101: result = 5; break;
+    case 102: result = 6; break;
+    case 103: result = 7; break;
+    }
+    return result;
+}
+
+int sparse(long long value) {
+    switch (value) {
+    case 10: return 1;
+    case 1000: return 2;
+    case 100000: return 3;
+    case 10000000: return 4;
+    case 1000000000000: return 5;
+    case 0 - 5: return 6;
+    case 0 - 1000000000000: return 7;
+    default: return 0;
+    }
+}
+
+int main() {
+    printf("%d %d %d %d %d %d %d %d\n", classify(0 - 3), classify(1), classify(2), classify(3),
+           classify(4), classify(5), classify(7), classify(0 - 4));
+    printf("%d %d %d %d %d\n", dense(97), dense(99), dense(100), dense(103), dense(122));
+    long long big = 1000000000000;
+    long long minus_big = 0 - big;
+    printf("%d %d %d %d %d %d %d\n", sparse(10), sparse(100000), sparse(10000000), sparse(big),
+           sparse(0 - 5), sparse(minus_big), sparse(11));
+
+    int counts = 0;
+    for (int i = 0; i < 10; i++) {
+        switch (i % 4) {
+        case 0:
+            continue;
+        case 1:
+            counts += 1;
+            break;
+        default:
+            switch (i) {
+            case 2:
+                counts += 100;
+                break;
+            default:
+                counts += 10;
+            }
+        }
+        counts += 1000;
+    }
+    printf("%d\n", counts);
+
+    int n = 0;
+    int steps = 5;
+    switch (steps % 3) {
+        n = 99;
+    case 0:
+        do {
+            n += 1;
+    case 2:
+            n += 1;
+    case 1:
+            n += 1;
+        } while (--steps > 0);
+    }
+    printf("%d\n", n);
+    return 0;
+}
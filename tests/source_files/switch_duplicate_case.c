@@ -0,0 +1,10 @@
+int main() {
+    int x = 2;
+    switch (x) {
+    case 1:
+        return 1;
+    case 1 + 0:
+        return 2;
+    }
+    return 0;
+}
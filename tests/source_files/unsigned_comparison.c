@@ -0,0 +1,9 @@
+int printf(const char *format, ...);
+int main() {
+    unsigned x = 4000000000;
+    int neg = 0 - 1;
+    unsigned char c = 200;
+    if (x > 1) printf("if ok\n");
+    printf("%d %d %d %d %d\n", x > 1, 1 < x, x == 4000000000, neg < x, c > 100);
+    return 0;
+}